@@ -0,0 +1,82 @@
+// A small program using the neural network library as a dependency
+//
+// Trains a network on XOR. The activation and hidden layer size can be
+// chosen on the command line to show how the library is configured; the
+// defaults are a sigmoid network with three hidden neurons.
+
+use neural_network::activations::{Activation, SIGMOID, SOFTMAX};
+use neural_network::network::Network;
+
+/// Epochs the demo trains for
+pub const EPOCHS: u32 = 100000;
+
+/// The four XOR input rows
+pub const XOR_INPUTS: [[f64; 2]; 4] = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+
+/// XOR of each input row
+pub const XOR_TARGETS: [f64; 4] = [0.0, 1.0, 1.0, 0.0];
+
+/// Settings for the demo network
+#[derive(Debug, Clone, Copy)]
+pub struct DemoConfig {
+    pub activation: Activation,
+    pub hidden: usize,
+    pub learning_rate: f64,
+    /// Seed for the initial weights; random when `None`
+    pub seed: Option<u64>,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self {
+            activation: SIGMOID,
+            hidden: 3,
+            learning_rate: 0.5,
+            seed: None,
+        }
+    }
+}
+
+impl DemoConfig {
+    /// Read `[activation] [hidden_size]` from the command line arguments
+    /// after the program name, keeping the default for any left out
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::default();
+        if let Some(name) = args.first() {
+            config.activation = match Activation::from_name(name) {
+                Some(activation) if activation == SOFTMAX => {
+                    return Err("softmax needs more than one output neuron".to_string());
+                }
+                Some(activation) => activation,
+                None => return Err(format!("Unknown activation: {}", name)),
+            };
+        }
+        if let Some(hidden) = args.get(1) {
+            config.hidden = match hidden.parse() {
+                Ok(0) | Err(_) => return Err(format!("Invalid hidden layer size: {}", hidden)),
+                Ok(hidden) => hidden,
+            };
+        }
+        if args.len() > 2 {
+            return Err("Usage: consumer_binary [activation] [hidden_size]".to_string());
+        }
+        Ok(config)
+    }
+
+    /// Layer sizes of the demo network
+    pub fn layers(&self) -> Vec<usize> {
+        vec![2, self.hidden, 1]
+    }
+}
+
+/// Train a network with `config` on XOR for `epochs` epochs
+pub fn train_xor(config: &DemoConfig, epochs: u32) -> Network {
+    let mut network = match config.seed {
+        Some(seed) => Network::new_seeded(config.layers(), config.activation, config.learning_rate, seed),
+        None => Network::new(config.layers(), config.activation, config.learning_rate),
+    };
+    let inputs = XOR_INPUTS.iter().map(|row| row.to_vec()).collect();
+    let targets = XOR_TARGETS.iter().map(|&target| vec![target]).collect();
+    network.train(inputs, targets, epochs);
+    network
+}
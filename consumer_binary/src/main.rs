@@ -1,31 +1,31 @@
-use neural_network::network::Network;
-use neural_network::activations::SIGMOID;
-use neural_network::matrix::Matrix;
+use consumer_binary::{DemoConfig, EPOCHS, XOR_INPUTS, train_xor};
 use std::env;
+
 fn main() {
 	// SAFETY: This is safe because we're setting the environment variable
 	// at the start of main, before any threads are spawned.
 	unsafe {
 		env::set_var("RUST_BACKTRACE", "1");
 	}
-    let inputs = vec![
-		vec![0.0, 0.0],
-		vec![0.0, 1.0],
-		vec![1.0, 0.0],
-		vec![1.0, 1.0],
-	];
-	let targets = vec![vec![0.0], vec![1.0], vec![0.0], vec![1.0]];
-
-    let mut network = Network::new(vec![2,3,1],SIGMOID,0.5);
-   
 
-    network.train(inputs, targets, 100000);
+	let args: Vec<String> = env::args().skip(1).collect();
+	let config = match DemoConfig::from_args(&args) {
+		Ok(config) => config,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(2);
+		}
+	};
+	println!(
+		"Training {:?} with {} activation, learning rate {}",
+		config.layers(),
+		config.activation.name,
+		config.learning_rate
+	);
 
-	println!("{:?}", network.feed_forward(Matrix::from(vec![0.0, 0.0])));
-	println!("{:?}", network.feed_forward(Matrix::from(vec![0.0, 1.0])));
-	println!("{:?}", network.feed_forward(Matrix::from(vec![1.0, 0.0])));
-	println!("{:?}", network.feed_forward(Matrix::from(vec![1.0, 1.0])));
-
-    
+	let network = train_xor(&config, EPOCHS);
 
+	for input in XOR_INPUTS {
+		println!("{:?} -> {:?}", input, network.predict(&input).expect("XOR inputs are valid"));
+	}
 }
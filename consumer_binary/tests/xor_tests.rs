@@ -0,0 +1,38 @@
+// Tests for the consumer binary's XOR demo
+use consumer_binary::{DemoConfig, EPOCHS, XOR_INPUTS, XOR_TARGETS, train_xor};
+use neural_network::activations::{SIGMOID, TANH};
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_default_config_solves_xor() {
+    let config = DemoConfig {
+        seed: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(config.layers(), [2, 3, 1]);
+    assert_eq!(config.activation, SIGMOID);
+
+    let network = train_xor(&config, EPOCHS);
+
+    for (input, target) in XOR_INPUTS.iter().zip(XOR_TARGETS) {
+        let output = network.predict(input).unwrap();
+        assert!((output[0] - target).abs() < 0.1, "{:?} -> {:?}", input, output);
+    }
+}
+
+#[test]
+fn test_config_from_args() {
+    let config = DemoConfig::from_args(&[]).unwrap();
+    assert_eq!((config.activation, config.hidden), (SIGMOID, 3));
+
+    let config = DemoConfig::from_args(&args(&["tanh", "5"])).unwrap();
+    assert_eq!((config.activation, config.hidden), (TANH, 5));
+
+    assert!(DemoConfig::from_args(&args(&["swish"])).is_err());
+    assert!(DemoConfig::from_args(&args(&["softmax"])).is_err());
+    assert!(DemoConfig::from_args(&args(&["relu", "0"])).is_err());
+    assert!(DemoConfig::from_args(&args(&["relu", "4", "extra"])).is_err());
+}
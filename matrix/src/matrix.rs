@@ -3,12 +3,29 @@ use rand::Rng;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "SerializedMatrix")]
 pub struct Matrix {
     pub rows: usize,
     pub cols: usize,
     pub data: Vec<f64>
 }
 
+/// A matrix as stored, whose shape is checked before it becomes a `Matrix`
+#[derive(Deserialize)]
+struct SerializedMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl TryFrom<SerializedMatrix> for Matrix {
+    type Error = String;
+
+    fn try_from(stored: SerializedMatrix) -> Result<Self, String> {
+        Matrix::try_new(stored.rows, stored.cols, stored.data)
+    }
+}
+
 
 // access through  i* numofcols + j
 
@@ -45,11 +62,31 @@ impl Matrix {
 
     }
 
+    /// Like `random`, but draws values from the given generator so results
+    /// are reproducible with a seeded RNG
+    pub fn random_with_rng<R: Rng + ?Sized>(rows: usize, cols: usize, rng: &mut R) -> Matrix {
+        let data = (0..rows * cols).map(|_| rng.gen_range(0.0..1.0)).collect();
+
+        Matrix { rows, cols, data }
+    }
+
+    /// Panics if `data` doesn't hold exactly `rows * cols` values
     pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Matrix {
+        Matrix::try_new(rows, cols, data).unwrap_or_else(|e| panic!("Invalid Size: {}", e))
+    }
 
-        assert!(data.len()-1 != rows * cols, "Invalid Size");
-       Matrix { rows, cols, data }  
-        
+    /// Like `new`, but describes a size mismatch instead of panicking
+    pub fn try_new(rows: usize, cols: usize, data: Vec<f64>) -> Result<Matrix, String> {
+        match rows.checked_mul(cols) {
+            Some(len) if len == data.len() => Ok(Matrix { rows, cols, data }),
+            _ => Err(format!(
+                "A {} x {} matrix needs {} values, got {}",
+                rows,
+                cols,
+                rows.saturating_mul(cols),
+                data.len()
+            )),
+        }
     }
    
     pub fn zeros(rows:usize, cols:usize) -> Matrix {
@@ -150,7 +187,7 @@ impl Matrix {
         }
     }
 
-    pub fn map<F>(&mut self, func: F) -> Matrix
+    pub fn map<F>(&self, func: F) -> Matrix
     where
         F: Fn(&f64) -> f64,
     {
@@ -165,6 +202,60 @@ impl Matrix {
         result
     }
 
+    /// Mean of all entries
+    pub fn mean(&self) -> f64 {
+        self.data.iter().sum::<f64>() / self.data.len() as f64
+    }
+
+    /// Population standard deviation of all entries
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / self.data.len() as f64;
+        variance.sqrt()
+    }
+
+    /// Smallest entry
+    pub fn min(&self) -> f64 {
+        self.data.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    /// Largest entry
+    pub fn max(&self) -> f64 {
+        self.data.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Frobenius (L2) norm: the square root of the sum of squared entries
+    pub fn norm(&self) -> f64 {
+        self.data.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    /// Iterate over the rows, each as a slice of `cols` entries
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[f64]> {
+        // Sliced by index rather than chunks_exact, which can't yield the
+        // empty rows of a matrix with no columns
+        (0..self.rows).map(move |i| &self.data[i * self.cols..(i + 1) * self.cols])
+    }
+
+    /// Copy of column `j`
+    ///
+    /// Panics if `j` is out of bounds.
+    pub fn col(&self, j: usize) -> Vec<f64> {
+        assert!(j < self.cols, "Column {} out of bounds for {} columns", j, self.cols);
+        self.rows_iter().map(|row| row[j]).collect()
+    }
+
+    /// The same entries, in row-major order, as a `rows` x `cols` matrix
+    ///
+    /// Fails if the matrix doesn't hold exactly `rows * cols` entries.
+    pub fn reshape(&self, rows: usize, cols: usize) -> Result<Matrix, String> {
+        Matrix::try_new(rows, cols, self.data.clone()).map_err(|e| {
+            format!(
+                "Cannot reshape a {} x {} matrix: {}",
+                self.rows, self.cols, e
+            )
+        })
+    }
+
 
 }
 impl From<Vec<f64>> for Matrix {
@@ -187,9 +278,9 @@ impl PartialEq for Matrix {
 
 impl fmt::Display for Matrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                write!(f, "{}", self.data[row * self.cols + col])?;
+        for row in self.rows_iter() {
+            for (col, value) in row.iter().enumerate() {
+                write!(f, "{}", value)?;
                 if col < self.cols - 1 {
                     write!(f, "\t")?; // Separate columns with a tab
                 }
@@ -375,9 +466,50 @@ mod tests {
         assert_eq!(transposed, expected);
     }
 
+    #[test]
+    fn test_statistics() {
+        let matrix = matrix![
+            1.0, -2.0;
+            3.0, 2.0
+        ];
+
+        assert_eq!(matrix.mean(), 1.0);
+        assert_eq!(matrix.std_dev(), 3.5f64.sqrt());
+        assert_eq!(matrix.min(), -2.0);
+        assert_eq!(matrix.max(), 3.0);
+        assert_eq!(matrix.norm(), 18.0f64.sqrt());
+    }
+
+    #[test]
+    fn test_rows_iter() {
+        let matrix = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let rows: Vec<&[f64]> = matrix.rows_iter().collect();
+
+        assert_eq!(rows, vec![&[1.0, 2.0, 3.0][..], &[4.0, 5.0, 6.0][..]]);
+
+        let empty_rows = Matrix::new(3, 0, vec![]);
+        let rows: Vec<&[f64]> = empty_rows.rows_iter().collect();
+        assert_eq!(rows, vec![&[][..]; 3]);
+    }
+
+    #[test]
+    fn test_col() {
+        let matrix = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        assert_eq!(matrix.col(0), vec![1.0, 4.0]);
+        assert_eq!(matrix.col(2), vec![3.0, 6.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_col_out_of_bounds() {
+        Matrix::new(2, 3, vec![0.0; 6]).col(3);
+    }
+
     #[test]
     fn test_map_add_one() {
-        let mut matrix = Matrix {
+        let matrix = Matrix {
             rows: 2,
             cols: 2,
             data: vec![1.0, 2.0, 3.0, 4.0],
@@ -396,7 +528,7 @@ mod tests {
 
     #[test]
     fn test_map_square() {
-        let mut matrix = Matrix {
+        let matrix = Matrix {
             rows: 2,
             cols: 2,
             data: vec![1.0, 2.0, 3.0, 4.0],
@@ -412,4 +544,42 @@ mod tests {
 
         assert_eq!(transformed, expected);
     }
+
+    #[test]
+    fn test_try_new_checks_size() {
+        assert_eq!(Matrix::try_new(2, 0, vec![]).unwrap().data.len(), 0);
+        assert_eq!(
+            Matrix::try_new(2, 2, vec![1.0; 3]).unwrap_err(),
+            "A 2 x 2 matrix needs 4 values, got 3"
+        );
+        assert!(Matrix::try_new(usize::MAX, 2, vec![]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Size")]
+    fn test_new_rejects_wrong_size() {
+        Matrix::new(2, 2, vec![1.0; 5]);
+    }
+
+    #[test]
+    fn test_reshape() {
+        let matrix = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let reshaped = matrix.reshape(3, 2).unwrap();
+        assert_eq!((reshaped.rows, reshaped.cols), (3, 2));
+        assert_eq!(reshaped.data, matrix.data);
+        assert_eq!(reshaped.col(1), [2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_reshape_rejects_incompatible_dimensions() {
+        let matrix = Matrix::new(2, 3, vec![1.0; 6]);
+
+        let error = matrix.reshape(4, 2).unwrap_err();
+        assert_eq!(
+            error,
+            "Cannot reshape a 2 x 3 matrix: A 4 x 2 matrix needs 8 values, got 6"
+        );
+        assert!(matrix.reshape(usize::MAX, 2).is_err());
+    }
 }
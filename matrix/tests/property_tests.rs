@@ -0,0 +1,58 @@
+// Property-based tests for matrix operations and serialization
+use matrix::matrix::Matrix;
+use proptest::prelude::*;
+
+const MAX_SIZE: usize = 32;
+
+fn finite() -> impl Strategy<Value = f64> {
+    any::<f64>().prop_filter("finite", |x| x.is_finite())
+}
+
+fn matrix_of(rows: usize, cols: usize) -> impl Strategy<Value = Matrix> {
+    prop::collection::vec(finite(), rows * cols).prop_map(move |data| Matrix::new(rows, cols, data))
+}
+
+/// Matrices of up to 32 x 32 finite values, including empty ones
+fn matrix() -> impl Strategy<Value = Matrix> {
+    (0..=MAX_SIZE, 0..=MAX_SIZE).prop_flat_map(|(rows, cols)| matrix_of(rows, cols))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn transpose_twice_is_identity(a in matrix()) {
+        prop_assert_eq!(a.transpose().transpose(), a);
+    }
+
+    #[test]
+    fn dot_multiply_has_outer_dimensions(
+        (a, b) in (1..=MAX_SIZE, 1..=MAX_SIZE, 1..=MAX_SIZE)
+            .prop_flat_map(|(rows, inner, cols)| (matrix_of(rows, inner), matrix_of(inner, cols)))
+    ) {
+        let product = a.dot_multiply(&b);
+
+        prop_assert_eq!((product.rows, product.cols), (a.rows, b.cols));
+        prop_assert_eq!(product.data.len(), a.rows * b.cols);
+    }
+
+    #[test]
+    fn json_round_trip_is_exact(a in matrix()) {
+        let json = serde_json::to_string(&a).unwrap();
+
+        prop_assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), a);
+    }
+
+    #[test]
+    fn mismatched_shapes_fail_to_deserialize(
+        rows in 0..=MAX_SIZE,
+        cols in 0..=MAX_SIZE,
+        data in prop::collection::vec(finite(), 0..=MAX_SIZE),
+    ) {
+        prop_assume!(rows * cols != data.len());
+        let json = serde_json::json!({ "rows": rows, "cols": cols, "data": data });
+
+        prop_assert!(serde_json::from_value::<Matrix>(json).is_err());
+        prop_assert!(Matrix::try_new(rows, cols, data).is_err());
+    }
+}
@@ -156,10 +156,8 @@ fn generate_svg(network: &neural_network::network::Network, args: &Args) -> Resu
             }
         }
 
-        for from_idx in 0..from_layer.len() {
-            for to_idx in 0..to_layer.len() {
-                let (x1, y1) = from_layer[from_idx];
-                let (x2, y2) = to_layer[to_idx];
+        for (from_idx, &(x1, y1)) in from_layer.iter().enumerate() {
+            for (to_idx, &(x2, y2)) in to_layer.iter().enumerate() {
 
                 // Get weight value (weights are stored as [to_neurons x from_neurons])
                 let weight = weights.data[to_idx * weights.cols + from_idx];
@@ -210,7 +208,7 @@ fn generate_svg(network: &neural_network::network::Network, args: &Args) -> Resu
 
     // Draw neurons
     svg.push_str("<!-- Neurons -->\n");
-    let _layer_names = vec!["Input", "Hidden", "Hidden", "Hidden", "Hidden", "Output"];
+    let _layer_names = ["Input", "Hidden", "Hidden", "Hidden", "Hidden", "Output"];
     for (layer_idx, layer_neurons) in neuron_positions.iter().enumerate() {
         let layer_name = if layer_idx == 0 {
             "Input"
@@ -2,7 +2,8 @@
 ///
 /// Command-line interface for training and evaluating neural networks
 /// on classic logic gate problems (AND, OR, XOR).
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use neural_network::checkpoint::CheckpointFormat;
 
 #[derive(Parser)]
 #[command(name = "neural-net")]
@@ -11,6 +12,25 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Suppress progress bars and status messages; only results and errors are printed
+    #[arg(short, long, global = true)]
+    quiet: bool,
+}
+
+/// How much a command prints besides its results
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    /// Results and errors only
+    Quiet,
+    /// Progress bars and status messages as well
+    Normal,
+}
+
+impl Verbosity {
+    fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
 }
 
 #[derive(Subcommand)]
@@ -35,6 +55,30 @@ enum Commands {
         /// Output file path for trained model
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Checkpoint format (json, bin, json-gz); inferred from the output extension if omitted
+        #[arg(long)]
+        format: Option<CheckpointFormat>,
+
+        /// Also write the output file every N epochs during training, not just at the end
+        #[arg(long, value_name = "N", requires = "output", value_parser = clap::value_parser!(u32).range(1..))]
+        checkpoint_every: Option<u32>,
+
+        /// Seed for reproducible weight initialization
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// How to report progress: an interactive bar, or one JSON object per epoch on stdout
+        #[arg(long, value_enum, default_value = "bar")]
+        progress: ProgressFormat,
+
+        /// Report a moving average of the loss with this smoothing factor (0 < ALPHA <= 1)
+        #[arg(long, value_name = "ALPHA")]
+        loss_ema: Option<f64>,
+
+        /// Write the per-epoch loss history to this file as a JSON array
+        #[arg(long, value_name = "FILE")]
+        history_out: Option<String>,
     },
 
     /// Resume training from a checkpoint
@@ -50,6 +94,10 @@ enum Commands {
         /// Output file path for updated model
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Checkpoint format (json, bin, json-gz); inferred from the output extension if omitted
+        #[arg(long)]
+        format: Option<CheckpointFormat>,
     },
 
     /// Evaluate a trained model
@@ -59,20 +107,124 @@ enum Commands {
         model: String,
 
         /// Input values (comma-separated)
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "inputs")]
         input: Option<String>,
+
+        /// File of inputs to evaluate in a batch, one comma-separated row per line
+        #[arg(long, value_name = "FILE")]
+        inputs: Option<String>,
+
+        /// File of labels, one per input row, to prefix each batch output line with
+        #[arg(long, value_name = "FILE", requires = "inputs")]
+        labels: Option<String>,
+
+        /// Decimal places of the outputs in the human-readable report
+        #[arg(long, value_name = "N", default_value = "4")]
+        precision: usize,
     },
 
     /// Display detailed model information
     Info {
         /// Path to model file
+        #[arg(short, long, required_unless_present = "dir", conflicts_with = "dir")]
+        model: Option<String>,
+
+        /// Directory of checkpoints to summarize, one line per model
+        #[arg(long)]
+        dir: Option<String>,
+    },
+
+    /// Convert a checkpoint between formats (json, bin, json-gz)
+    Convert {
+        /// Checkpoint to read, in any format
         #[arg(short, long)]
-        model: String,
+        input: String,
+
+        /// File to write
+        #[arg(short, long)]
+        output: String,
+
+        /// Checkpoint format (json, bin, json-gz); inferred from the output extension if omitted
+        #[arg(long)]
+        format: Option<CheckpointFormat>,
     },
+
+    /// Search for a good learning rate and hidden layer size by random sampling
+    Search {
+        /// Example to tune (and, or, xor)
+        #[arg(short, long)]
+        example: String,
+
+        /// Number of configurations to try
+        #[arg(long, default_value = "20", value_parser = clap::value_parser!(u32).range(1..))]
+        trials: u32,
+
+        /// Training epochs per trial
+        #[arg(short = 'n', long, default_value = "2000")]
+        epochs: u32,
+
+        /// Smallest learning rate to sample
+        #[arg(long, default_value = "0.01")]
+        min_lr: f64,
+
+        /// Largest learning rate to sample
+        #[arg(long, default_value = "2.0")]
+        max_lr: f64,
+
+        /// Largest hidden layer size to sample (from 1)
+        #[arg(long, default_value = "16", value_parser = clap::value_parser!(u64).range(1..))]
+        max_hidden: u64,
+
+        /// Fraction of the samples held out to score each trial
+        #[arg(long, default_value = "0.25")]
+        validation_split: f64,
+
+        /// Seed for sampling configurations and initializing weights
+        #[arg(long, default_value = "0")]
+        seed: u64,
+    },
+
+    /// Measure training or inference speed
+    Bench {
+        /// What to measure
+        #[arg(long, value_enum, default_value = "training")]
+        mode: BenchMode,
+
+        /// Path to trained model file (inference mode)
+        #[arg(short, long, required_if_eq("mode", "inference"))]
+        model: Option<String>,
+
+        /// Example to train on (training mode)
+        #[arg(short, long, default_value = "xor")]
+        example: String,
+
+        /// Number of epochs (training mode) or inferences (inference mode) to time
+        #[arg(long, default_value = "1000")]
+        iterations: u32,
+    },
+}
+
+/// How `train` reports progress
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ProgressFormat {
+    /// Progress bar with the current loss
+    Bar,
+    /// Newline-delimited JSON objects `{"epoch":n,"loss":x}`, for wrapping tools
+    Json,
+}
+
+/// What the `bench` command measures
+#[derive(Clone, Copy, ValueEnum)]
+enum BenchMode {
+    /// Epochs per second when training an example
+    Training,
+    /// Feed-forward passes per second through a trained model
+    Inference,
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let verbosity = if cli.quiet { Verbosity::Quiet } else { Verbosity::Normal };
 
     match cli.command {
         Commands::List => {
@@ -83,22 +235,80 @@ fn main() -> anyhow::Result<()> {
             epochs,
             learning_rate,
             output,
+            format,
+            checkpoint_every,
+            seed,
+            progress,
+            loss_ema,
+            history_out,
         } => {
-            cmd_train(&example, epochs, learning_rate, output)?;
+            cmd_train(
+                &example,
+                epochs,
+                learning_rate,
+                output,
+                format,
+                checkpoint_every,
+                seed,
+                progress,
+                loss_ema,
+                history_out,
+                verbosity,
+            )?;
         }
         Commands::Resume {
             checkpoint,
             epochs,
             output,
+            format,
         } => {
-            cmd_resume(&checkpoint, epochs, output)?;
+            cmd_resume(&checkpoint, epochs, output, format, verbosity)?;
         }
-        Commands::Eval { model, input } => {
-            cmd_eval(&model, input)?;
+        Commands::Eval {
+            model,
+            input,
+            inputs,
+            labels,
+            precision,
+        } => match inputs {
+            Some(inputs) => cmd_eval_batch(&model, &inputs, labels.as_deref())?,
+            None => cmd_eval(&model, input, precision, verbosity)?,
+        },
+        Commands::Info { model, dir } => match dir {
+            Some(dir) => cmd_info_dir(&dir)?,
+            // clap requires --model without --dir
+            None => cmd_info(&model.unwrap_or_default())?,
+        },
+        Commands::Convert { input, output, format } => {
+            cmd_convert(&input, &output, format, verbosity)?;
         }
-        Commands::Info { model } => {
-            cmd_info(&model)?;
+        Commands::Search {
+            example,
+            trials,
+            epochs,
+            min_lr,
+            max_lr,
+            max_hidden,
+            validation_split,
+            seed,
+        } => {
+            let space = SearchSpace {
+                min_lr,
+                max_lr,
+                max_hidden: max_hidden as usize,
+            };
+            cmd_search(&example, trials, epochs, &space, validation_split, seed)?;
         }
+        Commands::Bench {
+            mode,
+            model,
+            example,
+            iterations,
+        } => match mode {
+            BenchMode::Training => cmd_bench_training(&example, iterations)?,
+            // clap requires --model in inference mode
+            BenchMode::Inference => cmd_bench_inference(&model.unwrap_or_default(), iterations)?,
+        },
     }
 
     Ok(())
@@ -120,11 +330,19 @@ fn cmd_list() -> anyhow::Result<()> {
 }
 
 /// Train a neural network
+#[allow(clippy::too_many_arguments)]
 fn cmd_train(
     example: &str,
     epochs: u32,
     learning_rate: f64,
     output: Option<String>,
+    format: Option<CheckpointFormat>,
+    checkpoint_every: Option<u32>,
+    seed: Option<u64>,
+    progress: ProgressFormat,
+    loss_ema: Option<f64>,
+    history_out: Option<String>,
+    verbosity: Verbosity,
 ) -> anyhow::Result<()> {
     use indicatif::{ProgressBar, ProgressStyle};
     use neural_network::{activations::SIGMOID, examples, network::Network, training::{TrainingConfig, TrainingController}};
@@ -134,29 +352,46 @@ fn cmd_train(
     let ex = examples::get_example(example)
         .ok_or_else(|| anyhow::anyhow!("Unknown example: {}. Use 'list' to see available examples.", example))?;
 
-    println!("Training {} network", ex.name);
-    println!("Architecture: {:?}", ex.recommended_arch);
-    println!("Epochs: {}", epochs);
-    println!("Learning rate: {}", learning_rate);
-    println!();
+    // JSON progress owns stdout, so status messages are left out
+    let json_progress = progress == ProgressFormat::Json;
+    let verbosity = if json_progress { Verbosity::Quiet } else { verbosity };
+
+    if !verbosity.is_quiet() {
+        println!("Training {} network", ex.name);
+        println!("Architecture: {:?}", ex.recommended_arch);
+        println!("Epochs: {}", epochs);
+        println!("Learning rate: {}", learning_rate);
+        println!();
+    }
 
     // Create network with recommended architecture
-    let network = Network::new(ex.recommended_arch.clone(), SIGMOID, learning_rate);
+    let network = match seed {
+        Some(seed) => Network::new_seeded(ex.recommended_arch.clone(), SIGMOID, learning_rate, seed),
+        None => Network::new(ex.recommended_arch.clone(), SIGMOID, learning_rate),
+    };
 
     // Create training config
     let config = TrainingConfig {
         epochs,
-        checkpoint_interval: if output.is_some() { Some(epochs) } else { None },
+        // The output file is rewritten at each checkpoint and after the last epoch
+        checkpoint_interval: output.as_ref().map(|_| checkpoint_every.unwrap_or(epochs)),
         checkpoint_path: output.as_ref().map(|p| Path::new(p).to_path_buf()),
         verbose: false,
         example_name: Some(ex.name.to_string()),
+        checkpoint_format: output_format(output.as_deref(), format),
+        loss_ema,
+        ..Default::default()
     };
 
     // Create training controller
     let mut controller = TrainingController::new(network, config);
 
     // Setup progress bar
-    let pb = ProgressBar::new(epochs as u64);
+    let pb = if verbosity.is_quiet() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(epochs as u64)
+    };
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
@@ -167,9 +402,20 @@ fn cmd_train(
 
     // Add progress callback (clone pb for the closure)
     let pb_clone = pb.clone();
-    controller.add_callback(Box::new(move |epoch, loss, _network| {
+    controller.add_progress_callback(Box::new(move |progress, _network| {
+        let epoch = progress.epoch;
+        if json_progress {
+            let mut event = serde_json::json!({ "epoch": epoch, "loss": progress.loss });
+            if let Some(ema) = progress.loss_ema {
+                event["loss_ema"] = ema.into();
+            }
+            println!("{}", event);
+            return;
+        }
         pb_clone.set_position(epoch as u64);
         if epoch % 100 == 0 || epoch == 1 {
+            // Show the smoothed loss when there is one
+            let loss = progress.loss_ema.unwrap_or(progress.loss);
             pb_clone.set_message(format!("Training (loss: {:.6})", loss));
         }
     }));
@@ -178,8 +424,17 @@ fn cmd_train(
     controller.train(ex.inputs.clone(), ex.targets.clone())?;
     pb.finish_with_message("Training complete!");
 
+    if let Some(history_path) = history_out {
+        controller.save_history(Path::new(&history_path))?;
+        if !verbosity.is_quiet() {
+            println!("Training history written to: {}", history_path);
+        }
+    }
+
     // Save model if output path specified
-    if let Some(output_path) = output {
+    if let Some(output_path) = output
+        && !verbosity.is_quiet()
+    {
         println!();
         println!("Saving model to: {}", output_path);
         println!("Model saved successfully!");
@@ -188,26 +443,43 @@ fn cmd_train(
     Ok(())
 }
 
+/// Pick the checkpoint format: an explicit `--format` wins, otherwise infer it
+/// from the output file extension
+fn output_format(output: Option<&str>, format: Option<CheckpointFormat>) -> CheckpointFormat {
+    format.unwrap_or_else(|| {
+        output
+            .map(|path| CheckpointFormat::from_path(std::path::Path::new(path)))
+            .unwrap_or_default()
+    })
+}
+
 /// Resume training from a checkpoint
-fn cmd_resume(checkpoint: &str, epochs: u32, output: Option<String>) -> anyhow::Result<()> {
+fn cmd_resume(
+    checkpoint: &str,
+    epochs: u32,
+    output: Option<String>,
+    format: Option<CheckpointFormat>,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
     use neural_network::{network::Network, training::{TrainingConfig, TrainingController}};
     use std::path::Path;
 
     let checkpoint_path = Path::new(checkpoint);
 
-    println!("Resuming training from checkpoint: {}", checkpoint);
-    println!("Additional epochs: {}", epochs);
-    println!();
-
     // Load checkpoint to get training data info
-    let (network, metadata) = Network::load_checkpoint(checkpoint_path)?;
+    let (network, metadata) = Network::load_checkpoint_auto(checkpoint_path)?;
 
-    println!("Loaded checkpoint:");
-    println!("  Architecture: {:?}", network.layers);
-    println!("  Previous epochs: {}", metadata.epoch);
-    println!("  Example: {}", metadata.example);
-    println!("  Learning rate: {}", metadata.learning_rate);
-    println!();
+    if !verbosity.is_quiet() {
+        println!("Resuming training from checkpoint: {}", checkpoint);
+        println!("Additional epochs: {}", epochs);
+        println!();
+        println!("Loaded checkpoint:");
+        println!("  Architecture: {:?}", network.layers);
+        println!("  Previous epochs: {}", metadata.epoch);
+        println!("  Example: {}", metadata.example);
+        println!("  Learning rate: {}", metadata.learning_rate);
+        println!();
+    }
 
     // Get training data from example
     use neural_network::examples;
@@ -221,17 +493,25 @@ fn cmd_resume(checkpoint: &str, epochs: u32, output: Option<String>) -> anyhow::
         checkpoint_path: output.as_ref().map(|p| Path::new(p).to_path_buf()),
         verbose: false,
         example_name: Some(metadata.example.clone()),
+        checkpoint_format: output_format(output.as_deref(), format),
+        ..Default::default()
     };
 
     // Resume training
     let mut controller = TrainingController::from_checkpoint(checkpoint_path, config)?;
 
-    println!("Resuming training...");
+    if !verbosity.is_quiet() {
+        println!("Resuming training...");
+    }
     controller.train(ex.inputs.clone(), ex.targets.clone())?;
-    println!("Training complete!");
+    if !verbosity.is_quiet() {
+        println!("Training complete!");
+    }
 
     // Save if output specified
-    if let Some(output_path) = output {
+    if let Some(output_path) = output
+        && !verbosity.is_quiet()
+    {
         println!();
         println!("Model saved to: {}", output_path);
     }
@@ -240,51 +520,45 @@ fn cmd_resume(checkpoint: &str, epochs: u32, output: Option<String>) -> anyhow::
 }
 
 /// Evaluate a trained model
-fn cmd_eval(model: &str, input: Option<String>) -> anyhow::Result<()> {
+///
+/// In quiet mode only the output values are printed, comma-separated.
+fn cmd_eval(model: &str, input: Option<String>, precision: usize, verbosity: Verbosity) -> anyhow::Result<()> {
     use neural_network::network::Network;
     use std::path::Path;
 
     let model_path = Path::new(model);
 
     // Load model
-    let (mut network, metadata) = Network::load_checkpoint(model_path)?;
+    let (network, metadata) = Network::load_checkpoint_auto(model_path)?;
 
     // Display model info
-    println!("Loaded model: {}", model);
-    println!("  Example: {}", metadata.example);
-    println!("  Architecture: {:?}", network.layers);
-    println!("  Training epochs: {}", metadata.epoch);
-    println!("  Learning rate: {}", metadata.learning_rate);
-    println!();
+    if !verbosity.is_quiet() {
+        println!("Loaded model: {}", model);
+        println!("  Example: {}", metadata.example);
+        println!("  Architecture: {:?}", network.layers);
+        println!("  Training epochs: {}", metadata.epoch);
+        println!("  Learning rate: {}", metadata.learning_rate);
+        println!();
+    }
 
     // Parse input if provided
     if let Some(input_str) = input {
-        let inputs: Result<Vec<f64>, _> = input_str
-            .split(',')
-            .map(|s| s.trim().parse::<f64>())
-            .collect();
+        let inputs = parse_input(&input_str)?;
 
-        let inputs = inputs.map_err(|e| {
-            anyhow::anyhow!("Invalid input format: {}. Expected comma-separated numbers (e.g., '0.0,1.0')", e)
-        })?;
-
-        // Validate input dimensions
-        if inputs.len() != network.layers[0] {
-            anyhow::bail!(
-                "Invalid input dimensions: expected {} inputs, got {}",
-                network.layers[0],
-                inputs.len()
-            );
-        }
-
-        // Run prediction
-        let input_matrix = neural_network::matrix::Matrix::from(inputs.clone());
-        let output = network.feed_forward(input_matrix);
+        // Run prediction, which rejects wrong input sizes and NaN or infinite values
+        let output = network.predict(&inputs)?;
 
         // Display results
-        println!("Input: {:?}", inputs);
-        println!("Output: {:?}", output.data);
-    } else {
+        if verbosity.is_quiet() {
+            let values: Vec<String> = output.iter().map(|v| v.to_string()).collect();
+            println!("{}", values.join(","));
+        } else {
+            // Quiet output above keeps full precision for scripts
+            let values: Vec<String> = output.iter().map(|v| format!("{:.*}", precision, v)).collect();
+            println!("Input: {:?}", inputs);
+            println!("Output: [{}]", values.join(", "));
+        }
+    } else if !verbosity.is_quiet() {
         println!("No input provided. Use --input <values> to make a prediction.");
         println!("Example: --input 0.0,1.0");
     }
@@ -292,6 +566,70 @@ fn cmd_eval(model: &str, input: Option<String>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse comma-separated input values
+fn parse_input(input: &str) -> anyhow::Result<Vec<f64>> {
+    input
+        .split(',')
+        .map(|s| s.trim().parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|e| {
+            anyhow::anyhow!("Invalid input format: {}. Expected comma-separated numbers (e.g., '0.0,1.0')", e)
+        })
+}
+
+/// Evaluate every row of an inputs file, printing one output line per row
+///
+/// Blank lines are skipped in both files. With a labels file, each output
+/// line starts with the label of its row.
+fn cmd_eval_batch(model: &str, inputs: &str, labels: Option<&str>) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use neural_network::network::Network;
+    use std::path::Path;
+
+    let (network, _) = Network::load_checkpoint_auto(Path::new(model))?;
+    let read_lines = |path: &str| -> anyhow::Result<Vec<String>> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+        Ok(text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect())
+    };
+
+    let rows = read_lines(inputs)?;
+    let labels = match labels {
+        Some(path) => {
+            let labels = read_lines(path)?;
+            if labels.len() != rows.len() {
+                anyhow::bail!(
+                    "{} has {} labels, but {} has {} input rows",
+                    path,
+                    labels.len(),
+                    inputs,
+                    rows.len()
+                );
+            }
+            Some(labels)
+        }
+        None => None,
+    };
+
+    for (i, row) in rows.iter().enumerate() {
+        let input = parse_input(row).with_context(|| format!("Row {} of {}", i + 1, inputs))?;
+        let output = network
+            .predict(&input)
+            .with_context(|| format!("Row {} of {}", i + 1, inputs))?;
+        let values: Vec<String> = output.iter().map(|v| v.to_string()).collect();
+        match &labels {
+            Some(labels) => println!("{}: {}", labels[i], values.join(",")),
+            None => println!("{}", values.join(",")),
+        }
+    }
+
+    Ok(())
+}
+
 /// Display detailed model information
 fn cmd_info(model: &str) -> anyhow::Result<()> {
     use neural_network::network::Network;
@@ -300,7 +638,7 @@ fn cmd_info(model: &str) -> anyhow::Result<()> {
     let model_path = Path::new(model);
 
     // Load model
-    let (network, metadata) = Network::load_checkpoint(model_path)?;
+    let (network, metadata) = Network::load_checkpoint_auto(model_path)?;
 
     // Display header
     println!("Model Information");
@@ -317,6 +655,21 @@ fn cmd_info(model: &str) -> anyhow::Result<()> {
     println!("  Timestamp: {}", metadata.timestamp);
     println!();
 
+    // Display resumed sessions, oldest first
+    if !metadata.history.is_empty() {
+        println!("Session History:");
+        for (i, session) in metadata.history.iter().enumerate() {
+            println!(
+                "  Session {}: {} epochs at learning rate {} ({})",
+                i + 1,
+                session.epochs,
+                session.learning_rate,
+                session.timestamp
+            );
+        }
+        println!();
+    }
+
     // Display architecture
     println!("Architecture:");
     println!("  Layers: {:?}", network.layers);
@@ -330,11 +683,11 @@ fn cmd_info(model: &str) -> anyhow::Result<()> {
     // Display weight matrices
     println!("Weights:");
     let mut total_params = 0;
-    for (i, weight) in network.weights.iter().enumerate() {
-        let params = weight.rows * weight.cols;
+    for (i, (rows, cols)) in network.layer_shapes().into_iter().enumerate() {
+        let params = rows * cols;
         total_params += params;
         println!("  Layer {} -> {}: {}x{} ({} parameters)",
-            i, i + 1, weight.rows, weight.cols, params);
+            i, i + 1, rows, cols, params);
     }
     println!();
 
@@ -353,3 +706,214 @@ fn cmd_info(model: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Print a one-line summary of every checkpoint in `dir`, sorted by filename
+///
+/// Files that can't be loaded as checkpoints are skipped with a warning.
+fn cmd_info_dir(dir: &str) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use neural_network::network::Network;
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Failed to read directory {}", dir))? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    println!(
+        "{:<24} {:<12} {:>8} {:<16} {:>10}",
+        "File", "Example", "Epochs", "Architecture", "Parameters"
+    );
+    for path in paths {
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let (network, metadata) = match Network::load_checkpoint_auto(&path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("Warning: skipping {}: {:#}", file, e);
+                continue;
+            }
+        };
+
+        let architecture = network
+            .layers
+            .iter()
+            .map(|size| size.to_string())
+            .collect::<Vec<_>>()
+            .join("-");
+        let parameters: usize = network.layer_shapes().iter().map(|(rows, cols)| rows * cols).sum::<usize>()
+            + network.biases.iter().map(|bias| bias.rows).sum::<usize>();
+        println!(
+            "{:<24} {:<12} {:>8} {:<16} {:>10}",
+            file, metadata.example, metadata.epoch, architecture, parameters
+        );
+    }
+
+    Ok(())
+}
+
+/// Convert a checkpoint to another format, keeping its metadata and any
+/// optimizer state
+fn cmd_convert(
+    input: &str,
+    output: &str,
+    format: Option<CheckpointFormat>,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
+    use neural_network::checkpoint::Checkpoint;
+    use std::path::Path;
+
+    let checkpoint = Checkpoint::load(Path::new(input))?;
+    let format = output_format(Some(output), format);
+    checkpoint.save(Path::new(output), format)?;
+
+    if !verbosity.is_quiet() {
+        println!("Converted {} to {} ({})", input, output, format);
+    }
+
+    Ok(())
+}
+
+/// Ranges `search` samples hyperparameters from
+struct SearchSpace {
+    min_lr: f64,
+    max_lr: f64,
+    max_hidden: usize,
+}
+
+/// Train an example with randomly sampled hyperparameters and report the
+/// configuration with the lowest final validation loss
+///
+/// Learning rates are sampled log-uniformly, so each order of magnitude is
+/// tried equally often, and every hidden layer gets the same sampled size.
+/// Trials are reproducible for a given `seed`.
+fn cmd_search(
+    example: &str,
+    trials: u32,
+    epochs: u32,
+    space: &SearchSpace,
+    validation_split: f64,
+    seed: u64,
+) -> anyhow::Result<()> {
+    use neural_network::{activations::SIGMOID, examples, network::Network, training::{TrainingConfig, TrainingController}};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let ex = examples::get_example(example)
+        .ok_or_else(|| anyhow::anyhow!("Unknown example: {}. Use 'list' to see available examples.", example))?;
+    anyhow::ensure!(
+        space.min_lr > 0.0 && space.min_lr <= space.max_lr,
+        "Learning rate range must satisfy 0 < min-lr <= max-lr, got {} to {}",
+        space.min_lr,
+        space.max_lr
+    );
+
+    println!("Searching {} over {} trials of {} epochs", ex.name, trials, epochs);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (log_min, log_max) = (space.min_lr.ln(), space.max_lr.ln());
+    let mut best: Option<(f64, usize, f64)> = None;
+    for trial in 1..=trials {
+        let learning_rate = rng.gen_range(log_min..=log_max).exp();
+        let hidden = rng.gen_range(1..=space.max_hidden);
+
+        let mut architecture = ex.recommended_arch.clone();
+        let outputs = architecture.len() - 1;
+        architecture[1..outputs].fill(hidden);
+
+        let network = Network::new_seeded(architecture, SIGMOID, learning_rate, seed.wrapping_add(trial as u64));
+        let config = TrainingConfig {
+            epochs,
+            validation_split: Some(validation_split),
+            validation_seed: seed,
+            example_name: Some(ex.name.to_string()),
+            ..Default::default()
+        };
+        let mut controller = TrainingController::new(network, config);
+        let report = controller.train(ex.inputs.clone(), ex.targets.clone())?;
+        let val_loss = report
+            .history
+            .last()
+            .and_then(|progress| progress.val_loss)
+            .unwrap_or(f64::INFINITY);
+
+        println!(
+            "  Trial {}: learning_rate {:.4}, hidden {}, val_loss {:.6}",
+            trial, learning_rate, hidden, val_loss
+        );
+        if best.is_none_or(|(best_loss, _, _)| val_loss < best_loss) {
+            best = Some((val_loss, hidden, learning_rate));
+        }
+    }
+
+    // trials is at least 1, so there is always a best trial
+    if let Some((val_loss, hidden, learning_rate)) = best {
+        println!(
+            "Best: learning_rate {:.4}, hidden {}, val_loss {:.6}",
+            learning_rate, hidden, val_loss
+        );
+    }
+
+    Ok(())
+}
+
+/// Time training epochs on an example
+fn cmd_bench_training(example: &str, iterations: u32) -> anyhow::Result<()> {
+    use neural_network::{activations::SIGMOID, examples, network::Network, training::{TrainingConfig, TrainingController}};
+
+    let ex = examples::get_example(example)
+        .ok_or_else(|| anyhow::anyhow!("Unknown example: {}. Use 'list' to see available examples.", example))?;
+    anyhow::ensure!(iterations > 0, "Iterations must be at least 1");
+
+    let network = Network::new_seeded(ex.recommended_arch.clone(), SIGMOID, 0.5, 0);
+    let config = TrainingConfig {
+        epochs: iterations,
+        example_name: Some(ex.name.to_string()),
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+
+    let start = std::time::Instant::now();
+    controller.train(ex.inputs.clone(), ex.targets.clone())?;
+    let elapsed = start.elapsed();
+
+    println!("Benchmark: training {} {:?}", ex.name, ex.recommended_arch);
+    print_bench_results(iterations, elapsed, "epoch", "Epochs");
+
+    Ok(())
+}
+
+/// Time feed-forward passes through a trained model
+fn cmd_bench_inference(model: &str, iterations: u32) -> anyhow::Result<()> {
+    use neural_network::{matrix::Matrix, network::Network};
+    use std::hint::black_box;
+    use std::path::Path;
+
+    anyhow::ensure!(iterations > 0, "Iterations must be at least 1");
+    let (mut network, _) = Network::load_checkpoint_auto(Path::new(model))?;
+
+    // A fixed mid-range input; the values don't affect the cost of a pass
+    let input = Matrix::from(vec![0.5; network.layers[0]]);
+
+    let start = std::time::Instant::now();
+    for _ in 0..iterations {
+        black_box(network.feed_forward(black_box(input.clone())));
+    }
+    let elapsed = start.elapsed();
+
+    println!("Benchmark: inference on {} {:?}", model, network.layers);
+    print_bench_results(iterations, elapsed, "inference", "Inferences");
+
+    Ok(())
+}
+
+/// Print iteration count, time per iteration and throughput
+fn print_bench_results(iterations: u32, elapsed: std::time::Duration, unit: &str, plural: &str) {
+    let seconds = elapsed.as_secs_f64();
+    println!("  Iterations: {}", iterations);
+    println!("  Total time: {:.3} ms", seconds * 1000.0);
+    println!("  Time per {}: {:.3} us", unit, seconds * 1e6 / iterations as f64);
+    // Guard against a zero reading from a coarse clock
+    println!("  {}/sec: {:.0}", plural, iterations as f64 / seconds.max(1e-9));
+}
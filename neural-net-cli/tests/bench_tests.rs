@@ -0,0 +1,69 @@
+// Integration tests for the bench command
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Extract the number printed after `label` in the bench output
+fn reported_value(stdout: &str, label: &str) -> f64 {
+    let line = stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with(label))
+        .unwrap_or_else(|| panic!("Output should report {}: {}", label, stdout));
+    line.split(':').nth(1).unwrap().trim().split(' ').next().unwrap().parse().unwrap()
+}
+
+#[test]
+fn test_bench_inference() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("xor.json");
+
+    let train = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "xor",
+            "--epochs",
+            "100",
+            "--output",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to train");
+    assert!(train.status.success());
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "bench",
+            "--mode",
+            "inference",
+            "--model",
+            model_path.to_str().unwrap(),
+            "--iterations",
+            "1000",
+        ])
+        .output()
+        .expect("Failed to run bench");
+
+    assert!(output.status.success(), "Bench should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(reported_value(&stdout, "Iterations"), 1000.0);
+    assert!(reported_value(&stdout, "Time per inference") > 0.0);
+    assert!(reported_value(&stdout, "Inferences/sec") > 0.0);
+}
+
+#[test]
+fn test_bench_inference_requires_model() {
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "neural-net-cli", "--", "bench", "--mode", "inference"])
+        .output()
+        .expect("Failed to run bench");
+
+    assert!(!output.status.success(), "Inference bench without a model should fail");
+}
@@ -0,0 +1,87 @@
+// Integration tests for converting checkpoints between formats
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_cli(args: &[&str]) -> String {
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "neural-net-cli", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to run CLI");
+
+    assert!(
+        output.status.success(),
+        "{:?} should succeed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn eval_output(model_path: &Path) -> String {
+    run_cli(&["-q", "eval", "--model", model_path.to_str().unwrap(), "--input", "1.0,0.0"])
+}
+
+#[test]
+fn test_convert_json_to_bin_and_back() {
+    let temp_dir = TempDir::new().unwrap();
+    let json_path = temp_dir.path().join("xor.json");
+    let bin_path = temp_dir.path().join("xor.bin");
+    let round_trip_path = temp_dir.path().join("round-trip.json");
+
+    run_cli(&[
+        "train", "--example", "xor", "--epochs", "300", "--seed", "5", "--output",
+        json_path.to_str().unwrap(),
+    ]);
+    let expected = eval_output(&json_path);
+
+    let stdout = run_cli(&[
+        "convert", "--input", json_path.to_str().unwrap(), "--output", bin_path.to_str().unwrap(),
+    ]);
+    assert!(stdout.contains("(bin)"), "{}", stdout);
+    assert!(std::fs::read(&bin_path).unwrap().starts_with(b"NNCK"));
+    assert_eq!(eval_output(&bin_path), expected);
+
+    run_cli(&[
+        "convert", "--input", bin_path.to_str().unwrap(), "--output", round_trip_path.to_str().unwrap(),
+    ]);
+    assert_eq!(eval_output(&round_trip_path), expected);
+
+    // Metadata and weights survive the round trip exactly
+    let read_json = |path: &Path| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    assert_eq!(read_json(&round_trip_path), read_json(&json_path));
+}
+
+#[test]
+fn test_convert_honors_explicit_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let json_path = temp_dir.path().join("and.json");
+    let output_path = temp_dir.path().join("and.model");
+
+    run_cli(&["train", "--example", "and", "--epochs", "10", "--output", json_path.to_str().unwrap()]);
+    run_cli(&[
+        "convert", "--input", json_path.to_str().unwrap(), "--output", output_path.to_str().unwrap(),
+        "--format", "json-gz",
+    ]);
+
+    // Gzip magic bytes
+    assert!(std::fs::read(&output_path).unwrap().starts_with(&[0x1f, 0x8b]));
+}
+
+#[test]
+fn test_convert_missing_input_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "neural-net-cli", "--", "convert", "--input"])
+        .arg(temp_dir.path().join("missing.json"))
+        .arg("--output")
+        .arg(temp_dir.path().join("out.bin"))
+        .output()
+        .expect("Failed to run CLI");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read checkpoint"));
+}
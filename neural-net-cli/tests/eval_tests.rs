@@ -248,6 +248,51 @@ fn test_eval_wrong_input_dimensions() {
     // TempDir automatically cleans up when dropped
 }
 
+#[test]
+fn test_eval_non_finite_input() {
+    let temp_dir = create_temp_dir();
+    let model_path = temp_dir.path().join("model.json");
+
+    Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "and",
+            "--epochs",
+            "100",
+            "--output",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to train");
+
+    // "NaN" and "inf" parse as floats, so the network has to reject them
+    for (input, expected) in [("0.0,NaN", "NaN at index 1"), ("inf,0.0", "inf at index 0")] {
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--bin",
+                "neural-net-cli",
+                "--",
+                "eval",
+                "--model",
+                model_path.to_str().unwrap(),
+                "--input",
+                input,
+            ])
+            .output()
+            .expect("Failed to run eval");
+
+        assert!(!output.status.success(), "Eval of {} should fail", input);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "{}", stderr);
+    }
+}
+
 #[test]
 fn test_eval_all_examples() {
     let temp_dir = create_temp_dir();
@@ -359,3 +404,114 @@ fn test_eval_shows_model_info() {
 
     // TempDir automatically cleans up when dropped
 }
+
+#[test]
+fn test_eval_batch_with_labels() {
+    let temp_dir = create_temp_dir();
+    let model_path = temp_dir.path().join("and_model.json");
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "--quiet",
+            "train",
+            "--example",
+            "and",
+            "--epochs",
+            "100",
+            "--output",
+            model_path.to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to train");
+    assert!(status.success());
+
+    let inputs_path = temp_dir.path().join("inputs.csv");
+    std::fs::write(&inputs_path, "0.0,0.0\n0.0,1.0\n\n1.0,1.0\n").unwrap();
+    let labels_path = temp_dir.path().join("labels.txt");
+    std::fs::write(&labels_path, "both off\none on\nboth on\n").unwrap();
+
+    let eval = |labels: &std::path::Path| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--bin",
+                "neural-net-cli",
+                "--",
+                "eval",
+                "--model",
+                model_path.to_str().unwrap(),
+                "--inputs",
+                inputs_path.to_str().unwrap(),
+                "--labels",
+                labels.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to run eval")
+    };
+
+    let output = eval(&labels_path);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "One line per input row:\n{}", stdout);
+    for (line, label) in lines.iter().zip(["both off: ", "one on: ", "both on: "]) {
+        let value = line.strip_prefix(label).unwrap_or_else(|| panic!("{:?} should start with {:?}", line, label));
+        assert!(value.parse::<f64>().is_ok(), "{:?} should be an output value", value);
+    }
+
+    // Every row needs a label
+    std::fs::write(&labels_path, "both off\none on\n").unwrap();
+    let output = eval(&labels_path);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("has 2 labels, but"), "{}", stderr);
+    assert!(stderr.contains("has 3 input rows"), "{}", stderr);
+}
+
+#[test]
+fn test_eval_precision_formats_outputs() {
+    let temp_dir = create_temp_dir();
+    let model_path = temp_dir.path().join("and_model.json");
+
+    let train = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "and",
+            "--epochs",
+            "500",
+            "--output",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to train");
+    assert!(train.status.success());
+
+    let eval = |precision: &[&str]| {
+        let output = Command::new("cargo")
+            .args(["run", "--bin", "neural-net-cli", "--", "eval", "--model", model_path.to_str().unwrap()])
+            .args(["--input", "1.0,1.0"])
+            .args(precision)
+            .output()
+            .expect("Failed to run eval");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let line = stdout.lines().find(|line| line.starts_with("Output:")).expect("Should print output");
+        line.trim_start_matches("Output:").trim().trim_matches(['[', ']']).to_string()
+    };
+
+    let value = eval(&["--precision", "2"]);
+    let decimals = value.split('.').nth(1).unwrap_or_else(|| panic!("{:?} should have decimals", value));
+    assert_eq!(decimals.len(), 2, "{:?} should have two decimals", value);
+    assert!(value.parse::<f64>().is_ok(), "{:?}", value);
+
+    // Four decimals by default
+    assert_eq!(eval(&[]).split('.').nth(1).unwrap().len(), 4);
+}
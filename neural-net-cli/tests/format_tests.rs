@@ -0,0 +1,112 @@
+// Integration tests for checkpoint formats
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn train(model_path: &Path, format: &str) {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "xor",
+            "--epochs",
+            "500",
+            "--seed",
+            "42",
+            "--format",
+            format,
+            "--output",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to train");
+
+    assert!(output.status.success(), "Training with --format {} should succeed", format);
+}
+
+fn eval_output(model_path: &Path) -> Vec<f64> {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "eval",
+            "--model",
+            model_path.to_str().unwrap(),
+            "--input",
+            "1.0,0.0",
+            "--precision",
+            "17",
+        ])
+        .output()
+        .expect("Failed to run eval");
+
+    assert!(output.status.success(), "Eval should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find(|line| line.starts_with("Output:"))
+        .expect("Should print output");
+
+    line.trim_start_matches("Output:")
+        .trim()
+        .trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .map(|v| v.trim().parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_bin_format_matches_json_with_same_seed() {
+    let temp_dir = TempDir::new().unwrap();
+    let json_path = temp_dir.path().join("xor.json");
+    let bin_path = temp_dir.path().join("xor.bin");
+
+    train(&json_path, "json");
+    train(&bin_path, "bin");
+
+    let bytes = std::fs::read(&bin_path).unwrap();
+    assert!(bytes.starts_with(b"NNCK"), "Binary checkpoint should start with magic bytes");
+    assert!(
+        bytes.len() < std::fs::read(&json_path).unwrap().len(),
+        "Binary checkpoint should be smaller than JSON"
+    );
+
+    let json_output = eval_output(&json_path);
+    let bin_output = eval_output(&bin_path);
+
+    assert_eq!(json_output.len(), bin_output.len());
+    for (a, b) in json_output.iter().zip(&bin_output) {
+        assert!((a - b).abs() < 1e-9, "Predictions should match: {} vs {}", a, b);
+    }
+}
+
+#[test]
+fn test_info_reads_gzip_checkpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("xor.json.gz");
+
+    train(&model_path, "json-gz");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "info",
+            "--model",
+            model_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run info");
+
+    assert!(output.status.success(), "Info should read gzip checkpoints");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Example: xor"));
+}
@@ -333,3 +333,85 @@ fn test_info_with_corrupted_file() {
 
     // TempDir automatically cleans up when dropped
 }
+
+#[test]
+fn test_info_dir_summarizes_every_checkpoint() {
+    let temp_dir = create_temp_dir();
+    for (example, file) in [("xor", "b_xor.json"), ("and", "a_and.bin")] {
+        let status = Command::new("cargo")
+            .args([
+                "run",
+                "--bin",
+                "neural-net-cli",
+                "--",
+                "--quiet",
+                "train",
+                "--example",
+                example,
+                "--epochs",
+                "100",
+                "--output",
+                temp_dir.path().join(file).to_str().unwrap(),
+            ])
+            .status()
+            .expect("Failed to train");
+        assert!(status.success());
+    }
+    std::fs::write(temp_dir.path().join("notes.txt"), "not a checkpoint").unwrap();
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "info",
+            "--dir",
+            temp_dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run info");
+
+    assert!(output.status.success(), "Info --dir should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "Header and one line per model:\n{}", stdout);
+    assert!(lines[0].starts_with("File"));
+
+    // Sorted by filename, with example, epochs, architecture and parameters
+    let and: Vec<&str> = lines[1].split_whitespace().collect();
+    assert_eq!(and, ["a_and.bin", "and", "100", "2-2-1", "9"]);
+    let xor: Vec<&str> = lines[2].split_whitespace().collect();
+    assert_eq!(xor, ["b_xor.json", "xor", "100", "2-3-1", "13"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: skipping notes.txt"), "{}", stderr);
+}
+
+#[test]
+fn test_info_displays_session_history() {
+    let temp_dir = create_temp_dir();
+    let model_path = temp_dir.path().join("model.json");
+    let model = model_path.to_str().unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new("cargo")
+            .args(["run", "--bin", "neural-net-cli", "--"])
+            .args(args)
+            .output()
+            .expect("Failed to run command");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    run(&["train", "--example", "and", "--epochs", "100", "--output", model]);
+    assert!(!run(&["info", "--model", model]).contains("Session History"));
+
+    run(&["resume", "--checkpoint", model, "--epochs", "50", "--output", model]);
+    run(&["resume", "--checkpoint", model, "--epochs", "30", "--output", model]);
+
+    let stdout = run(&["info", "--model", model]);
+    assert!(stdout.contains("Session History:"), "{}", stdout);
+    assert!(stdout.contains("Session 1: 50 epochs"), "{}", stdout);
+    assert!(stdout.contains("Session 2: 30 epochs"), "{}", stdout);
+}
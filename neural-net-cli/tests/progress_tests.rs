@@ -252,3 +252,75 @@ fn test_progress_without_output_file() {
         "Should show training status"
     );
 }
+
+#[test]
+fn test_train_json_progress_emits_one_line_per_epoch() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "xor",
+            "--epochs",
+            "25",
+            "--progress",
+            "json",
+        ])
+        .output()
+        .expect("Failed to train");
+
+    assert!(output.status.success(), "JSON progress training should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("Every line should be JSON ({}): {}", e, line))
+        })
+        .collect();
+
+    assert_eq!(events.len(), 25, "Should emit one line per epoch");
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event["epoch"], i as u64 + 1);
+        assert!(event["loss"].as_f64().unwrap().is_finite());
+    }
+    assert_eq!(events.last().unwrap()["epoch"], 25, "Final epoch should be the requested count");
+}
+
+#[test]
+fn test_train_json_progress_includes_loss_ema() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "xor",
+            "--epochs",
+            "10",
+            "--progress",
+            "json",
+            "--loss-ema",
+            "0.2",
+        ])
+        .output()
+        .expect("Failed to train");
+
+    assert!(output.status.success(), "Training with --loss-ema should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let events: Vec<serde_json::Value> =
+        stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+    assert_eq!(events.len(), 10);
+    assert_eq!(events[0]["loss_ema"], events[0]["loss"], "The average starts at the first loss");
+    for event in &events {
+        assert!(event["loss_ema"].as_f64().unwrap().is_finite());
+    }
+}
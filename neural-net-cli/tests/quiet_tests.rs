@@ -0,0 +1,49 @@
+// Integration tests for the global --quiet flag
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_cli(args: &[&str]) -> std::process::Output {
+    Command::new("cargo")
+        .args(["run", "--bin", "neural-net-cli", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to run CLI")
+}
+
+#[test]
+fn test_train_quiet_prints_nothing_but_saves_model() {
+    let temp_dir = TempDir::new().unwrap();
+    let model_path = temp_dir.path().join("quiet.json");
+    let model = model_path.to_str().unwrap();
+
+    let output = run_cli(&["train", "--example", "xor", "--epochs", "200", "--output", model, "--quiet"]);
+
+    assert!(output.status.success(), "Quiet training should succeed");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).trim().is_empty(),
+        "Quiet training should print nothing: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert!(model_path.exists(), "Model file should still be created");
+
+    // The flag is global, so it also works before the subcommand
+    let output = run_cli(&["--quiet", "resume", "--checkpoint", model, "--epochs", "10", "--output", model]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).trim().is_empty());
+
+    // Quiet eval prints just the output values
+    let output = run_cli(&["eval", "--model", model, "--input", "1.0,0.0", "-q"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "Quiet eval should print one line: {}", stdout);
+    lines[0].parse::<f64>().expect("Quiet eval should print a bare number");
+}
+
+#[test]
+fn test_quiet_still_reports_errors() {
+    let output = run_cli(&["train", "--example", "nonexistent", "--quiet"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown example"));
+}
@@ -0,0 +1,58 @@
+// Integration tests for the search command
+use std::process::Command;
+
+fn run_search(seed: &str) -> String {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "search",
+            "--example",
+            "or",
+            "--trials",
+            "3",
+            "--epochs",
+            "500",
+            "--seed",
+            seed,
+        ])
+        .output()
+        .expect("Failed to run search");
+    assert!(
+        output.status.success(),
+        "Search should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_search_reports_best_configuration() {
+    let stdout = run_search("7");
+
+    assert_eq!(stdout.lines().filter(|line| line.trim_start().starts_with("Trial")).count(), 3, "{}", stdout);
+    let best = stdout
+        .lines()
+        .find(|line| line.starts_with("Best:"))
+        .unwrap_or_else(|| panic!("Output should report the best configuration: {}", stdout));
+    let val_loss: f64 = best.rsplit(' ').next().unwrap().parse().unwrap();
+    assert!(val_loss.is_finite(), "{}", best);
+    assert!(best.contains("learning_rate") && best.contains("hidden"), "{}", best);
+
+    // The same seed samples and trains the same configurations
+    assert_eq!(run_search("7"), stdout);
+}
+
+#[test]
+fn test_search_rejects_invalid_learning_rate_range() {
+    let output = Command::new("cargo")
+        .args([
+            "run", "--bin", "neural-net-cli", "--", "search", "--example", "or", "--min-lr", "0.5", "--max-lr", "0.1",
+        ])
+        .output()
+        .expect("Failed to run search");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("min-lr"));
+}
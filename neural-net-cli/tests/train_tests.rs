@@ -252,3 +252,94 @@ fn test_train_creates_valid_checkpoint() {
 
     // TempDir automatically cleans up when dropped
 }
+
+#[test]
+fn test_train_checkpoint_every() {
+    let temp_dir = create_temp_dir();
+    let output_path = temp_dir.path().join("periodic.json");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "and",
+            "--epochs",
+            "250",
+            "--checkpoint-every",
+            "100",
+            "--output",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run CLI");
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    // The last write comes after the final epoch, not the last multiple of 100
+    use neural_network::network::Network;
+    let (_, metadata) = Network::load_checkpoint(&output_path).unwrap();
+    assert_eq!(metadata.epoch, 250);
+}
+
+#[test]
+fn test_train_checkpoint_every_requires_output() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "train",
+            "--example",
+            "and",
+            "--checkpoint-every",
+            "100",
+        ])
+        .output()
+        .expect("Failed to run CLI");
+
+    assert!(!output.status.success(), "--checkpoint-every without --output should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--output"));
+}
+
+#[test]
+fn test_train_writes_history() {
+    let temp_dir = create_temp_dir();
+    let history_path = temp_dir.path().join("history.json");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--bin",
+            "neural-net-cli",
+            "--",
+            "--quiet",
+            "train",
+            "--example",
+            "xor",
+            "--epochs",
+            "50",
+            "--loss-ema",
+            "0.5",
+            "--history-out",
+            history_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let history: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&history_path).expect("History should be written")).unwrap();
+    let entries = history.as_array().expect("History should be a JSON array");
+    assert_eq!(entries.len(), 50, "One entry per epoch");
+    for (i, entry) in entries.iter().enumerate() {
+        assert_eq!(entry["epoch"], i as u64 + 1);
+        assert!(entry["loss"].as_f64().unwrap() > 0.0);
+        assert!(entry["gradient_norm"].is_f64());
+        assert!(entry["loss_ema"].is_f64());
+        assert_eq!(entry["learning_rate"], 0.5);
+    }
+}
@@ -0,0 +1,26 @@
+// Generates the C header from the exported functions into OUT_DIR
+//
+// The committed include/neural_net.h is only rewritten when
+// NEURAL_NET_FFI_UPDATE_HEADER is set, so packaged and read-only builds
+// never write to the source tree.
+use std::error::Error;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?);
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=NEURAL_NET_FFI_UPDATE_HEADER");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))?;
+    let bindings = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()?;
+    bindings.write_to_file(out_dir.join("neural_net.h"));
+    if std::env::var_os("NEURAL_NET_FFI_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(crate_dir.join("include/neural_net.h"));
+    }
+    Ok(())
+}
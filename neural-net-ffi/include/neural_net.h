@@ -0,0 +1,84 @@
+/* Generated by cbindgen from neural-net-ffi/src/lib.rs; do not edit */
+
+#ifndef NEURAL_NET_H
+#define NEURAL_NET_H
+
+#include <stddef.h>
+
+/**
+ * The call succeeded
+ */
+#define NN_OK 0
+
+/**
+ * A required pointer argument was null
+ */
+#define NN_ERR_NULL_POINTER -1
+
+/**
+ * A buffer length doesn't match the network's input or output size
+ */
+#define NN_ERR_DIMENSIONS -2
+
+/**
+ * The library panicked; the message is available as the last error
+ */
+#define NN_ERR_PANIC -3
+
+/**
+ * An input value is NaN or infinite
+ */
+#define NN_ERR_INVALID_INPUT -4
+
+/**
+ * A trained network loaded from a checkpoint
+ */
+typedef struct NnModel NnModel;
+
+/**
+ * Load a checkpoint in any format (json, bin or json-gz)
+ *
+ * Returns null if the checkpoint can't be loaded. Release the model with
+ * `nn_free`.
+ *
+ * # Safety
+ *
+ * `path` must be null or point to a NUL-terminated string.
+ */
+NnModel *nn_load_checkpoint(const char *path);
+
+/**
+ * Run the network on `n_in` inputs, writing its `n_out` outputs to `out`
+ *
+ * Returns `NN_OK`, `NN_ERR_NULL_POINTER` if any pointer is null,
+ * `NN_ERR_DIMENSIONS` if the lengths don't match the network's input and
+ * output layers, or `NN_ERR_INVALID_INPUT` if an input isn't finite. `out`
+ * is left untouched on failure.
+ *
+ * # Safety
+ *
+ * `model` must come from `nn_load_checkpoint` and not have been freed.
+ * `input` must point to `n_in` readable doubles and `out` to `n_out`
+ * writable doubles.
+ */
+int nn_predict(const NnModel *model, const double *input, size_t n_in, double *out, size_t n_out);
+
+/**
+ * Release a model; null is ignored
+ *
+ * # Safety
+ *
+ * `model` must be null or come from `nn_load_checkpoint`, and must not be
+ * used again afterwards.
+ */
+void nn_free(NnModel *model);
+
+/**
+ * Message describing the last error on this thread, or null if there was none
+ *
+ * The string is owned by the library and stays valid until the next failing
+ * call on the same thread.
+ */
+const char *nn_last_error_message(void);
+
+#endif  /* NEURAL_NET_H */
@@ -0,0 +1,176 @@
+// C interface for embedding trained models
+//
+// A checkpoint is loaded once into an opaque `NnModel` and then run with the
+// immutable forward pass on caller-owned buffers, so C programs such as
+// firmware simulators can use a trained network without touching Rust types.
+// Failures are reported as a null model or a negative error code, with
+// `nn_last_error_message` describing the most recent one. Panics are caught
+// and never unwind into C. The header `include/neural_net.h` is generated
+// from this file by cbindgen when the crate builds.
+
+use neural_network::network::Network;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+/// The call succeeded
+pub const NN_OK: c_int = 0;
+
+/// A required pointer argument was null
+pub const NN_ERR_NULL_POINTER: c_int = -1;
+
+/// A buffer length doesn't match the network's input or output size
+pub const NN_ERR_DIMENSIONS: c_int = -2;
+
+/// The library panicked; the message is available as the last error
+pub const NN_ERR_PANIC: c_int = -3;
+
+/// An input value is NaN or infinite
+pub const NN_ERR_INVALID_INPUT: c_int = -4;
+
+/// A trained network loaded from a checkpoint
+pub struct NnModel {
+    network: Network,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl std::fmt::Display) {
+    // An interior NUL would cut the message short in C
+    let message = message.to_string().replace('\0', "");
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(message).ok());
+}
+
+/// Run `f`, returning `on_panic` if it panics
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown error".to_string());
+        set_last_error(format!("Panic: {}", message));
+        on_panic
+    })
+}
+
+/// Load a checkpoint in any format (json, bin or json-gz)
+///
+/// Returns null if the checkpoint can't be loaded. Release the model with
+/// `nn_free`.
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nn_load_checkpoint(path: *const c_char) -> *mut NnModel {
+    guard(ptr::null_mut(), || {
+        if path.is_null() {
+            set_last_error("Checkpoint path is null");
+            return ptr::null_mut();
+        }
+        let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+            set_last_error("Checkpoint path is not valid UTF-8");
+            return ptr::null_mut();
+        };
+
+        match Network::load_checkpoint_auto(Path::new(path)) {
+            Ok((network, _)) => Box::into_raw(Box::new(NnModel { network })),
+            Err(e) => {
+                set_last_error(format!("{:#}", e));
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Run the network on `n_in` inputs, writing its `n_out` outputs to `out`
+///
+/// Returns `NN_OK`, `NN_ERR_NULL_POINTER` if any pointer is null,
+/// `NN_ERR_DIMENSIONS` if the lengths don't match the network's input and
+/// output layers, or `NN_ERR_INVALID_INPUT` if an input isn't finite. `out`
+/// is left untouched on failure.
+///
+/// # Safety
+///
+/// `model` must come from `nn_load_checkpoint` and not have been freed.
+/// `input` must point to `n_in` readable doubles and `out` to `n_out`
+/// writable doubles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nn_predict(
+    model: *const NnModel,
+    input: *const f64,
+    n_in: usize,
+    out: *mut f64,
+    n_out: usize,
+) -> c_int {
+    guard(NN_ERR_PANIC, || {
+        if model.is_null() || input.is_null() || out.is_null() {
+            set_last_error("Model, input or output pointer is null");
+            return NN_ERR_NULL_POINTER;
+        }
+        let network = unsafe { &(*model).network };
+        let input_size = network.layers[0];
+        let output_size = network.layers[network.layers.len() - 1];
+        if n_in != input_size || n_out != output_size {
+            set_last_error(format!(
+                "Expected {} inputs and {} outputs, got {} and {}",
+                input_size, output_size, n_in, n_out
+            ));
+            return NN_ERR_DIMENSIONS;
+        }
+
+        let input = unsafe { std::slice::from_raw_parts(input, n_in) };
+        let output = match network.predict(input) {
+            Ok(output) => output,
+            Err(e) => {
+                set_last_error(e);
+                return NN_ERR_INVALID_INPUT;
+            }
+        };
+        unsafe { std::slice::from_raw_parts_mut(out, n_out) }.copy_from_slice(&output);
+        NN_OK
+    })
+}
+
+/// Release a model; null is ignored
+///
+/// # Safety
+///
+/// `model` must be null or come from `nn_load_checkpoint`, and must not be
+/// used again afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn nn_free(model: *mut NnModel) {
+    guard((), || {
+        if !model.is_null() {
+            drop(unsafe { Box::from_raw(model) });
+        }
+    })
+}
+
+/// Message describing the last error on this thread, or null if there was none
+///
+/// The string is owned by the library and stays valid until the next failing
+/// call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn nn_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_turns_panics_into_errors() {
+        let code = guard(NN_ERR_PANIC, || -> c_int { panic!("boom") });
+
+        assert_eq!(code, NN_ERR_PANIC);
+        let message = unsafe { CStr::from_ptr(nn_last_error_message()) };
+        assert_eq!(message.to_str().unwrap(), "Panic: boom");
+    }
+}
@@ -0,0 +1,127 @@
+// Integration tests calling the exported C functions and comparing them with
+// the native API
+use neural_net_ffi::{
+    NN_ERR_DIMENSIONS, NN_ERR_INVALID_INPUT, NN_ERR_NULL_POINTER, NN_OK, nn_free, nn_last_error_message, nn_load_checkpoint, nn_predict,
+};
+use neural_network::activations::SIGMOID;
+use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
+use neural_network::examples;
+use neural_network::network::Network;
+use std::ffi::{CStr, CString};
+use std::path::Path;
+use std::ptr;
+use tempfile::TempDir;
+
+fn metadata() -> CheckpointMetadata {
+    CheckpointMetadata {
+        version: "1.0".to_string(),
+        example: "xor".to_string(),
+        epoch: 500,
+        total_epochs: 500,
+        learning_rate: 0.5,
+        timestamp: "2025-10-13T12:00:00Z".to_string(),
+        history: Vec::new(),
+    }
+}
+
+fn trained_network() -> Network {
+    let example = examples::get_example("xor").unwrap();
+    let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 7);
+    network.train(example.inputs, example.targets, 500);
+    network
+}
+
+fn load(path: &Path) -> *mut neural_net_ffi::NnModel {
+    let path = CString::new(path.to_str().unwrap()).unwrap();
+    unsafe { nn_load_checkpoint(path.as_ptr()) }
+}
+
+fn last_error() -> String {
+    let message = nn_last_error_message();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_predictions_match_native_api() {
+    let temp_dir = TempDir::new().unwrap();
+    let network = trained_network();
+
+    for (name, format) in [("xor.json", CheckpointFormat::Json), ("xor.bin", CheckpointFormat::Bin)] {
+        let path = temp_dir.path().join(name);
+        network.save_checkpoint_as(&path, metadata(), format).unwrap();
+
+        let model = load(&path);
+        assert!(!model.is_null(), "{}", last_error());
+        for input in examples::get_example("xor").unwrap().inputs {
+            let mut output = [f64::NAN];
+            let code = unsafe { nn_predict(model, input.as_ptr(), input.len(), output.as_mut_ptr(), output.len()) };
+
+            assert_eq!(code, NN_OK);
+            assert_eq!(output.to_vec(), network.predict(&input).unwrap(), "{} {:?}", name, input);
+        }
+        unsafe { nn_free(model) };
+    }
+}
+
+#[test]
+fn test_invalid_input_returns_error_code() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("xor.json");
+    trained_network().save_checkpoint_as(&path, metadata(), CheckpointFormat::Json).unwrap();
+    let model = load(&path);
+
+    let input = [1.0, 0.0, 1.0];
+    let mut output = [-1.0];
+    let code = unsafe { nn_predict(model, input.as_ptr(), input.len(), output.as_mut_ptr(), output.len()) };
+    assert_eq!(code, NN_ERR_DIMENSIONS);
+    assert_eq!(last_error(), "Expected 2 inputs and 1 outputs, got 3 and 1");
+    assert_eq!(output, [-1.0]);
+
+    let mut outputs = [0.0; 2];
+    let code = unsafe { nn_predict(model, input.as_ptr(), 2, outputs.as_mut_ptr(), outputs.len()) };
+    assert_eq!(code, NN_ERR_DIMENSIONS);
+
+    let input = [f64::NAN, 0.0];
+    let code = unsafe { nn_predict(model, input.as_ptr(), input.len(), output.as_mut_ptr(), output.len()) };
+    assert_eq!(code, NN_ERR_INVALID_INPUT);
+    assert_eq!(last_error(), "Input values must be finite, got NaN at index 0");
+    assert_eq!(output, [-1.0]);
+
+    unsafe { nn_free(model) };
+}
+
+#[test]
+fn test_missing_checkpoint_returns_null() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let model = load(&temp_dir.path().join("missing.json"));
+
+    assert!(model.is_null());
+    assert!(last_error().contains("Failed to read checkpoint"), "{}", last_error());
+}
+
+#[test]
+fn test_null_pointers_are_rejected() {
+    let input = [0.0, 0.0];
+    let mut output = [0.0];
+
+    assert!(unsafe { nn_load_checkpoint(ptr::null()) }.is_null());
+    assert_eq!(last_error(), "Checkpoint path is null");
+
+    let code = unsafe { nn_predict(ptr::null(), input.as_ptr(), 2, output.as_mut_ptr(), 1) };
+    assert_eq!(code, NN_ERR_NULL_POINTER);
+
+    // Freeing null is a no-op
+    unsafe { nn_free(ptr::null_mut()) };
+}
+
+#[test]
+fn test_committed_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/neural_net.h"));
+    let committed = include_str!("../include/neural_net.h");
+    assert!(
+        generated == committed,
+        "include/neural_net.h is stale; rebuild with NEURAL_NET_FFI_UPDATE_HEADER=1"
+    );
+}
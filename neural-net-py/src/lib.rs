@@ -0,0 +1,289 @@
+// Python bindings for the neural network library
+//
+// Exposes `Network`, `TrainingConfig`, checkpoints and the examples registry
+// to Python as the `neural_net` module. Inputs and targets are taken as
+// sequences of float rows, so lists and 2-D numpy arrays both work; outputs
+// are plain lists (wrap them in `numpy.asarray` as needed). Library errors
+// are raised as `neural_net.NetworkError` carrying the error message.
+
+use neural_network::{
+    activations::Activation,
+    checkpoint::{CheckpointFormat, CheckpointMetadata},
+    examples,
+    network::Network,
+    optimizer::Optimizer,
+    training::{TrainingConfig, TrainingController},
+};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use std::path::Path;
+
+create_exception!(neural_net, NetworkError, PyException, "Error raised by the neural network library");
+
+fn network_error(e: impl std::fmt::Display) -> PyErr {
+    NetworkError::new_err(e.to_string())
+}
+
+/// Settings for `Network.train`
+#[pyclass(name = "TrainingConfig")]
+#[derive(Clone)]
+pub struct PyTrainingConfig {
+    #[pyo3(get, set)]
+    pub epochs: u32,
+
+    /// "sgd", "momentum", "nesterov" or "adam"
+    #[pyo3(get, set)]
+    pub optimizer: String,
+
+    /// Fraction of samples held out to report validation loss
+    #[pyo3(get, set)]
+    pub validation_split: Option<f64>,
+
+    #[pyo3(get, set)]
+    pub center_targets: bool,
+
+    #[pyo3(get, set)]
+    pub verbose: bool,
+}
+
+#[pymethods]
+impl PyTrainingConfig {
+    #[new]
+    #[pyo3(signature = (epochs = 1000, optimizer = "sgd".to_string(), validation_split = None, center_targets = false, verbose = false))]
+    fn new(
+        epochs: u32,
+        optimizer: String,
+        validation_split: Option<f64>,
+        center_targets: bool,
+        verbose: bool,
+    ) -> Self {
+        Self {
+            epochs,
+            optimizer,
+            validation_split,
+            center_targets,
+            verbose,
+        }
+    }
+}
+
+impl PyTrainingConfig {
+    fn to_config(&self) -> PyResult<TrainingConfig> {
+        let optimizer = match self.optimizer.as_str() {
+            "sgd" => Optimizer::Sgd,
+            "momentum" => Optimizer::Momentum {
+                beta: 0.9,
+                nesterov: false,
+            },
+            "nesterov" => Optimizer::Momentum {
+                beta: 0.9,
+                nesterov: true,
+            },
+            "adam" => Optimizer::adam(),
+            other => {
+                return Err(network_error(format!(
+                    "Unknown optimizer: {}. Expected sgd, momentum, nesterov or adam",
+                    other
+                )));
+            }
+        };
+        Ok(TrainingConfig {
+            epochs: self.epochs,
+            optimizer,
+            validation_split: self.validation_split,
+            center_targets: self.center_targets,
+            verbose: self.verbose,
+            ..Default::default()
+        })
+    }
+}
+
+/// Outcome of `Network.train`
+#[pyclass(name = "TrainingReport", get_all)]
+pub struct PyTrainingReport {
+    pub final_loss: f64,
+    pub epochs_run: u32,
+    pub duration_ms: u64,
+    /// Training loss after each epoch
+    pub losses: Vec<f64>,
+}
+
+/// A feedforward neural network
+#[pyclass(name = "Network")]
+pub struct PyNetwork {
+    network: Network,
+}
+
+#[pymethods]
+impl PyNetwork {
+    /// Create a network with the given layer sizes
+    #[new]
+    #[pyo3(signature = (layers, learning_rate = 0.5, activation = "sigmoid", seed = None))]
+    fn new(layers: Vec<usize>, learning_rate: f64, activation: &str, seed: Option<u64>) -> PyResult<Self> {
+        if layers.len() < 2 || layers.contains(&0) {
+            return Err(network_error(format!(
+                "Invalid architecture {:?}: need at least two non-empty layers",
+                layers
+            )));
+        }
+        let activation = Activation::from_name(activation)
+            .ok_or_else(|| network_error(format!("Unknown activation: {}", activation)))?;
+        let network = match seed {
+            Some(seed) => Network::new_seeded(layers, activation, learning_rate, seed),
+            None => Network::new(layers, activation, learning_rate),
+        };
+        Ok(Self { network })
+    }
+
+    /// Layer sizes, input first
+    #[getter]
+    fn layers(&self) -> Vec<usize> {
+        self.network.layers.clone()
+    }
+
+    #[getter]
+    fn learning_rate(&self) -> f64 {
+        self.network.learning_rate
+    }
+
+    /// Run the network on one input row
+    fn predict(&self, input: Vec<f64>) -> PyResult<Vec<f64>> {
+        self.network.predict(&input).map_err(network_error)
+    }
+
+    /// Train on rows of inputs and targets
+    #[pyo3(signature = (inputs, targets, config = None))]
+    fn train(
+        &mut self,
+        inputs: Vec<Vec<f64>>,
+        targets: Vec<Vec<f64>>,
+        config: Option<PyTrainingConfig>,
+    ) -> PyResult<PyTrainingReport> {
+        let config = match config {
+            Some(config) => config.to_config()?,
+            None => TrainingConfig::default(),
+        };
+        self.check_rows(&inputs, &targets)?;
+
+        let mut controller = TrainingController::new(self.network.clone(), config);
+        let report = controller.train(inputs, targets).map_err(network_error)?;
+        self.network = controller.into_network();
+
+        Ok(PyTrainingReport {
+            final_loss: report.final_loss,
+            epochs_run: report.epochs_run,
+            duration_ms: report.duration_ms,
+            losses: report.history.iter().map(|progress| progress.loss).collect(),
+        })
+    }
+
+    /// Save a checkpoint; the format ("json", "bin" or "json-gz") defaults
+    /// to one inferred from the file extension
+    #[pyo3(signature = (path, format = None))]
+    fn save(&self, path: &str, format: Option<&str>) -> PyResult<()> {
+        let path = Path::new(path);
+        let format = match format {
+            Some(format) => format.parse().map_err(network_error)?,
+            None => CheckpointFormat::from_path(path),
+        };
+        let metadata = CheckpointMetadata {
+            version: "1.0".to_string(),
+            example: "python".to_string(),
+            epoch: 0,
+            total_epochs: 0,
+            learning_rate: self.network.learning_rate,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            history: Vec::new(),
+        };
+        self.network
+            .save_checkpoint_as(path, metadata, format)
+            .map_err(network_error)
+    }
+
+    /// Load a network from a checkpoint in any format
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        let (network, _) = Network::load_checkpoint_auto(Path::new(path)).map_err(network_error)?;
+        Ok(Self { network })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Network(layers={:?}, learning_rate={})",
+            self.network.layers, self.network.learning_rate
+        )
+    }
+}
+
+impl PyNetwork {
+    fn check_rows(&self, inputs: &[Vec<f64>], targets: &[Vec<f64>]) -> PyResult<()> {
+        let layers = &self.network.layers;
+        let (input_size, output_size) = (layers[0], layers[layers.len() - 1]);
+        if inputs.len() != targets.len() {
+            return Err(network_error(format!(
+                "Got {} input rows but {} target rows",
+                inputs.len(),
+                targets.len()
+            )));
+        }
+        if let Some(row) = inputs.iter().find(|row| row.len() != input_size) {
+            return Err(network_error(format!(
+                "Input rows must have {} values, got {}",
+                input_size,
+                row.len()
+            )));
+        }
+        if let Some(row) = targets.iter().find(|row| row.len() != output_size) {
+            return Err(network_error(format!(
+                "Target rows must have {} values, got {}",
+                output_size,
+                row.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A built-in example dataset
+#[pyclass(name = "Example", get_all)]
+pub struct PyExample {
+    pub name: String,
+    pub description: String,
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+    /// Recommended layer sizes
+    pub architecture: Vec<usize>,
+}
+
+/// Names of the built-in examples
+#[pyfunction]
+fn list_examples() -> Vec<&'static str> {
+    examples::list_examples()
+}
+
+/// Look up a built-in example by name
+#[pyfunction]
+fn get_example(name: &str) -> PyResult<PyExample> {
+    let example =
+        examples::get_example(name).ok_or_else(|| network_error(format!("Unknown example: {}", name)))?;
+    Ok(PyExample {
+        name: example.name.to_string(),
+        description: example.description.to_string(),
+        inputs: example.inputs,
+        targets: example.targets,
+        architecture: example.recommended_arch,
+    })
+}
+
+#[pymodule]
+pub fn neural_net(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("NetworkError", m.py().get_type::<NetworkError>())?;
+    m.add_class::<PyNetwork>()?;
+    m.add_class::<PyTrainingConfig>()?;
+    m.add_class::<PyTrainingReport>()?;
+    m.add_class::<PyExample>()?;
+    m.add_function(wrap_pyfunction!(list_examples, m)?)?;
+    m.add_function(wrap_pyfunction!(get_example, m)?)?;
+    Ok(())
+}
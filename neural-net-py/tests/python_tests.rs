@@ -0,0 +1,144 @@
+// Tests driving the bindings from Python code, through an embedded interpreter
+use neural_net::neural_net;
+use pyo3::prelude::*;
+use std::ffi::CString;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+/// Start the embedded interpreter with the `neural_net` module importable
+fn init_python() {
+    INIT.call_once(|| {
+        pyo3::append_to_inittab!(neural_net);
+        pyo3::prepare_freethreaded_python();
+    });
+}
+
+/// Whether the interpreter can import `module`
+fn has_module(module: &str) -> bool {
+    init_python();
+    Python::with_gil(|py| py.import(module).is_ok())
+}
+
+/// Run Python code with the `neural_net` module importable, failing the
+/// test on any exception
+fn run_python(code: &str) {
+    init_python();
+    Python::with_gil(|py| {
+        let code = CString::new(code).unwrap();
+        if let Err(e) = py.run(&code, None, None) {
+            e.display(py);
+            panic!("Python code failed: {}", e);
+        }
+    });
+}
+
+#[test]
+fn test_train_xor_and_predict() {
+    run_python(
+        r#"
+import neural_net
+
+assert "xor" in neural_net.list_examples()
+xor = neural_net.get_example("xor")
+network = neural_net.Network(xor.architecture, 0.5, seed=1)
+
+report = network.train(xor.inputs, xor.targets, neural_net.TrainingConfig(epochs=5000))
+
+assert report.epochs_run == 5000
+assert len(report.losses) == 5000
+assert report.final_loss < 0.05, report.final_loss
+for inputs, target in zip(xor.inputs, xor.targets):
+    output = network.predict(inputs)
+    assert round(output[0]) == target[0], (inputs, output)
+
+# Any sequence of rows works, such as tuples or numpy arrays
+assert network.predict((1.0, 0.0)) == network.predict([1.0, 0.0])
+"#,
+    );
+}
+
+#[test]
+fn test_numpy_arrays_are_accepted() {
+    if !has_module("numpy") {
+        eprintln!("numpy is not installed; skipping test_numpy_arrays_are_accepted");
+        return;
+    }
+    run_python(
+        r#"
+import neural_net
+import numpy as np
+
+xor = neural_net.get_example("xor")
+inputs = np.array(xor.inputs)
+targets = np.array(xor.targets)
+assert inputs.shape == (4, 2) and inputs.dtype == np.float64
+
+from_lists = neural_net.Network(xor.architecture, 0.5, seed=1)
+from_arrays = neural_net.Network(xor.architecture, 0.5, seed=1)
+config = neural_net.TrainingConfig(epochs=200)
+expected = from_lists.train(xor.inputs, xor.targets, config)
+report = from_arrays.train(inputs, targets, config)
+assert report.losses == expected.losses
+
+# Rows of a 2-D array, and other float dtypes, predict like lists
+for row, values in zip(inputs, xor.inputs):
+    assert from_arrays.predict(row) == from_lists.predict(values)
+    assert from_arrays.predict(row.astype(np.float32)) == from_lists.predict(values)
+
+output = np.asarray(from_arrays.predict(inputs[0]))
+assert output.shape == (1,) and output.dtype == np.float64
+"#,
+    );
+}
+
+#[test]
+fn test_checkpoint_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    run_python(&format!(
+        r#"
+import neural_net
+
+network = neural_net.Network([2, 3, 1], 0.3, activation="tanh", seed=7)
+for name in ["model.json", "model.bin", "model.json.gz"]:
+    path = {dir:?} + "/" + name
+    network.save(path)
+    loaded = neural_net.Network.load(path)
+    assert loaded.layers == [2, 3, 1]
+    assert loaded.learning_rate == 0.3
+    assert loaded.predict([1.0, 0.0]) == network.predict([1.0, 0.0])
+
+# An explicit format overrides the extension
+network.save({dir:?} + "/model.ckpt", format="bin")
+assert open({dir:?} + "/model.ckpt", "rb").read(4) == b"NNCK"
+"#,
+        dir = dir.path().display().to_string()
+    ));
+}
+
+#[test]
+fn test_errors_raise_network_error() {
+    run_python(
+        r#"
+import neural_net
+
+def raises(f, message):
+    try:
+        f()
+    except neural_net.NetworkError as e:
+        assert message in str(e), str(e)
+    else:
+        raise AssertionError("expected NetworkError: " + message)
+
+network = neural_net.Network([2, 2, 1])
+raises(lambda: network.predict([1.0]), "expected 2, got 1")
+raises(lambda: network.train([[0.0, 1.0]], [[1.0], [0.0]]), "1 input rows but 2 target rows")
+raises(lambda: network.train([[0.0, 1.0]], [[1.0]], neural_net.TrainingConfig(optimizer="rmsprop")), "Unknown optimizer")
+raises(lambda: neural_net.Network([2]), "Invalid architecture")
+raises(lambda: neural_net.Network([2, 1], activation="swish"), "Unknown activation")
+raises(lambda: neural_net.Network.load("/nonexistent/model.json"), "Failed to read checkpoint")
+raises(lambda: neural_net.get_example("nand3"), "Unknown example")
+assert issubclass(neural_net.NetworkError, Exception)
+"#,
+    );
+}
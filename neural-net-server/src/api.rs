@@ -0,0 +1,513 @@
+// Request and response types for the REST API
+//
+// These types are shared by the server handlers and the typed client so the
+// wire format is defined in exactly one place.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub use crate::error::ErrorResponse;
+
+/// Health check response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"status": "ok"}))]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+/// Example list response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"name": "xor", "description": "XOR gate", "architecture": [2, 3, 1]}))]
+pub struct ExampleInfo {
+    pub name: String,
+    pub description: String,
+    pub architecture: Vec<usize>,
+}
+
+/// Example detail response, including the training data
+///
+/// `inputs` and `targets` hold at most the server's `max_example_rows`
+/// rows; `truncated` is set when the example has more (`samples` is the full count).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"name": "and", "description": "AND gate", "architecture": [2, 2, 1], "inputs": [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]], "targets": [[0.0], [0.0], [0.0], [1.0]], "samples": 4, "truncated": false, "recommended_epochs": 5000, "recommended_learning_rate": 0.5, "activation": "sigmoid"}))]
+pub struct ExampleDetail {
+    pub name: String,
+    pub description: String,
+    pub architecture: Vec<usize>,
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+    pub samples: usize,
+    pub truncated: bool,
+    pub recommended_epochs: u32,
+    pub recommended_learning_rate: f64,
+    /// Activation function the server trains with
+    pub activation: String,
+}
+
+/// Train request
+///
+/// Trains on a built-in `example`, an uploaded `dataset_id`, or small
+/// `inputs` and `targets` sent inline. Datasets and inline data require an
+/// `architecture`; for examples it is optional and defaults to the example's
+/// recommended architecture.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"example": "xor", "epochs": 10000, "learning_rate": 0.5}))]
+pub struct TrainRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset_id: Option<String>,
+    /// Inline training inputs, one row per sample (at most 256 rows of 64 values)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Vec<Vec<f64>>>,
+    /// Inline training targets, one row per input row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<Vec<f64>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<Vec<usize>>,
+    pub epochs: u32,
+    pub learning_rate: f64,
+    /// Seed for reproducible weight initialization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Keep a checkpoint of the running job every this many epochs, for
+    /// download from `/api/jobs/{id}/checkpoint`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_interval: Option<u32>,
+    /// Activation for the hidden layers: sigmoid (default), tanh or relu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden_activation: Option<String>,
+    /// Activation for the output layer: sigmoid (default), tanh, relu or softmax
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_activation: Option<String>,
+    /// Smoothing factor in (0, 1] for the `loss_ema` reported in progress events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss_ema: Option<f64>,
+    /// http(s) URL the server POSTs a `JobNotification` to when the job ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+}
+
+/// Train response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "example": "xor", "epochs": 10000, "final_loss": 0.0012, "epochs_run": 10000, "duration_ms": 85, "accuracy": 1.0}))]
+pub struct TrainResponse {
+    pub model_id: String,
+    pub example: String,
+    pub epochs: u32,
+    pub final_loss: f64,
+    pub epochs_run: u32,
+    pub duration_ms: u64,
+    pub accuracy: f64,
+}
+
+/// Training stream `progress` event, sent after each epoch
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"epoch": 100, "loss": 0.21, "loss_ema": 0.23, "accuracy": 0.75, "eta_secs": 1.8, "lr": 0.5}))]
+pub struct ProgressEvent {
+    pub epoch: u32,
+    pub loss: f64,
+    /// Moving average of the loss, when the request set `loss_ema`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss_ema: Option<f64>,
+    /// Accuracy on the training data, omitted for large datasets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accuracy: Option<f64>,
+    /// Estimated seconds until training finishes, from the mean epoch time so far
+    pub eta_secs: f64,
+    /// Learning rate used for the epoch
+    pub lr: f64,
+}
+
+/// Lifecycle state of a training job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    /// Waiting for a free training slot
+    Queued,
+    Running,
+    /// Cancelled while queued; the job disappears once its request ends
+    Cancelled,
+}
+
+/// Status of a training job, also sent as the training stream `queued` event
+///
+/// `position` is 1 for the next job to run, and only set while queued.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"job_id": 7, "status": "queued", "position": 2}))]
+pub struct JobStatusResponse {
+    pub job_id: u64,
+    pub status: JobState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+}
+
+/// How a training job ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobOutcome {
+    /// Trained and stored, possibly stopped early by shutdown
+    Completed,
+    Failed,
+    /// Cancelled while queued
+    Cancelled,
+}
+
+/// Body POSTed to a training request's `callback_url` when the job ends
+///
+/// Completed jobs carry `model_id`, `final_loss` and `duration_ms`; failed and
+/// cancelled ones carry `error`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"job_id": 7, "status": "completed", "model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "final_loss": 0.0012, "duration_ms": 85}))]
+pub struct JobNotification {
+    pub job_id: u64,
+    pub status: JobOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_loss: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+/// Training stream `checkpoint` event, sent when the trained model has been
+/// written to the server's model directory
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "path": "models/4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10.json"}))]
+pub struct CheckpointEvent {
+    pub model_id: String,
+    pub path: String,
+}
+
+/// Uploaded dataset summary
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"dataset_id": "9b2d7c41-1e3f-4a6b-8c5d-0f1e2d3c4b5a", "rows": 4, "inputs": 2, "targets": 1}))]
+pub struct DatasetInfo {
+    pub dataset_id: String,
+    pub rows: usize,
+    pub inputs: usize,
+    pub targets: usize,
+}
+
+/// Eval request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "input": [1.0, 0.0]}))]
+pub struct EvalRequest {
+    pub model_id: String,
+    pub input: Vec<f64>,
+}
+
+/// Eval response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"output": [0.97]}))]
+pub struct EvalResponse {
+    pub output: Vec<f64>,
+}
+
+/// Request to stream a model's outputs for many inputs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"inputs": [[0.0, 0.0], [1.0, 0.0]]}))]
+pub struct EvalStreamRequest {
+    pub inputs: Vec<Vec<f64>>,
+}
+
+/// One streamed output, at the position of its input in the request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"index": 1, "output": [0.97]}))]
+pub struct EvalStreamOutput {
+    pub index: usize,
+    pub output: Vec<f64>,
+}
+
+/// Final event of an eval stream
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"count": 2}))]
+pub struct EvalStreamComplete {
+    /// Number of outputs sent
+    pub count: usize,
+}
+
+/// How an ensemble combines its members' outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EnsembleMethod {
+    /// Element-wise mean of the outputs
+    #[default]
+    Mean,
+    /// Majority vote on the predicted class; the winning class is returned
+    /// one-hot (or as 0/1 for single-output models). Ties go to the lowest class.
+    Vote,
+}
+
+/// Ensemble eval request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_ids": ["4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "0d9e8f7a-6b5c-4d3e-2f1a-0b9c8d7e6f5a"], "input": [1.0, 0.0], "method": "mean"}))]
+pub struct EnsembleRequest {
+    pub model_ids: Vec<String>,
+    pub input: Vec<f64>,
+    #[serde(default)]
+    pub method: EnsembleMethod,
+}
+
+/// One ensemble member's output
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "output": [0.97]}))]
+pub struct ModelOutput {
+    pub model_id: String,
+    pub output: Vec<f64>,
+}
+
+/// Ensemble eval response: the combined output and each member's output
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"output": [0.95], "method": "mean", "outputs": [{"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "output": [0.97]}, {"model_id": "0d9e8f7a-6b5c-4d3e-2f1a-0b9c8d7e6f5a", "output": [0.93]}]}))]
+pub struct EnsembleResponse {
+    pub output: Vec<f64>,
+    pub method: EnsembleMethod,
+    pub outputs: Vec<ModelOutput>,
+}
+
+/// Model info response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "example": "xor", "architecture": [2, 3, 1], "epochs": 10000, "learning_rate": 0.5, "total_parameters": 13, "final_loss": 0.0012, "epochs_run": 10000, "duration_ms": 85, "accuracy": 1.0, "name": "xor baseline", "tags": ["demo"], "notes": "Trained with default settings", "created_at": "2025-10-13T12:00:00.000000Z"}))]
+pub struct ModelInfoResponse {
+    pub model_id: String,
+    pub example: String,
+    pub architecture: Vec<usize>,
+    pub epochs: u32,
+    pub learning_rate: f64,
+    pub total_parameters: usize,
+    pub final_loss: f64,
+    pub epochs_run: u32,
+    pub duration_ms: u64,
+    pub accuracy: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// When the model finished training, as an RFC 3339 UTC timestamp
+    #[serde(default)]
+    pub created_at: String,
+}
+
+/// Model metadata update
+///
+/// Fields left out are unchanged. `tags` replaces the whole tag list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"name": "xor baseline", "tags": ["demo"], "learning_rate": 0.1}))]
+pub struct UpdateModelRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// New learning rate for the stored network, used when training continues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate: Option<f64>,
+}
+
+/// Confusion matrix request: labelled samples to classify
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"inputs": [[0.9, 0.8], [0.1, 0.9]], "targets": [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0]]}))]
+pub struct ConfusionRequest {
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+}
+
+/// Precision, recall and F1 score for one class
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"class": 0, "precision": 1.0, "recall": 0.5, "f1": 0.6667, "support": 2}))]
+pub struct ClassMetrics {
+    pub class: usize,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    pub support: usize,
+}
+
+/// Confusion matrix response
+///
+/// `confusion_matrix[actual][predicted]` counts samples, with one class per
+/// output neuron.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"confusion_matrix": [[1, 1], [0, 2]], "classes": [{"class": 0, "precision": 1.0, "recall": 0.5, "f1": 0.6667, "support": 2}, {"class": 1, "precision": 0.6667, "recall": 1.0, "f1": 0.8, "support": 2}], "accuracy": 0.75}))]
+pub struct ConfusionResponse {
+    pub confusion_matrix: Vec<Vec<usize>>,
+    pub classes: Vec<ClassMetrics>,
+    pub accuracy: f64,
+}
+
+/// Request to score a stored model on an example
+///
+/// `example` defaults to the data the model was trained on: its built-in
+/// example or uploaded dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"example": "xor"}))]
+pub struct EvaluateRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+}
+
+/// A model's output for one sample of an evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"input": [1.0, 0.0], "target": [1.0], "output": [0.97], "correct": true}))]
+pub struct EvaluatedSample {
+    pub input: Vec<f64>,
+    pub target: Vec<f64>,
+    pub output: Vec<f64>,
+    /// Whether the output's class matches the target's
+    pub correct: bool,
+}
+
+/// Score of a stored model on an example
+///
+/// `confusion_matrix[actual][predicted]` counts samples and is only present
+/// for multi-output (one-hot) models.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "example": "xor", "loss": 0.0012, "accuracy": 1.0, "samples": [{"input": [1.0, 0.0], "target": [1.0], "output": [0.97], "correct": true}]}))]
+pub struct EvaluateResponse {
+    pub model_id: String,
+    pub example: String,
+    /// Mean squared error over the example's samples
+    pub loss: f64,
+    pub accuracy: f64,
+    pub samples: Vec<EvaluatedSample>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confusion_matrix: Option<Vec<Vec<usize>>>,
+}
+
+/// Weight statistics for one layer of a model
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"layer": 0, "rows": 3, "cols": 2, "mean": 0.41, "std": 2.7, "min": -4.9, "max": 5.3, "norm": 9.6}))]
+pub struct LayerStats {
+    /// Weight layer index (0 = input to first hidden)
+    pub layer: usize,
+    pub rows: usize,
+    pub cols: usize,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    /// L2 norm of the layer's weights
+    pub norm: f64,
+}
+
+/// Parameter statistics of a model, for spotting dead or exploded layers
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "layers": [{"layer": 0, "rows": 3, "cols": 2, "mean": 0.41, "std": 2.7, "min": -4.9, "max": 5.3, "norm": 9.6}, {"layer": 1, "rows": 1, "cols": 3, "mean": 1.2, "std": 5.8, "min": -6.1, "max": 7.4, "norm": 10.3}], "total_norm": 14.1, "prediction_cache": {"capacity": 256, "entries": 4, "hits": 96, "misses": 4}}))]
+pub struct ModelStatsResponse {
+    pub model_id: String,
+    pub layers: Vec<LayerStats>,
+    /// L2 norm over the weights of every layer
+    pub total_norm: f64,
+    /// Usage of the model's prediction cache, once it has been evaluated on
+    /// a server with `prediction_cache_size` set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prediction_cache: Option<PredictionCacheStats>,
+}
+
+/// A matrix as `rows`, `cols` and row-major `data`, the layout `Matrix`
+/// serializes to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"rows": 1, "cols": 3, "data": [6.1, -5.8, 7.4]}))]
+pub struct MatrixData {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f64>,
+}
+
+/// Raw weights and biases of a model, input side first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "weights": [{"rows": 3, "cols": 2, "data": [4.9, -4.7, 5.3, 3.1, -2.6, -2.8]}, {"rows": 1, "cols": 3, "data": [6.1, -5.8, 7.4]}], "biases": [{"rows": 3, "cols": 1, "data": [-2.1, 1.4, 0.3]}, {"rows": 1, "cols": 1, "data": [-2.9]}]}))]
+pub struct ModelWeightsResponse {
+    pub model_id: String,
+    /// One matrix per weight layer, `rows` outputs by `cols` inputs
+    pub weights: Vec<MatrixData>,
+    /// One column vector per weight layer
+    pub biases: Vec<MatrixData>,
+}
+
+/// Usage of a model's cache of `/api/eval` results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"capacity": 256, "entries": 4, "hits": 96, "misses": 4}))]
+pub struct PredictionCacheStats {
+    /// Most outputs the cache holds
+    pub capacity: usize,
+    /// Outputs currently cached
+    pub entries: usize,
+    /// Evaluations answered from the cache
+    pub hits: u64,
+    /// Evaluations that ran the network
+    pub misses: u64,
+}
+
+/// Loss of a model at one epoch of its training run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"epoch": 10, "loss": 0.183, "val_loss": 0.201}))]
+pub struct HistoryPoint {
+    pub epoch: u32,
+    pub loss: f64,
+    /// Loss on the validation split, when training held one out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub val_loss: Option<f64>,
+}
+
+/// Loss and accuracy of one model in a comparison
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "loss": 0.0012, "accuracy": 1.0}))]
+pub struct ModelScore {
+    pub model_id: String,
+    /// Mean squared error over the example's samples
+    pub loss: f64,
+    pub accuracy: f64,
+}
+
+/// Both models' outputs for one sample of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"input": [1.0, 0.0], "target": [1.0], "output_a": [0.97], "output_b": [0.52]}))]
+pub struct SampleComparison {
+    pub input: Vec<f64>,
+    pub target: Vec<f64>,
+    pub output_a: Vec<f64>,
+    pub output_b: Vec<f64>,
+}
+
+/// Side-by-side comparison of two models on an example
+///
+/// `weight_deltas[i]` is the largest absolute difference between the models'
+/// weights in layer `i`; it is only present when the architectures match.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"example": "xor", "a": {"model_id": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "loss": 0.0012, "accuracy": 1.0}, "b": {"model_id": "0d9e8f7a-6b5c-4d3e-2f1a-0b9c8d7e6f5a", "loss": 0.25, "accuracy": 0.5}, "samples": [{"input": [1.0, 0.0], "target": [1.0], "output_a": [0.97], "output_b": [0.52]}], "weight_deltas": [3.2, 4.1]}))]
+pub struct CompareResponse {
+    pub example: String,
+    pub a: ModelScore,
+    pub b: ModelScore,
+    pub samples: Vec<SampleComparison>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight_deltas: Option<Vec<f64>>,
+}
+
+/// Pairwise comparison request: two models run on the same inputs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"model_a": "4f1c2a9e-6d3b-4b8a-9a51-2f0c7d3e8b10", "model_b": "0d9e8f7a-6b5c-4d3e-2f1a-0b9c8d7e6f5a", "inputs": [[1.0, 0.0], [1.0, 1.0]]}))]
+pub struct PairwiseCompareRequest {
+    pub model_a: String,
+    pub model_b: String,
+    pub inputs: Vec<Vec<f64>>,
+}
+
+/// Both models' outputs for each input, in request order
+///
+/// `diff[i]` is `outputs_a[i] - outputs_b[i]`, element by element.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({"outputs_a": [[0.03], [0.96]], "outputs_b": [[0.11], [0.81]], "diff": [[-0.08], [0.15]]}))]
+pub struct PairwiseCompareResponse {
+    pub outputs_a: Vec<Vec<f64>>,
+    pub outputs_b: Vec<Vec<f64>>,
+    pub diff: Vec<Vec<f64>>,
+}
@@ -0,0 +1,47 @@
+// API key authentication middleware
+
+use crate::AppState;
+use crate::error::ApiError;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// Require a configured API key on mutating requests
+///
+/// Read-only requests (GET, HEAD, OPTIONS) always pass. When no keys are
+/// configured every request passes. The key may be sent as
+/// `Authorization: Bearer <key>` or `X-Api-Key: <key>`; it is never logged
+/// or echoed back.
+pub async fn require_api_key(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let api_keys = &state.config.api_keys;
+    if api_keys.is_empty() || is_read_only(request.method()) {
+        return next.run(request).await;
+    }
+
+    match extract_key(request.headers()) {
+        None => ApiError::unauthorized("Missing API key").into_response(),
+        Some(key) if api_keys.iter().any(|k| k == key) => next.run(request).await,
+        Some(_) => ApiError::forbidden("Invalid API key").into_response(),
+    }
+}
+
+fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Read the key from `Authorization: Bearer` or `X-Api-Key`
+fn extract_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(value) = headers.get("authorization").and_then(|v| v.to_str().ok())
+        && let Some(key) = value.strip_prefix("Bearer ")
+    {
+        return Some(key.trim());
+    }
+
+    headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+}
@@ -0,0 +1,378 @@
+// Typed HTTP client for the neural network server API
+// Enabled with the `client` feature
+
+use crate::api::{
+    CompareResponse, ConfusionRequest, ConfusionResponse, DatasetInfo, EnsembleMethod,
+    EnsembleRequest, EnsembleResponse, EvalRequest, EvalResponse, EvaluateRequest, EvaluateResponse,
+    ExampleDetail, ExampleInfo, HistoryPoint, JobStatusResponse, ModelInfoResponse,
+    ModelStatsResponse, ModelWeightsResponse, PairwiseCompareRequest, PairwiseCompareResponse, TrainRequest,
+    TrainResponse, UpdateModelRequest,
+};
+use crate::jobs::{CHECKPOINT_EPOCH_HEADER, JobCheckpoint};
+use anyhow::Context;
+use neural_network::checkpoint::Checkpoint;
+use serde::de::DeserializeOwned;
+
+/// Client for the neural network REST API
+///
+/// Wraps the server endpoints with the same request/response types the
+/// server uses, so both sides stay in sync.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neural_net_server::api::TrainRequest;
+/// use neural_net_server::client::NeuralNetClient;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let client = NeuralNetClient::new("http://127.0.0.1:3000");
+/// let trained = client
+///     .train(&TrainRequest {
+///         example: Some("xor".to_string()),
+///         epochs: 10000,
+///         learning_rate: 0.5,
+///         ..Default::default()
+///     })
+///     .await?;
+/// let output = client.eval(&trained.model_id, vec![1.0, 0.0]).await?;
+/// println!("{:?}", output.output);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NeuralNetClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl NeuralNetClient {
+    /// Create a client for a server at `base_url` (e.g. `http://127.0.0.1:3000`)
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// List the built-in training examples
+    pub async fn list_examples(&self) -> anyhow::Result<Vec<ExampleInfo>> {
+        let response = self
+            .http
+            .get(self.url("/api/examples"))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch an example's training data and recommended settings
+    pub async fn example_detail(&self, name: &str) -> anyhow::Result<ExampleDetail> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/examples/{}", name)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Train a new model and return its summary
+    pub async fn train(&self, request: &TrainRequest) -> anyhow::Result<TrainResponse> {
+        let response = self
+            .http
+            .post(self.url("/api/train"))
+            .json(request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Train a model without storing it on the server and return its checkpoint
+    pub async fn train_inline(&self, request: &TrainRequest) -> anyhow::Result<Checkpoint> {
+        let response = self
+            .http
+            .post(self.url("/api/train/inline"))
+            .json(request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Evaluate a stored model on a single input
+    pub async fn eval(&self, model_id: &str, input: Vec<f64>) -> anyhow::Result<EvalResponse> {
+        let request = EvalRequest {
+            model_id: model_id.to_string(),
+            input,
+        };
+        let response = self
+            .http
+            .post(self.url("/api/eval"))
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Evaluate several models on one input and combine their outputs
+    pub async fn eval_ensemble(
+        &self,
+        model_ids: Vec<String>,
+        input: Vec<f64>,
+        method: EnsembleMethod,
+    ) -> anyhow::Result<EnsembleResponse> {
+        let request = EnsembleRequest {
+            model_ids,
+            input,
+            method,
+        };
+        let response = self
+            .http
+            .post(self.url("/api/eval/ensemble"))
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch information about a stored model
+    pub async fn model_info(&self, model_id: &str) -> anyhow::Result<ModelInfoResponse> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/models/{}", model_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// List stored models, optionally only those carrying `tag`
+    pub async fn list_models(&self, tag: Option<&str>) -> anyhow::Result<Vec<ModelInfoResponse>> {
+        let mut request = self.http.get(self.url("/api/models"));
+        if let Some(tag) = tag {
+            request = request.query(&[("tag", tag)]);
+        }
+        let response = request.send().await.context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Update a stored model's name, tags or notes
+    pub async fn update_model(
+        &self,
+        model_id: &str,
+        update: &UpdateModelRequest,
+    ) -> anyhow::Result<ModelInfoResponse> {
+        let response = self
+            .http
+            .patch(self.url(&format!("/api/models/{}", model_id)))
+            .json(update)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Confusion matrix and per-class metrics for a multi-output model
+    pub async fn confusion(
+        &self,
+        model_id: &str,
+        request: &ConfusionRequest,
+    ) -> anyhow::Result<ConfusionResponse> {
+        let response = self
+            .http
+            .post(self.url(&format!("/api/models/{}/confusion", model_id)))
+            .json(request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Score a stored model on an example, by default the one it was trained on
+    pub async fn evaluate_model(
+        &self,
+        model_id: &str,
+        request: &EvaluateRequest,
+    ) -> anyhow::Result<EvaluateResponse> {
+        let response = self
+            .http
+            .post(self.url(&format!("/api/models/{}/evaluate", model_id)))
+            .json(request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch per-layer weight statistics of a stored model
+    pub async fn model_stats(&self, model_id: &str) -> anyhow::Result<ModelStatsResponse> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/models/{}/stats", model_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch the raw weights and biases of a stored model
+    pub async fn model_weights(&self, model_id: &str) -> anyhow::Result<ModelWeightsResponse> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/models/{}/weights", model_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch the loss history of a stored model, keeping every `stride`-th epoch
+    pub async fn model_history(
+        &self,
+        model_id: &str,
+        stride: usize,
+    ) -> anyhow::Result<Vec<HistoryPoint>> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/models/{}/history", model_id)))
+            .query(&[("stride", stride)])
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Compare two models on a built-in example
+    pub async fn compare_models(
+        &self,
+        a: &str,
+        b: &str,
+        example: &str,
+    ) -> anyhow::Result<CompareResponse> {
+        let response = self
+            .http
+            .get(self.url("/api/models/compare"))
+            .query(&[("a", a), ("b", b), ("example", example)])
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Run two models on the same inputs and diff their outputs
+    pub async fn compare_outputs(
+        &self,
+        request: &PairwiseCompareRequest,
+    ) -> anyhow::Result<PairwiseCompareResponse> {
+        let response = self
+            .http
+            .post(self.url("/api/compare"))
+            .json(request)
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Upload a CSV dataset with `inputs` input columns followed by `targets` target columns
+    pub async fn upload_dataset(
+        &self,
+        csv: impl Into<String>,
+        inputs: usize,
+        targets: usize,
+    ) -> anyhow::Result<DatasetInfo> {
+        let response = self
+            .http
+            .post(self.url(&format!("/api/datasets?inputs={}&targets={}", inputs, targets)))
+            .header(reqwest::header::CONTENT_TYPE, "text/csv")
+            .body(csv.into())
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// List uploaded datasets
+    pub async fn list_datasets(&self) -> anyhow::Result<Vec<DatasetInfo>> {
+        let response = self
+            .http
+            .get(self.url("/api/datasets"))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Fetch the status of a training job, including its queue position
+    pub async fn job_status(&self, job_id: u64) -> anyhow::Result<JobStatusResponse> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/jobs/{}", job_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Cancel a queued training job
+    pub async fn cancel_job(&self, job_id: u64) -> anyhow::Result<JobStatusResponse> {
+        let response = self
+            .http
+            .delete(self.url(&format!("/api/jobs/{}", job_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        parse_response(response).await
+    }
+
+    /// Download the latest checkpoint of a running job
+    ///
+    /// Job IDs come from the `x-job-id` header of a streaming training
+    /// response. The bytes load with `Network::load_checkpoint_from_slice`.
+    pub async fn job_checkpoint(&self, job_id: u64) -> anyhow::Result<JobCheckpoint> {
+        let response = self
+            .http
+            .get(self.url(&format!("/api/jobs/{}/checkpoint", job_id)))
+            .send()
+            .await
+            .context("Failed to reach server")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Server returned {}: {}", status, body);
+        }
+
+        let epoch = response
+            .headers()
+            .get(CHECKPOINT_EPOCH_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .context("Checkpoint response is missing its epoch")?;
+        let bytes = response.bytes().await.context("Failed to read checkpoint")?;
+        Ok(JobCheckpoint {
+            epoch,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+/// Decode a successful JSON response, or turn an error status into an error
+async fn parse_response<T: DeserializeOwned>(response: reqwest::Response) -> anyhow::Result<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Server returned {}: {}", status, body);
+    }
+
+    response
+        .json::<T>()
+        .await
+        .context("Failed to parse server response")
+}
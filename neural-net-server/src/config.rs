@@ -0,0 +1,292 @@
+// Server configuration
+//
+// Settings come from three layers, each overriding the previous one:
+// built-in defaults, an optional TOML file, and `NN_SERVER_*` environment
+// variables. The server binary applies its command-line flags last.
+
+use crate::logging::LogFormat;
+use crate::rate_limit::RateLimitConfig;
+use anyhow::Context;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Runtime configuration for the server
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Address to bind to (e.g. `127.0.0.1:3000`)
+    pub addr: String,
+
+    /// Directory of static files for the web UI
+    pub static_dir: PathBuf,
+
+    /// Directory where trained models are persisted. `None` keeps models in memory only.
+    pub model_dir: Option<PathBuf>,
+
+    /// Origins allowed by CORS; `"*"` allows any. With no `cors_*` setting
+    /// given, CORS is fully permissive.
+    pub cors_origins: Vec<String>,
+
+    /// Methods allowed by CORS. Empty allows any method.
+    pub cors_methods: Vec<String>,
+
+    /// Whether CORS requests may carry credentials (cookies or
+    /// `Authorization`). Requires explicit `cors_origins`.
+    pub cors_allow_credentials: bool,
+
+    /// Upper bound on epochs accepted by training requests. `None` means unlimited.
+    pub max_epochs: Option<u32>,
+
+    /// Number of training runs allowed at once; later ones wait in a queue.
+    /// `None` means unlimited.
+    pub max_concurrent_trainings: Option<usize>,
+
+    /// API keys accepted on mutating routes. Empty disables authentication.
+    pub api_keys: Vec<String>,
+
+    /// Rate limit for training endpoints. `None` disables limiting.
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// How long shutdown waits for running trainings to stop and persist
+    pub shutdown_timeout_secs: u64,
+
+    /// Largest number of rows accepted in an uploaded dataset
+    pub max_dataset_rows: usize,
+
+    /// Most rows of training data returned by the example detail endpoint
+    pub max_example_rows: usize,
+
+    /// Most models kept at once; the least recently used are evicted beyond it.
+    /// `None` means unlimited.
+    pub max_models: Option<usize>,
+
+    /// Seconds a model may go unused before it is evicted. `None` keeps models forever.
+    pub model_ttl_secs: Option<u64>,
+
+    /// Seconds a training run may go without completing an epoch before it
+    /// is stopped and fails with `training_stalled`. `None` never stops it.
+    pub stall_timeout_secs: Option<u64>,
+
+    /// Outputs each model remembers for repeated `/api/eval` inputs. `None`
+    /// disables the cache.
+    pub prediction_cache_size: Option<usize>,
+
+    /// Most verbose log level written: error, warn, info, debug, trace or off
+    pub log_level: String,
+
+    /// Log line format
+    pub log_format: LogFormat,
+
+    /// Whether training requests may set a `callback_url` webhook
+    pub webhooks: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:3000".to_string(),
+            static_dir: PathBuf::from("static"),
+            model_dir: None,
+            cors_origins: Vec::new(),
+            cors_methods: Vec::new(),
+            cors_allow_credentials: false,
+            max_epochs: None,
+            max_concurrent_trainings: None,
+            api_keys: Vec::new(),
+            rate_limit: None,
+            shutdown_timeout_secs: 10,
+            max_dataset_rows: 10_000,
+            max_example_rows: 50,
+            max_models: None,
+            model_ttl_secs: None,
+            stall_timeout_secs: None,
+            prediction_cache_size: None,
+            log_level: "info".to_string(),
+            log_format: LogFormat::default(),
+            webhooks: true,
+        }
+    }
+}
+
+impl From<&str> for ServerConfig {
+    fn from(addr: &str) -> Self {
+        Self {
+            addr: addr.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<String> for ServerConfig {
+    fn from(addr: String) -> Self {
+        Self {
+            addr,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&String> for ServerConfig {
+    fn from(addr: &String) -> Self {
+        Self::from(addr.as_str())
+    }
+}
+
+impl ServerConfig {
+    /// Load configuration from a TOML file. Missing keys keep their defaults.
+    pub fn from_toml(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    /// Defaults overridden by `NN_SERVER_*` environment variables
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::default().with_env()
+    }
+
+    /// Apply `NN_SERVER_*` environment variable overrides
+    pub fn with_env(self) -> anyhow::Result<Self> {
+        self.with_overrides(|key| std::env::var(key).ok())
+    }
+
+    /// Apply overrides from an environment-like lookup
+    ///
+    /// Recognized keys:
+    /// - `NN_SERVER_ADDR`
+    /// - `NN_SERVER_STATIC_DIR`
+    /// - `NN_SERVER_MODEL_DIR`
+    /// - `NN_SERVER_CORS_ORIGINS` (comma-separated)
+    /// - `NN_SERVER_CORS_METHODS` (comma-separated)
+    /// - `NN_SERVER_CORS_ALLOW_CREDENTIALS` (`true` or `false`)
+    /// - `NN_SERVER_MAX_EPOCHS`
+    /// - `NN_SERVER_MAX_CONCURRENT_TRAININGS`
+    /// - `NN_SERVER_API_KEYS` (comma-separated)
+    /// - `NN_SERVER_RATE_LIMIT` (requests per minute)
+    /// - `NN_SERVER_SHUTDOWN_TIMEOUT_SECS`
+    /// - `NN_SERVER_MAX_DATASET_ROWS`
+    /// - `NN_SERVER_MAX_EXAMPLE_ROWS`
+    /// - `NN_SERVER_MAX_MODELS`
+    /// - `NN_SERVER_MODEL_TTL_SECS`
+    /// - `NN_SERVER_STALL_TIMEOUT_SECS`
+    /// - `NN_SERVER_PREDICTION_CACHE_SIZE`
+    /// - `NN_SERVER_LOG_LEVEL`
+    /// - `NN_SERVER_LOG_FORMAT` (`pretty` or `json`)
+    /// - `NN_SERVER_WEBHOOKS` (`true` or `false`)
+    pub fn with_overrides<F>(mut self, lookup: F) -> anyhow::Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(addr) = lookup("NN_SERVER_ADDR") {
+            self.addr = addr;
+        }
+        if let Some(dir) = lookup("NN_SERVER_STATIC_DIR") {
+            self.static_dir = PathBuf::from(dir);
+        }
+        if let Some(dir) = lookup("NN_SERVER_MODEL_DIR") {
+            self.model_dir = Some(PathBuf::from(dir));
+        }
+        if let Some(origins) = lookup("NN_SERVER_CORS_ORIGINS") {
+            self.cors_origins = split_list(&origins);
+        }
+        if let Some(methods) = lookup("NN_SERVER_CORS_METHODS") {
+            self.cors_methods = split_list(&methods);
+        }
+        if let Some(allowed) = lookup("NN_SERVER_CORS_ALLOW_CREDENTIALS") {
+            self.cors_allow_credentials = allowed
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_CORS_ALLOW_CREDENTIALS: {}", allowed))?;
+        }
+        if let Some(epochs) = lookup("NN_SERVER_MAX_EPOCHS") {
+            self.max_epochs = Some(
+                epochs
+                    .parse()
+                    .with_context(|| format!("Invalid NN_SERVER_MAX_EPOCHS: {}", epochs))?,
+            );
+        }
+        if let Some(limit) = lookup("NN_SERVER_MAX_CONCURRENT_TRAININGS") {
+            self.max_concurrent_trainings = Some(
+                limit
+                    .parse()
+                    .with_context(|| format!("Invalid NN_SERVER_MAX_CONCURRENT_TRAININGS: {}", limit))?,
+            );
+        }
+        if let Some(keys) = lookup("NN_SERVER_API_KEYS") {
+            self.api_keys = split_list(&keys);
+        }
+        if let Some(rate) = lookup("NN_SERVER_RATE_LIMIT") {
+            self.rate_limit = Some(RateLimitConfig {
+                requests_per_minute: rate
+                    .parse()
+                    .with_context(|| format!("Invalid NN_SERVER_RATE_LIMIT: {}", rate))?,
+            });
+        }
+        if let Some(timeout) = lookup("NN_SERVER_SHUTDOWN_TIMEOUT_SECS") {
+            self.shutdown_timeout_secs = timeout
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_SHUTDOWN_TIMEOUT_SECS: {}", timeout))?;
+        }
+        if let Some(rows) = lookup("NN_SERVER_MAX_DATASET_ROWS") {
+            self.max_dataset_rows = rows
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_MAX_DATASET_ROWS: {}", rows))?;
+        }
+        if let Some(rows) = lookup("NN_SERVER_MAX_EXAMPLE_ROWS") {
+            self.max_example_rows = rows
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_MAX_EXAMPLE_ROWS: {}", rows))?;
+        }
+        if let Some(max) = lookup("NN_SERVER_MAX_MODELS") {
+            self.max_models = Some(
+                max.parse()
+                    .with_context(|| format!("Invalid NN_SERVER_MAX_MODELS: {}", max))?,
+            );
+        }
+        if let Some(ttl) = lookup("NN_SERVER_MODEL_TTL_SECS") {
+            self.model_ttl_secs = Some(
+                ttl.parse()
+                    .with_context(|| format!("Invalid NN_SERVER_MODEL_TTL_SECS: {}", ttl))?,
+            );
+        }
+        if let Some(timeout) = lookup("NN_SERVER_STALL_TIMEOUT_SECS") {
+            self.stall_timeout_secs = Some(
+                timeout
+                    .parse()
+                    .with_context(|| format!("Invalid NN_SERVER_STALL_TIMEOUT_SECS: {}", timeout))?,
+            );
+        }
+        if let Some(size) = lookup("NN_SERVER_PREDICTION_CACHE_SIZE") {
+            self.prediction_cache_size = Some(
+                size.parse()
+                    .with_context(|| format!("Invalid NN_SERVER_PREDICTION_CACHE_SIZE: {}", size))?,
+            );
+        }
+        if let Some(level) = lookup("NN_SERVER_LOG_LEVEL") {
+            self.log_level = level;
+        }
+        if let Some(format) = lookup("NN_SERVER_LOG_FORMAT") {
+            self.log_format = format
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_LOG_FORMAT: {}", format))?;
+        }
+        if let Some(enabled) = lookup("NN_SERVER_WEBHOOKS") {
+            self.webhooks = enabled
+                .parse()
+                .with_context(|| format!("Invalid NN_SERVER_WEBHOOKS: {}", enabled))?;
+        }
+
+        Ok(self)
+    }
+}
+
+/// Split a comma-separated list, dropping empty entries
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,334 @@
+// Uploaded training datasets
+//
+// Clients upload CSV data with `POST /api/datasets?inputs=N&targets=M`.
+// Each row holds N input columns followed by M target columns. A first row
+// that isn't numeric is treated as a header. Small datasets can also be sent
+// inline with a training request, without uploading them first.
+
+use crate::AppState;
+use crate::api::DatasetInfo;
+use crate::error::ApiError;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// How many invalid rows are listed in a validation error
+const MAX_REPORTED_ROW_ERRORS: usize = 10;
+
+/// Most samples accepted inline in a training request
+pub const MAX_INLINE_SAMPLES: usize = 256;
+
+/// Most input or target columns accepted inline in a training request
+pub const MAX_INLINE_FEATURES: usize = 64;
+
+/// A validated dataset held by the server
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+}
+
+impl Dataset {
+    /// Parse CSV text with `input_count` input and `target_count` target columns
+    ///
+    /// Returns one message per invalid row, each naming its 1-based line number.
+    pub fn from_csv(
+        csv: &str,
+        input_count: usize,
+        target_count: usize,
+    ) -> Result<Self, Vec<String>> {
+        let columns = input_count + target_count;
+        let mut inputs = Vec::new();
+        let mut targets = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, line) in csv.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let values: Result<Vec<f64>, _> =
+                line.split(',').map(|field| field.trim().parse::<f64>()).collect();
+
+            let values = match values {
+                Ok(values) => values,
+                // A non-numeric first line is a header
+                Err(_) if index == 0 => continue,
+                Err(e) => {
+                    errors.push(format!("Row {}: {}", line_number, e));
+                    continue;
+                }
+            };
+
+            if values.len() != columns {
+                errors.push(format!(
+                    "Row {}: expected {} columns, got {}",
+                    line_number,
+                    columns,
+                    values.len()
+                ));
+            } else if values.iter().any(|v| !v.is_finite()) {
+                errors.push(format!("Row {}: values must be finite", line_number));
+            } else {
+                inputs.push(values[..input_count].to_vec());
+                targets.push(values[input_count..].to_vec());
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        if inputs.is_empty() {
+            return Err(vec!["Dataset has no rows".to_string()]);
+        }
+
+        Ok(Self { inputs, targets })
+    }
+
+    /// Validate rows given as separate input and target vectors
+    ///
+    /// Every row must have the same widths as the first one, at least one
+    /// input and one target, and only finite values. Returns one message per
+    /// invalid row, each naming its 1-based row number.
+    pub fn from_rows(inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>) -> Result<Self, Vec<String>> {
+        if inputs.is_empty() {
+            return Err(vec!["Dataset has no rows".to_string()]);
+        }
+        if inputs.len() != targets.len() {
+            return Err(vec![format!(
+                "Dataset has {} input rows but {} target rows",
+                inputs.len(),
+                targets.len()
+            )]);
+        }
+
+        let input_count = inputs[0].len();
+        let target_count = targets[0].len();
+        if input_count == 0 || target_count == 0 {
+            return Err(vec![
+                "Datasets need at least one input and one target column".to_string(),
+            ]);
+        }
+
+        let mut errors = Vec::new();
+        for (index, (input, target)) in inputs.iter().zip(&targets).enumerate() {
+            let row_number = index + 1;
+            if input.len() != input_count {
+                errors.push(format!(
+                    "Row {}: expected {} inputs, got {}",
+                    row_number,
+                    input_count,
+                    input.len()
+                ));
+            } else if target.len() != target_count {
+                errors.push(format!(
+                    "Row {}: expected {} targets, got {}",
+                    row_number,
+                    target_count,
+                    target.len()
+                ));
+            } else if input.iter().chain(target).any(|v| !v.is_finite()) {
+                errors.push(format!("Row {}: values must be finite", row_number));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self { inputs, targets })
+    }
+
+    /// Short hash identifying the dataset's contents
+    ///
+    /// Two datasets with the same rows in the same order share a fingerprint.
+    pub fn fingerprint(&self) -> String {
+        // 64-bit FNV-1a over the row widths and value bits
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |word: u64| {
+            for byte in word.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        };
+        for row in self.inputs.iter().chain(&self.targets) {
+            feed(row.len() as u64);
+            for value in row {
+                feed(value.to_bits());
+            }
+        }
+        format!("{:016x}", hash)
+    }
+
+    fn info(&self, dataset_id: String) -> DatasetInfo {
+        DatasetInfo {
+            dataset_id,
+            rows: self.inputs.len(),
+            inputs: self.inputs[0].len(),
+            targets: self.targets[0].len(),
+        }
+    }
+}
+
+/// Query parameters describing an uploaded CSV
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(crate) struct UploadParams {
+    /// Number of input columns
+    inputs: usize,
+    /// Number of target columns, following the inputs
+    targets: usize,
+}
+
+/// Upload a CSV dataset
+#[utoipa::path(
+    post,
+    path = "/api/datasets",
+    params(UploadParams),
+    request_body(content = String, content_type = "text/csv", description = "CSV rows of inputs followed by targets"),
+    responses(
+        (status = 200, description = "Dataset stored", body = DatasetInfo),
+        (status = 400, description = "Invalid column counts", body = ErrorResponse),
+        (status = 422, description = "Invalid rows or too many rows", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn upload_dataset(
+    State(state): State<AppState>,
+    Query(params): Query<UploadParams>,
+    body: String,
+) -> Result<Json<DatasetInfo>, ApiError> {
+    if params.inputs == 0 || params.targets == 0 {
+        return Err(ApiError::bad_request(
+            "Datasets need at least one input and one target column",
+        ));
+    }
+
+    let dataset = Dataset::from_csv(&body, params.inputs, params.targets).map_err(invalid_dataset)?;
+
+    let max_rows = state.config.max_dataset_rows;
+    if dataset.inputs.len() > max_rows {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_dataset",
+            format!(
+                "Dataset has {} rows, but this server allows at most {}",
+                dataset.inputs.len(),
+                max_rows
+            ),
+        ));
+    }
+
+    let dataset_id = Uuid::new_v4().to_string();
+    let info = dataset.info(dataset_id.clone());
+    state.datasets.lock().unwrap().insert(dataset_id, dataset);
+
+    Ok(Json(info))
+}
+
+/// Validate a training request's inline data against the inline limits
+pub(crate) fn inline_dataset(inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>) -> Result<Dataset, ApiError> {
+    if inputs.len() > MAX_INLINE_SAMPLES {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_dataset",
+            format!(
+                "Inline data has {} rows, but at most {} are allowed; upload larger datasets instead",
+                inputs.len(),
+                MAX_INLINE_SAMPLES
+            ),
+        ));
+    }
+
+    let dataset = Dataset::from_rows(inputs, targets).map_err(invalid_dataset)?;
+    let widest = dataset.inputs[0].len().max(dataset.targets[0].len());
+    if widest > MAX_INLINE_FEATURES {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_dataset",
+            format!(
+                "Inline data has {} columns, but at most {} inputs or targets are allowed",
+                widest, MAX_INLINE_FEATURES
+            ),
+        ));
+    }
+
+    Ok(dataset)
+}
+
+/// Error listing the first few invalid rows of a dataset
+fn invalid_dataset(errors: Vec<String>) -> ApiError {
+    let shown = errors.len().min(MAX_REPORTED_ROW_ERRORS);
+    let mut message = errors[..shown].join("; ");
+    if errors.len() > shown {
+        message.push_str(&format!("; and {} more", errors.len() - shown));
+    }
+    ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_dataset", message)
+}
+
+/// List uploaded datasets
+#[utoipa::path(
+    get,
+    path = "/api/datasets",
+    responses((status = 200, description = "Uploaded datasets", body = [DatasetInfo]))
+)]
+pub(crate) async fn list_datasets(State(state): State<AppState>) -> Json<Vec<DatasetInfo>> {
+    let datasets = state.datasets.lock().unwrap();
+    let mut infos: Vec<DatasetInfo> = datasets
+        .iter()
+        .map(|(id, dataset)| dataset.info(id.clone()))
+        .collect();
+    infos.sort_by(|a, b| a.dataset_id.cmp(&b.dataset_id));
+
+    Json(infos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let csv = "a,b,out\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n";
+        let dataset = Dataset::from_csv(csv, 2, 1).unwrap();
+
+        assert_eq!(dataset.inputs.len(), 4);
+        assert_eq!(dataset.inputs[1], vec![0.0, 1.0]);
+        assert_eq!(dataset.targets[1], vec![1.0]);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_row_numbers() {
+        let csv = "0,0,0\n0,1\n1,x,1\n1,1,0\n";
+        let errors = Dataset::from_csv(csv, 2, 1).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Row 2:"));
+        assert!(errors[1].starts_with("Row 3:"));
+    }
+
+    #[test]
+    fn test_rows_must_match_the_first_row() {
+        let inputs = vec![vec![0.0, 0.0], vec![0.0], vec![1.0, f64::NAN], vec![1.0, 1.0]];
+        let targets = vec![vec![1.0]; 4];
+        let errors = Dataset::from_rows(inputs, targets).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Row 2:"));
+        assert!(errors[1].starts_with("Row 3:"));
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_contents() {
+        let nand = || Dataset::from_rows(vec![vec![0.0, 0.0], vec![1.0, 1.0]], vec![vec![1.0], vec![0.0]]);
+        let and = Dataset::from_rows(vec![vec![0.0, 0.0], vec![1.0, 1.0]], vec![vec![0.0], vec![1.0]]);
+
+        assert_eq!(nand().unwrap().fingerprint(), nand().unwrap().fingerprint());
+        assert_ne!(nand().unwrap().fingerprint(), and.unwrap().fingerprint());
+        assert_eq!(nand().unwrap().fingerprint().len(), 16);
+    }
+}
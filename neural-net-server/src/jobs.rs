@@ -0,0 +1,435 @@
+// Tracking of queued and running training jobs
+//
+// Every training run registers itself here for as long as it runs. With
+// `max_concurrent_trainings` set, jobs beyond the limit wait in a FIFO queue
+// and start in order as running jobs finish; queued jobs can be cancelled.
+// On shutdown the registry stops accepting new jobs, drops the queue, asks
+// the running ones to stop, and lets the server wait for them to store their
+// partial models.
+//
+// Jobs trained with a `checkpoint_interval` also keep their most recent
+// periodic checkpoint, which `GET /api/jobs/:id/checkpoint` serves while the
+// job runs.
+
+use crate::AppState;
+use crate::api::{JobState, JobStatusResponse};
+use crate::error::ApiError;
+use axum::{
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Response header carrying the ID of the job a request started
+pub const JOB_ID_HEADER: &str = "x-job-id";
+
+/// Response header carrying the epoch a job checkpoint was taken at
+pub const CHECKPOINT_EPOCH_HEADER: &str = "x-checkpoint-epoch";
+
+/// Registry of queued and running training jobs
+#[derive(Debug, Default)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: Mutex<Jobs>,
+    /// Most jobs running at once; later jobs wait in the queue
+    max_running: Option<usize>,
+    shutting_down: AtomicBool,
+    idle: Notify,
+    /// Notified whenever jobs start running or leave the queue
+    changed: Notify,
+}
+
+/// Registered jobs, guarded by one lock so the queue and counts agree
+#[derive(Debug, Default)]
+struct Jobs {
+    all: HashMap<u64, Arc<Job>>,
+    /// IDs of jobs waiting to run, next in line first
+    queue: VecDeque<u64>,
+    running: usize,
+}
+
+/// Shared state of one job
+#[derive(Debug, Default)]
+struct Job {
+    stop: Arc<AtomicBool>,
+    running: AtomicBool,
+    checkpoint: Mutex<Option<JobCheckpoint>>,
+}
+
+/// Most recent periodic checkpoint of a running job
+#[derive(Debug, Clone)]
+pub struct JobCheckpoint {
+    /// Epoch the checkpoint was taken after
+    pub epoch: u32,
+    /// Encoded checkpoint, readable with `Network::load_checkpoint_from_slice`
+    pub bytes: Vec<u8>,
+}
+
+impl JobRegistry {
+    /// Create a registry running at most `max_running` jobs at once
+    pub fn new(max_running: Option<usize>) -> Self {
+        Self {
+            max_running,
+            ..Self::default()
+        }
+    }
+
+    /// Register a new job, or refuse it if the server is shutting down
+    ///
+    /// The job runs straight away if a slot is free and queues otherwise;
+    /// call `JobGuard::wait_turn` before doing its work.
+    pub fn start(self: &Arc<Self>) -> Result<JobGuard, ApiError> {
+        let job = Arc::new(Job::default());
+        let mut jobs = self.jobs.lock().unwrap();
+
+        // Checked under the lock so a job can't slip in after `shutdown`
+        // has signalled everything it knows about
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return Err(ApiError::service_unavailable("Server is shutting down"));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        jobs.all.insert(id, job.clone());
+        jobs.queue.push_back(id);
+        self.promote(&mut jobs);
+
+        Ok(JobGuard {
+            id,
+            job,
+            registry: self.clone(),
+        })
+    }
+
+    /// Start queued jobs while there are free slots
+    fn promote(&self, jobs: &mut Jobs) {
+        while self.max_running.is_none_or(|max| jobs.running < max)
+            && let Some(id) = jobs.queue.pop_front()
+        {
+            jobs.all[&id].running.store(true, Ordering::SeqCst);
+            jobs.running += 1;
+        }
+        self.changed.notify_waiters();
+    }
+
+    fn get(&self, id: u64) -> Result<Arc<Job>, ApiError> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .all
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| ApiError::not_found("Job not found; it may have finished"))
+    }
+
+    /// Whether a job is queued (and where), running or cancelled
+    pub fn status(&self, id: u64) -> Result<JobStatusResponse, ApiError> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .all
+            .get(&id)
+            .ok_or_else(|| ApiError::not_found("Job not found; it may have finished"))?;
+
+        let position = jobs.queue.iter().position(|&queued| queued == id);
+        let status = match position {
+            Some(_) => JobState::Queued,
+            None if job.running.load(Ordering::SeqCst) => JobState::Running,
+            None => JobState::Cancelled,
+        };
+        Ok(JobStatusResponse {
+            job_id: id,
+            status,
+            position: position.map(|index| index + 1),
+        })
+    }
+
+    /// Cancel a queued job; running jobs can't be cancelled
+    pub fn cancel(&self, id: u64) -> Result<JobStatusResponse, ApiError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .all
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| ApiError::not_found("Job not found; it may have finished"))?;
+
+        if job.running.load(Ordering::SeqCst) {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "job_running",
+                "Job is already running; only queued jobs can be cancelled",
+            ));
+        }
+        job.stop.store(true, Ordering::SeqCst);
+        jobs.queue.retain(|&queued| queued != id);
+        self.changed.notify_waiters();
+
+        Ok(JobStatusResponse {
+            job_id: id,
+            status: JobState::Cancelled,
+            position: None,
+        })
+    }
+
+    /// Latest checkpoint of a running job
+    pub fn checkpoint(&self, id: u64) -> Result<JobCheckpoint, ApiError> {
+        let job = self.get(id)?;
+
+        job.checkpoint.lock().unwrap().clone().ok_or_else(|| {
+            ApiError::new(
+                StatusCode::CONFLICT,
+                "no_checkpoint",
+                "Job has not produced a checkpoint yet",
+            )
+        })
+    }
+
+    /// Number of jobs currently running
+    pub fn running(&self) -> usize {
+        self.jobs.lock().unwrap().running
+    }
+
+    /// Number of jobs waiting in the queue
+    pub fn queued(&self) -> usize {
+        self.jobs.lock().unwrap().queue.len()
+    }
+
+    /// Stop accepting jobs, drop the queue and ask every running job to stop
+    pub fn shutdown(&self) {
+        let mut jobs = self.jobs.lock().unwrap();
+        self.shutting_down.store(true, Ordering::SeqCst);
+        jobs.queue.clear();
+        for job in jobs.all.values() {
+            job.stop.store(true, Ordering::SeqCst);
+        }
+        self.changed.notify_waiters();
+    }
+
+    /// Wait until no jobs are queued or running. Returns `false` on timeout.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let notified = self.idle.notified();
+                if self.jobs.lock().unwrap().all.is_empty() {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// Handle held by a queued or running job; unregisters the job when dropped
+#[derive(Debug)]
+pub struct JobGuard {
+    id: u64,
+    job: Arc<Job>,
+    registry: Arc<JobRegistry>,
+}
+
+impl JobGuard {
+    /// ID clients use to refer to this job
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Wait until the job may run
+    ///
+    /// Fails if the job is cancelled while queued or the server shuts down.
+    pub async fn wait_turn(&self) -> Result<(), ApiError> {
+        loop {
+            let changed = self.registry.changed.notified();
+            if self.registry.shutting_down.load(Ordering::SeqCst) {
+                return Err(ApiError::service_unavailable("Server is shutting down"));
+            }
+            if self.job.stop.load(Ordering::SeqCst) && !self.job.running.load(Ordering::SeqCst) {
+                return Err(ApiError::new(
+                    StatusCode::CONFLICT,
+                    "job_cancelled",
+                    "Job was cancelled while queued",
+                ));
+            }
+            if self.job.running.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            changed.await;
+        }
+    }
+
+    /// Flag set when the job should stop early
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.job.stop.clone()
+    }
+
+    /// Function replacing the job's latest checkpoint, for use in training callbacks
+    pub fn checkpoint_recorder(&self) -> impl Fn(JobCheckpoint) + Send + 'static {
+        let job = self.job.clone();
+        move |checkpoint| *job.checkpoint.lock().unwrap() = Some(checkpoint)
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        let mut jobs = self.registry.jobs.lock().unwrap();
+        jobs.all.remove(&self.id);
+        if self.job.running.load(Ordering::SeqCst) {
+            jobs.running -= 1;
+        } else {
+            jobs.queue.retain(|&queued| queued != self.id);
+        }
+        self.registry.promote(&mut jobs);
+        self.registry.idle.notify_waiters();
+    }
+}
+
+/// Status of a job: queued with its position, running, or cancelled
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{id}",
+    params(("id" = u64, Path, description = "Job ID from the x-job-id header of a streaming training response")),
+    responses(
+        (status = 200, description = "Job status", body = JobStatusResponse),
+        (status = 404, description = "Job not found or already finished", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn job_status(
+    State(state): State<AppState>,
+    Path(job_id): Path<u64>,
+) -> Result<Json<JobStatusResponse>, ApiError> {
+    state.jobs.status(job_id).map(Json)
+}
+
+/// Cancel a queued job
+#[utoipa::path(
+    delete,
+    path = "/api/jobs/{id}",
+    params(("id" = u64, Path, description = "Job ID from the x-job-id header of a streaming training response")),
+    responses(
+        (status = 200, description = "Job cancelled", body = JobStatusResponse),
+        (status = 404, description = "Job not found or already finished", body = ErrorResponse),
+        (status = 409, description = "Job is already running", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn cancel_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<u64>,
+) -> Result<Json<JobStatusResponse>, ApiError> {
+    let status = state.jobs.cancel(job_id)?;
+    tracing::info!(job_id, "queued job cancelled");
+    Ok(Json(status))
+}
+
+/// Download the latest checkpoint of a running job
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{id}/checkpoint",
+    params(("id" = u64, Path, description = "Job ID from the x-job-id header of a streaming training response")),
+    responses(
+        (status = 200, description = "Binary checkpoint; the x-checkpoint-epoch header holds its epoch", content_type = "application/octet-stream", body = String),
+        (status = 404, description = "Job not found or already finished", body = ErrorResponse),
+        (status = 409, description = "No checkpoint produced yet", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn job_checkpoint(
+    State(state): State<AppState>,
+    Path(job_id): Path<u64>,
+) -> Result<Response, ApiError> {
+    let checkpoint = state.jobs.checkpoint(job_id)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::HeaderName::from_static(CHECKPOINT_EPOCH_HEADER), checkpoint.epoch.to_string()),
+        ],
+        checkpoint.bytes,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_signals_jobs_and_refuses_new_ones() {
+        let registry = Arc::new(JobRegistry::new(None));
+        let job = registry.start().unwrap();
+        let stop = job.stop_flag();
+
+        registry.shutdown();
+
+        assert!(stop.load(Ordering::Relaxed));
+        assert!(registry.start().is_err());
+        assert!(!registry.wait_idle(Duration::from_millis(10)).await);
+
+        drop(job);
+        assert!(registry.wait_idle(Duration::from_millis(10)).await);
+    }
+
+    #[test]
+    fn test_checkpoint_lifecycle() {
+        let registry = Arc::new(JobRegistry::new(None));
+        let job = registry.start().unwrap();
+
+        assert_eq!(registry.checkpoint(job.id()).unwrap_err().status, StatusCode::CONFLICT);
+
+        let record = job.checkpoint_recorder();
+        record(JobCheckpoint { epoch: 10, bytes: vec![1] });
+        record(JobCheckpoint { epoch: 20, bytes: vec![2] });
+        let latest = registry.checkpoint(job.id()).unwrap();
+        assert_eq!((latest.epoch, latest.bytes), (20, vec![2]));
+
+        let id = job.id();
+        drop(job);
+        assert_eq!(registry.checkpoint(id).unwrap_err().status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_queue_runs_jobs_in_order() {
+        let registry = Arc::new(JobRegistry::new(Some(1)));
+        let first = registry.start().unwrap();
+        let second = registry.start().unwrap();
+        let third = registry.start().unwrap();
+        let fourth = registry.start().unwrap();
+
+        first.wait_turn().await.unwrap();
+        assert_eq!(registry.status(first.id()).unwrap().status, JobState::Running);
+        assert_eq!(registry.status(second.id()).unwrap().position, Some(1));
+        assert_eq!(registry.status(third.id()).unwrap().position, Some(2));
+        assert_eq!((registry.running(), registry.queued()), (1, 3));
+
+        // Cancelling moves later jobs up and fails the cancelled job's wait
+        registry.cancel(third.id()).unwrap();
+        assert_eq!(registry.status(fourth.id()).unwrap().position, Some(2));
+        assert_eq!(third.wait_turn().await.unwrap_err().code, "job_cancelled");
+        assert_eq!(registry.cancel(first.id()).unwrap_err().code, "job_running");
+
+        // Finishing a job starts the next one in line
+        drop(first);
+        second.wait_turn().await.unwrap();
+        assert_eq!(registry.status(fourth.id()).unwrap().position, Some(1));
+        drop(third);
+        drop(second);
+        fourth.wait_turn().await.unwrap();
+        assert_eq!((registry.running(), registry.queued()), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_fails_queued_jobs() {
+        let registry = Arc::new(JobRegistry::new(Some(1)));
+        let running = registry.start().unwrap();
+        let queued = registry.start().unwrap();
+
+        registry.shutdown();
+
+        assert_eq!(queued.wait_turn().await.unwrap_err().code, "server_busy");
+        drop(queued);
+        drop(running);
+        assert!(registry.wait_idle(Duration::from_millis(10)).await);
+    }
+}
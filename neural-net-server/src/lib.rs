@@ -1,104 +1,330 @@
 // Neural Network Server Library
 // REST API server for neural network training and evaluation
 
+pub mod api;
+pub mod auth;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod config;
+pub mod datasets;
+pub mod error;
+pub mod jobs;
+pub mod logging;
+pub mod models;
+pub mod negotiate;
+pub mod openapi;
+mod prediction_cache;
+pub mod rate_limit;
+mod static_files;
+mod watchdog;
+pub mod webhooks;
+
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::{Json, sse::{Event, Sse}},
+    middleware,
+    response::{IntoResponse, Json, sse::{Event, Sse}},
     routing::{get, post},
     Router,
 };
-use futures::stream::{self, Stream};
+pub use api::{
+    CheckpointEvent, ClassMetrics, CompareResponse, ConfusionRequest, ConfusionResponse,
+    DatasetInfo, EnsembleMethod, EnsembleRequest, EnsembleResponse, ErrorResponse, EvalRequest,
+    EvalResponse, EvaluateRequest, EvaluateResponse, EvaluatedSample, ExampleDetail, ExampleInfo,
+    HealthResponse, HistoryPoint, JobNotification, JobOutcome, JobState, JobStatusResponse,
+    LayerStats, MatrixData, ModelInfoResponse, ModelOutput, ModelScore, ModelStatsResponse,
+    ModelWeightsResponse, PairwiseCompareRequest, PairwiseCompareResponse, PredictionCacheStats, ProgressEvent,
+    SampleComparison, TrainRequest, TrainResponse, UpdateModelRequest,
+};
+use config::ServerConfig;
+use datasets::Dataset;
+use error::ApiError;
+use jobs::{JobCheckpoint, JobGuard, JobRegistry};
+use models::{ModelMetadata, ModelStore, StoredModel};
+use negotiate::{Encoded, Negotiated};
+use rate_limit::RateLimiter;
+use watchdog::Watchdog;
+use futures::stream;
 use std::convert::Infallible;
 use neural_network::{
-    activations::SIGMOID,
+    activations::{ACTIVATIONS, Activation, SIGMOID, SOFTMAX},
+    checkpoint::{Checkpoint, CheckpointFormat, CheckpointMetadata},
     examples,
+    metrics::{AccuracyMode, argmax, class_of},
     network::Network,
-    training::{TrainingConfig, TrainingController},
+    training::{TrainingConfig, TrainingController, TrainingReport},
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
-    models: Arc<Mutex<HashMap<String, StoredModel>>>,
+    models: Arc<Mutex<ModelStore>>,
+    datasets: Arc<Mutex<HashMap<String, Dataset>>>,
+    config: Arc<ServerConfig>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    jobs: Arc<JobRegistry>,
+    /// Client delivering job webhooks, unless they are disabled
+    webhooks: Option<reqwest::Client>,
 }
 
 impl AppState {
-    fn new() -> Self {
-        Self {
-            models: Arc::new(Mutex::new(HashMap::new())),
+    /// Create the state, loading any models persisted in the model directory
+    fn new(config: ServerConfig) -> anyhow::Result<Self> {
+        let models = match &config.model_dir {
+            Some(dir) => models::load_models(dir)?,
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            models: Arc::new(Mutex::new(ModelStore::new(models))),
+            datasets: Arc::new(Mutex::new(HashMap::new())),
+            rate_limiter: config.rate_limit.map(|limit| Arc::new(RateLimiter::new(limit))),
+            jobs: Arc::new(JobRegistry::new(config.max_concurrent_trainings)),
+            webhooks: config.webhooks.then(webhooks::client),
+            config: Arc::new(config),
+        })
+    }
+
+    /// Resolve the data and architecture a training request refers to
+    fn training_data(&self, req: &TrainRequest) -> Result<TrainingData, ApiError> {
+        let inline = req.inputs.is_some() || req.targets.is_some();
+        let data = match (&req.example, &req.dataset_id, inline) {
+            (Some(name), None, false) => {
+                let example = examples::get_example(name)
+                    .ok_or_else(|| ApiError::bad_request(format!("Unknown example: {}", name)))?;
+                TrainingData {
+                    name: name.clone(),
+                    architecture: req
+                        .architecture
+                        .clone()
+                        .unwrap_or_else(|| example.recommended_arch.clone()),
+                    inputs: example.inputs,
+                    targets: example.targets,
+                }
+            }
+            (None, Some(dataset_id), false) => {
+                let architecture = req.architecture.clone().ok_or_else(|| {
+                    ApiError::bad_request("Training on a dataset requires an architecture")
+                })?;
+                let dataset = self
+                    .datasets
+                    .lock()
+                    .unwrap()
+                    .get(dataset_id)
+                    .cloned()
+                    .ok_or_else(|| ApiError::not_found("Dataset not found"))?;
+                TrainingData {
+                    name: format!("dataset:{}", dataset_id),
+                    architecture,
+                    inputs: dataset.inputs,
+                    targets: dataset.targets,
+                }
+            }
+            (None, None, true) => {
+                let architecture = req.architecture.clone().ok_or_else(|| {
+                    ApiError::bad_request("Training on inline data requires an architecture")
+                })?;
+                let (Some(inputs), Some(targets)) = (&req.inputs, &req.targets) else {
+                    return Err(ApiError::bad_request("Inline data needs both inputs and targets"));
+                };
+                let dataset = datasets::inline_dataset(inputs.clone(), targets.clone())?;
+                TrainingData {
+                    name: format!("inline:{}", dataset.fingerprint()),
+                    architecture,
+                    inputs: dataset.inputs,
+                    targets: dataset.targets,
+                }
+            }
+            _ => {
+                return Err(ApiError::bad_request(
+                    "Specify exactly one of example, dataset_id or inputs and targets",
+                ));
+            }
+        };
+
+        data.validate_architecture()?;
+        Ok(data)
+    }
+
+    /// Reject requests asking for more epochs than the server allows
+    fn check_epochs(&self, epochs: u32) -> Result<(), ApiError> {
+        match self.config.max_epochs {
+            Some(max) if epochs > max => Err(ApiError::bad_request(format!(
+                "Requested {} epochs, but this server allows at most {}",
+                epochs, max
+            ))),
+            _ => Ok(()),
         }
     }
-}
 
-/// Stored model with metadata
-#[derive(Clone)]
-struct StoredModel {
-    network: Network,
-    example: String,
-    epochs: u32,
-    learning_rate: f64,
-}
+    /// Check the parts of a training request that don't depend on its data
+    fn check_request(&self, req: &TrainRequest) -> Result<(), ApiError> {
+        self.check_epochs(req.epochs)?;
+        if req.checkpoint_interval == Some(0) {
+            return Err(ApiError::bad_request("checkpoint_interval must be at least 1"));
+        }
+        if let Some(alpha) = req.loss_ema
+            && !(alpha > 0.0 && alpha <= 1.0)
+        {
+            return Err(ApiError::bad_request(format!("loss_ema must be in (0, 1], got {}", alpha)));
+        }
+        if let Some(url) = &req.callback_url {
+            if self.webhooks.is_none() {
+                return Err(ApiError::bad_request("Webhooks are disabled on this server"));
+            }
+            webhooks::validate_callback_url(url)?;
+        }
+        Ok(())
+    }
 
-/// Health check response
-#[derive(Serialize)]
-struct HealthResponse {
-    status: String,
+    /// Tell the request's `callback_url`, if any, how its job ended
+    fn notify_job_end(
+        &self,
+        callback_url: Option<String>,
+        job_id: u64,
+        result: &Result<TrainResponse, ApiError>,
+    ) {
+        if let (Some(client), Some(url)) = (&self.webhooks, callback_url) {
+            webhooks::notify(client, url, JobNotification::new(job_id, result));
+        }
+    }
+
+    /// Store a trained model, writing it to the model directory if one is configured
+    ///
+    /// Returns the checkpoint path when the model was written to disk.
+    fn store_model(&self, model_id: String, model: StoredModel) -> Option<PathBuf> {
+        let mut saved = None;
+        if let Some(dir) = &self.config.model_dir {
+            match model.save(dir, &model_id) {
+                Ok(()) => saved = Some(dir.join(format!("{}.json", model_id))),
+                Err(e) => tracing::error!(
+                    model_id = %model_id,
+                    error = %format!("{:#}", e),
+                    "failed to persist model"
+                ),
+            }
+        }
+
+        self.models.lock().unwrap().insert(model_id, model);
+        self.evict_models();
+        saved
+    }
+
+    /// Stop `job`'s training if it stalls, when `stall_timeout_secs` is set
+    fn watch_for_stalls(
+        &self,
+        controller: &mut TrainingController,
+        job: &JobGuard,
+    ) -> Option<Watchdog> {
+        let timeout = std::time::Duration::from_secs(self.config.stall_timeout_secs?);
+        Some(Watchdog::watch(controller, timeout, job.stop_flag()))
+    }
+
+    /// Evict expired and least recently used models, deleting their files
+    fn evict_models(&self) {
+        let ttl = self.config.model_ttl_secs.map(std::time::Duration::from_secs);
+        if ttl.is_none() && self.config.max_models.is_none() {
+            return;
+        }
+
+        let evicted = self.models.lock().unwrap().evict(ttl, self.config.max_models);
+        if let Some(dir) = &self.config.model_dir {
+            for model_id in &evicted {
+                models::delete_model_files(dir, model_id);
+            }
+        }
+    }
 }
 
-/// Example list response
-#[derive(Serialize)]
-struct ExampleInfo {
+/// Inputs, targets and network shape for one training run
+struct TrainingData {
+    /// Example name, `dataset:<id>` for uploaded data, or
+    /// `inline:<fingerprint>` for data sent with the request
     name: String,
-    description: String,
     architecture: Vec<usize>,
+    inputs: Vec<Vec<f64>>,
+    targets: Vec<Vec<f64>>,
 }
 
-/// Train request
-#[derive(Deserialize)]
-struct TrainRequest {
-    example: String,
-    epochs: u32,
-    learning_rate: f64,
-}
+impl TrainingData {
+    /// Check the architecture fits the data's input and target widths
+    fn validate_architecture(&self) -> Result<(), ApiError> {
+        let arch = &self.architecture;
+        if arch.len() < 2 || arch.contains(&0) {
+            return Err(ApiError::bad_request(
+                "Architecture needs at least two non-empty layers",
+            ));
+        }
 
-/// Train response
-#[derive(Serialize)]
-struct TrainResponse {
-    model_id: String,
-    example: String,
-    epochs: u32,
+        let input_width = self.inputs[0].len();
+        let target_width = self.targets[0].len();
+        if arch[0] != input_width || arch[arch.len() - 1] != target_width {
+            return Err(ApiError::bad_request(format!(
+                "Architecture {:?} does not match data with {} inputs and {} targets",
+                arch, input_width, target_width
+            )));
+        }
+
+        Ok(())
+    }
 }
 
-/// Eval request
-#[derive(Deserialize)]
-struct EvalRequest {
-    model_id: String,
-    input: Vec<f64>,
+/// Default interval between SSE heartbeat events
+const DEFAULT_HEARTBEAT_MS: u64 = 1000;
+
+/// How often the SSE stream polls for new training progress
+const STREAM_POLL_INTERVAL_MS: u64 = 10;
+
+/// Largest training set for which stream progress events include accuracy
+const STREAM_ACCURACY_MAX_SAMPLES: usize = 1000;
+
+/// A message from a streaming training run, sent as one typed SSE event
+enum StreamMessage {
+    Queued(JobStatusResponse),
+    Progress(ProgressEvent),
+    Checkpoint(CheckpointEvent),
+    Complete(TrainResponse),
+    Error(ErrorResponse),
+    /// Keepalive while no other event is due, with an empty JSON object
+    Heartbeat,
 }
 
-/// Eval response
-#[derive(Serialize)]
-struct EvalResponse {
-    output: Vec<f64>,
+impl StreamMessage {
+    fn into_event(self) -> Event {
+        let (name, data) = match self {
+            StreamMessage::Queued(e) => ("queued", serde_json::to_string(&e)),
+            StreamMessage::Progress(e) => ("progress", serde_json::to_string(&e)),
+            StreamMessage::Checkpoint(e) => ("checkpoint", serde_json::to_string(&e)),
+            StreamMessage::Complete(e) => ("complete", serde_json::to_string(&e)),
+            StreamMessage::Error(e) => ("error", serde_json::to_string(&e)),
+            StreamMessage::Heartbeat => ("heartbeat", Ok("{}".to_string())),
+        };
+        Event::default()
+            .event(name)
+            .data(data.expect("stream events serialize to JSON"))
+    }
 }
 
-/// Model info response
-#[derive(Serialize)]
-struct ModelInfoResponse {
-    model_id: String,
-    example: String,
-    architecture: Vec<usize>,
-    epochs: u32,
-    learning_rate: f64,
-    total_parameters: usize,
+/// Query parameters for the training stream
+#[derive(Deserialize, utoipa::IntoParams)]
+struct StreamParams {
+    /// Milliseconds without progress before a heartbeat event is sent
+    heartbeat_ms: Option<u64>,
 }
 
 /// Health check endpoint
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, description = "Server is up", body = HealthResponse))
+)]
 async fn health() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok".to_string(),
@@ -106,6 +332,11 @@ async fn health() -> Json<HealthResponse> {
 }
 
 /// List available examples
+#[utoipa::path(
+    get,
+    path = "/api/examples",
+    responses((status = 200, description = "Built-in training examples, in a fixed order", body = [ExampleInfo]))
+)]
 async fn list_examples() -> Json<Vec<ExampleInfo>> {
     let example_names = examples::list_examples();
     let examples_info: Vec<ExampleInfo> = example_names
@@ -122,246 +353,878 @@ async fn list_examples() -> Json<Vec<ExampleInfo>> {
     Json(examples_info)
 }
 
+/// Get an example's training data and recommended settings
+#[utoipa::path(
+    get,
+    path = "/api/examples/{name}",
+    params(("name" = String, Path, description = "Example name, e.g. xor")),
+    responses(
+        (status = 200, description = "Example details and (possibly truncated) training data", body = ExampleDetail),
+        (status = 404, description = "Unknown example", body = ErrorResponse)
+    )
+)]
+async fn example_detail(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ExampleDetail>, ApiError> {
+    let mut example = examples::get_example(&name)
+        .ok_or_else(|| ApiError::not_found(format!("Unknown example: {}", name)))?;
+
+    let samples = example.inputs.len();
+    let max_rows = state.config.max_example_rows;
+    example.inputs.truncate(max_rows);
+    example.targets.truncate(max_rows);
+
+    Ok(Json(ExampleDetail {
+        name: example.name.to_string(),
+        description: example.description.to_string(),
+        architecture: example.recommended_arch,
+        inputs: example.inputs,
+        targets: example.targets,
+        samples,
+        truncated: samples > max_rows,
+        recommended_epochs: example.recommended_epochs,
+        recommended_learning_rate: example.recommended_lr,
+        activation: "sigmoid".to_string(),
+    }))
+}
+
 /// Train a new model
+#[utoipa::path(
+    post,
+    path = "/api/train",
+    request_body = TrainRequest,
+    responses(
+        (status = 200, description = "Model trained and stored", body = TrainResponse),
+        (status = 400, description = "Invalid example, dataset, architecture or epochs", body = ErrorResponse),
+        (status = 404, description = "Dataset not found", body = ErrorResponse),
+        (status = 409, description = "Cancelled while queued", body = ErrorResponse),
+        (status = 429, description = "Rate limited", body = ErrorResponse),
+        (status = 500, description = "Training failed, or stalled past `stall_timeout_secs`", body = ErrorResponse),
+        (status = 503, description = "Server shutting down", body = ErrorResponse)
+    )
+)]
 async fn train(
     State(state): State<AppState>,
     Json(req): Json<TrainRequest>,
-) -> Result<Json<TrainResponse>, (StatusCode, String)> {
-    // Get example
-    let example = examples::get_example(&req.example)
-        .ok_or_else(|| {
-            (
-                StatusCode::BAD_REQUEST,
-                format!("Unknown example: {}", req.example),
-            )
-        })?;
-
-    // Create network
-    let network = Network::new(example.recommended_arch.clone(), SIGMOID, req.learning_rate);
-
-    // Create training config
-    let config = TrainingConfig {
-        epochs: req.epochs,
-        checkpoint_interval: None,
-        checkpoint_path: None,
-        verbose: false,
-        example_name: Some(example.name.to_string()),
-    };
+) -> Result<Json<TrainResponse>, ApiError> {
+    // Resolve example or dataset
+    let data = state.training_data(&req)?;
+    state.check_request(&req)?;
+    let (hidden, output) = request_activations(&req)?;
+    let job = state.jobs.start()?;
+    let job_id = job.id();
+    let callback_url = req.callback_url.clone();
+    let notifier = state.clone();
 
-    // Train
-    let mut controller = TrainingController::new(network, config);
-    controller
-        .train(example.inputs.clone(), example.targets.clone())
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    // Store model
-    let model_id = Uuid::new_v4().to_string();
-    let stored_model = StoredModel {
-        network: controller.into_network(),
-        example: req.example.clone(),
-        epochs: req.epochs,
-        learning_rate: req.learning_rate,
+    let training = async move {
+        job.wait_turn().await?;
+
+        // Train off the async runtime so the server keeps answering, e.g. job
+        // status requests, while this job runs
+        tokio::task::spawn_blocking(move || {
+            tracing::info!(job_id = job.id(), example = %data.name, epochs = req.epochs, "training started");
+            let (network, report) = train_network(&state, &job, &data, &req, hidden, output)?;
+            let accuracy =
+                network.accuracy(&data.inputs, &data.targets, AccuracyMode::Classification);
+
+            // Store model
+            let model_id = Uuid::new_v4().to_string();
+            let stored_model = StoredModel::new(
+                network,
+                ModelMetadata {
+                    example: data.name.clone(),
+                    epochs: req.epochs,
+                    learning_rate: req.learning_rate,
+                    final_loss: report.final_loss,
+                    epochs_run: report.epochs_run,
+                    duration_ms: report.duration_ms,
+                    accuracy,
+                    name: None,
+                    tags: Vec::new(),
+                    notes: None,
+                    history: report.history.iter().map(HistoryPoint::from).collect(),
+                    created_at: models::timestamp_now(),
+                },
+            );
+
+            state.store_model(model_id.clone(), stored_model);
+            tracing::info!(
+                job_id = job.id(),
+                model_id = %model_id,
+                final_loss = report.final_loss,
+                duration_ms = report.duration_ms,
+                "training finished"
+            );
+
+            Ok(TrainResponse {
+                model_id,
+                example: data.name,
+                epochs: req.epochs,
+                final_loss: report.final_loss,
+                epochs_run: report.epochs_run,
+                duration_ms: report.duration_ms,
+                accuracy,
+            })
+        })
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
     };
 
-    state
+    // The job runs in its own task, so a client that disconnects doesn't
+    // drop it before the webhook for its end is sent
+    tokio::spawn(async move {
+        let result = training.await;
+        notifier.notify_job_end(callback_url, job_id, &result);
+        result
+    })
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))?
+    .map(Json)
+}
+
+/// Train a model and return its checkpoint without storing it
+///
+/// One-shot clients that keep the model themselves can use this instead of
+/// `/api/train`, so the server doesn't hold on to a model nobody will query.
+#[utoipa::path(
+    post,
+    path = "/api/train/inline",
+    request_body = TrainRequest,
+    responses(
+        (status = 200, description = "Trained model as a JSON checkpoint, loadable with `Network::from_checkpoint`", body = Object),
+        (status = 400, description = "Invalid example, dataset, architecture or epochs, or a callback_url", body = ErrorResponse),
+        (status = 404, description = "Dataset not found", body = ErrorResponse),
+        (status = 409, description = "Cancelled while queued", body = ErrorResponse),
+        (status = 429, description = "Rate limited", body = ErrorResponse),
+        (status = 500, description = "Training failed, or stalled past `stall_timeout_secs`", body = ErrorResponse),
+        (status = 503, description = "Server shutting down", body = ErrorResponse)
+    )
+)]
+async fn train_inline(
+    State(state): State<AppState>,
+    Json(req): Json<TrainRequest>,
+) -> Result<Json<Checkpoint>, ApiError> {
+    let data = state.training_data(&req)?;
+    state.check_request(&req)?;
+    if req.callback_url.is_some() {
+        // Job notifications point at a stored model
+        return Err(ApiError::bad_request("callback_url is not supported for inline training"));
+    }
+    let (hidden, output) = request_activations(&req)?;
+    let job = state.jobs.start()?;
+    job.wait_turn().await?;
+
+    let checkpoint = tokio::task::spawn_blocking(move || {
+        tracing::info!(job_id = job.id(), example = %data.name, epochs = req.epochs, "inline training started");
+        let (network, report) = train_network(&state, &job, &data, &req, hidden, output)?;
+        tracing::info!(
+            job_id = job.id(),
+            final_loss = report.final_loss,
+            duration_ms = report.duration_ms,
+            "inline training finished"
+        );
+
+        Ok::<_, ApiError>(network.to_checkpoint(CheckpointMetadata {
+            version: "1.0".to_string(),
+            example: data.name,
+            epoch: report.epochs_run,
+            total_epochs: req.epochs,
+            learning_rate: req.learning_rate,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            history: Vec::new(),
+        }))
+    })
+    .await
+    .map_err(|e| ApiError::internal(e.to_string()))??;
+
+    Ok(Json(checkpoint))
+}
+
+/// Evaluate a model
+#[utoipa::path(
+    post,
+    path = "/api/eval",
+    request_body(content = EvalRequest, content_type = "application/json", description = "JSON, or MessagePack with `Content-Type: application/msgpack`"),
+    responses(
+        (status = 200, description = "Network output, in MessagePack when requested with `Accept: application/msgpack`", body = EvalResponse),
+        (status = 400, description = "Wrong number of inputs or a non-finite input value", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse),
+        (status = 415, description = "Body is neither JSON nor MessagePack", body = ErrorResponse)
+    )
+)]
+async fn eval(
+    State(state): State<AppState>,
+    Negotiated { body: req, response_format }: Negotiated<EvalRequest>,
+) -> Result<Encoded<EvalResponse>, ApiError> {
+    // Wrong input sizes and non-finite values are rejected by `predict`
+    let output = state
         .models
         .lock()
         .unwrap()
-        .insert(model_id.clone(), stored_model);
+        .get(&req.model_id)?
+        .predict(&req.input, state.config.prediction_cache_size)?;
 
-    Ok(Json(TrainResponse {
-        model_id,
-        example: req.example,
-        epochs: req.epochs,
-    }))
+    Ok(Encoded(response_format, EvalResponse { output }))
 }
 
-/// Evaluate a model
-async fn eval(
+/// Evaluate several models on one input and combine their outputs
+#[utoipa::path(
+    post,
+    path = "/api/eval/ensemble",
+    request_body(content = EnsembleRequest, content_type = "application/json", description = "JSON, or MessagePack with `Content-Type: application/msgpack`"),
+    responses(
+        (status = 200, description = "Combined and individual outputs, in MessagePack when requested with `Accept: application/msgpack`", body = EnsembleResponse),
+        (status = 400, description = "No models, mismatched model shapes, wrong number of inputs or non-finite input values", body = ErrorResponse),
+        (status = 404, description = "Some models not found; the message lists them", body = ErrorResponse),
+        (status = 415, description = "Body is neither JSON nor MessagePack", body = ErrorResponse)
+    )
+)]
+async fn eval_ensemble(
     State(state): State<AppState>,
-    Json(req): Json<EvalRequest>,
-) -> Result<Json<EvalResponse>, (StatusCode, String)> {
-    // Get model
-    let models = state.models.lock().unwrap();
-    let stored_model = models
-        .get(&req.model_id)
-        .ok_or_else(|| (StatusCode::NOT_FOUND, "Model not found".to_string()))?;
-
-    // Clone network for evaluation
-    let mut network = stored_model.network.clone();
-
-    // Validate input dimensions
-    if req.input.len() != network.layers[0] {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            format!(
-                "Invalid input dimensions: expected {}, got {}",
-                network.layers[0],
-                req.input.len()
-            ),
+    Negotiated { body: req, response_format }: Negotiated<EnsembleRequest>,
+) -> Result<Encoded<EnsembleResponse>, ApiError> {
+    if req.model_ids.is_empty() {
+        return Err(ApiError::bad_request("An ensemble needs at least one model"));
+    }
+
+    let networks: Vec<Network> = {
+        let mut models = state.models.lock().unwrap();
+        let missing: Vec<&str> = req
+            .model_ids
+            .iter()
+            .filter(|id| !models.contains_key(id))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(ApiError::not_found(format!(
+                "Models not found: {}",
+                missing.join(", ")
+            )));
+        }
+        req.model_ids
+            .iter()
+            .map(|id| models.get(id).map(|model| model.network.clone()))
+            .collect::<Result<_, _>>()?
+    };
+
+    // Every member must accept the same input and produce the same output shape
+    let shape = |network: &Network| (network.layers[0], network.layers[network.layers.len() - 1]);
+    let (input_width, output_width) = shape(&networks[0]);
+    if networks.iter().any(|network| shape(network) != (input_width, output_width)) {
+        return Err(ApiError::bad_request(
+            "Ensemble models must share input and output sizes",
         ));
     }
+    if req.input.len() != input_width {
+        return Err(ApiError::bad_request(format!(
+            "Invalid input dimensions: expected {}, got {}",
+            input_width,
+            req.input.len()
+        )));
+    }
 
-    // Run prediction
-    let input_matrix = neural_network::matrix::Matrix::from(req.input);
-    let output = network.feed_forward(input_matrix);
+    let outputs: Vec<Vec<f64>> = networks
+        .iter()
+        .map(|network| network.predict(&req.input))
+        .collect::<Result<_, _>>()
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
 
-    Ok(Json(EvalResponse {
-        output: output.data,
+    Ok(Encoded(response_format, EnsembleResponse {
+        output: combine_outputs(&outputs, req.method),
+        method: req.method,
+        outputs: req
+            .model_ids
+            .into_iter()
+            .zip(outputs)
+            .map(|(model_id, output)| ModelOutput { model_id, output })
+            .collect(),
     }))
 }
 
+/// Combine equally sized outputs from the members of an ensemble
+fn combine_outputs(outputs: &[Vec<f64>], method: EnsembleMethod) -> Vec<f64> {
+    let width = outputs[0].len();
+    match method {
+        EnsembleMethod::Mean => (0..width)
+            .map(|i| outputs.iter().map(|output| output[i]).sum::<f64>() / outputs.len() as f64)
+            .collect(),
+        EnsembleMethod::Vote => {
+            let class_count = if width == 1 { 2 } else { width };
+            let mut votes = vec![0.0; class_count];
+            for output in outputs {
+                votes[class_of(output)] += 1.0;
+            }
+            let winner = argmax(&votes);
+
+            if width == 1 {
+                vec![winner as f64]
+            } else {
+                (0..width).map(|i| if i == winner { 1.0 } else { 0.0 }).collect()
+            }
+        }
+    }
+}
+
 /// Get model information
+#[utoipa::path(
+    get,
+    path = "/api/models/{id}",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    responses(
+        (status = 200, description = "Model details", body = ModelInfoResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
 async fn model_info(
     State(state): State<AppState>,
     Path(model_id): Path<String>,
-) -> Result<Json<ModelInfoResponse>, (StatusCode, String)> {
-    let models = state.models.lock().unwrap();
-    let stored_model = models
-        .get(&model_id)
-        .ok_or_else(|| (StatusCode::NOT_FOUND, "Model not found".to_string()))?;
-
-    // Calculate total parameters
-    let mut total_params = 0;
-    for weight in &stored_model.network.weights {
-        total_params += weight.rows * weight.cols;
-    }
-    for bias in &stored_model.network.biases {
-        total_params += bias.rows;
-    }
+) -> Result<Json<ModelInfoResponse>, ApiError> {
+    let mut models = state.models.lock().unwrap();
+    let stored_model = models.get(&model_id)?;
 
-    Ok(Json(ModelInfoResponse {
-        model_id,
-        example: stored_model.example.clone(),
-        architecture: stored_model.network.layers.clone(),
-        epochs: stored_model.epochs,
-        learning_rate: stored_model.learning_rate,
-        total_parameters: total_params,
-    }))
+    Ok(Json(stored_model.info(model_id)))
 }
 
 /// Train with SSE progress streaming
+///
+/// Each SSE event names its type in the `event:` field:
+/// - `queued` first if the job waits for a training slot, with data
+///   `JobStatusResponse`
+/// - `progress` after every epoch, with data `ProgressEvent`
+/// - `checkpoint` once the model is written to the model directory, with
+///   data `CheckpointEvent`
+/// - `complete` when the model is stored, with data `TrainResponse`
+/// - `error` if training fails or the job is cancelled while queued, with
+///   data `ErrorResponse`
+/// - `heartbeat` while no other event arrives, with data `{}`
+#[utoipa::path(
+    post,
+    path = "/api/train/stream",
+    params(StreamParams),
+    request_body = TrainRequest,
+    responses(
+        (status = 200, description = "Server-Sent Events stream of training progress", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Invalid example, dataset, architecture or epochs", body = ErrorResponse),
+        (status = 404, description = "Dataset not found", body = ErrorResponse),
+        (status = 429, description = "Rate limited", body = ErrorResponse),
+        (status = 503, description = "Server shutting down", body = ErrorResponse)
+    )
+)]
 async fn train_stream(
     State(state): State<AppState>,
+    Query(params): Query<StreamParams>,
     Json(req): Json<TrainRequest>,
-) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
-    // Get example
-    let example = examples::get_example(&req.example)
-        .ok_or_else(|| {
-            (
-                StatusCode::BAD_REQUEST,
-                format!("Unknown example: {}", req.example),
-            )
-        })?;
+) -> Result<impl IntoResponse, ApiError> {
+    // Resolve example or dataset
+    let data = state.training_data(&req)?;
+    state.check_request(&req)?;
+    let (hidden, output) = request_activations(&req)?;
+    let job = state.jobs.start()?;
 
     // Create channel for progress updates (use std mpsc for Send compatibility)
-    let (tx, rx) = std::sync::mpsc::channel::<(u32, f64)>();
+    let (tx, rx) = std::sync::mpsc::channel::<StreamMessage>();
 
     // Spawn blocking training task
-    let example_name = req.example.clone();
+    let TrainingData {
+        name: example_name,
+        architecture: arch,
+        inputs,
+        targets,
+    } = data;
     let epochs = req.epochs;
     let learning_rate = req.learning_rate;
+    let seed = req.seed;
     let state_clone = state.clone();
-    let inputs = example.inputs.clone();
-    let targets = example.targets.clone();
-    let arch = example.recommended_arch.clone();
-
-    tokio::task::spawn_blocking(move || {
-        // Create network
-        let network = Network::new(arch, SIGMOID, learning_rate);
-
-        // Create training config
-        let config = TrainingConfig {
-            epochs,
-            checkpoint_interval: None,
-            checkpoint_path: None,
-            verbose: false,
-            example_name: Some(example_name.clone()),
-        };
+    let job_id = job.id();
+    let request_id = logging::current_request_id();
+    let callback_url = req.callback_url.clone();
+
+    // Tell the client where it is in the queue before waiting for a slot
+    if let Ok(status) = state.jobs.status(job_id)
+        && status.status == JobState::Queued
+    {
+        tracing::info!(job_id, position = status.position, "streaming training queued");
+        let _ = tx.send(StreamMessage::Queued(status));
+    }
 
-        let mut controller = TrainingController::new(network, config);
+    tokio::spawn(async move {
+        if let Err(e) = job.wait_turn().await {
+            state_clone.notify_job_end(callback_url, job_id, &Err(e.clone()));
+            let _ = tx.send(StreamMessage::Error(ErrorResponse {
+                code: e.code.to_string(),
+                error: e.message,
+                request_id,
+            }));
+            return;
+        }
+        tracing::info!(job_id, example = %example_name, epochs, "streaming training started");
 
-        // Add callback to send progress
-        let tx_clone = tx.clone();
-        controller.add_callback(Box::new(move |epoch, loss, _network| {
-            let _ = tx_clone.send((epoch, loss));
-        }));
+        let _ = tokio::task::spawn_blocking(move || {
+            // Create network
+            let network = new_network(arch, learning_rate, seed).with_activations(hidden, output);
+
+            // Create training config
+            let config = TrainingConfig {
+                epochs,
+                checkpoint_interval: None,
+                checkpoint_path: None,
+                verbose: false,
+                example_name: Some(example_name.clone()),
+                loss_ema: req.loss_ema,
+                ..Default::default()
+            };
+
+            // The job stays registered until this closure returns, after the
+            // model has been stored, so shutdown waits for the flush
+            let mut controller = TrainingController::new(network, config);
+            controller.set_stop_flag(job.stop_flag());
+            record_job_checkpoints(&mut controller, &job, &req, &example_name);
+
+            // Add callback to send progress. Accuracy needs an extra pass over
+            // the data, so it is only reported for small training sets.
+            let tx_clone = tx.clone();
+            let accuracy_data =
+                (inputs.len() <= STREAM_ACCURACY_MAX_SAMPLES).then(|| (inputs.clone(), targets.clone()));
+            let start = std::time::Instant::now();
+            controller.add_progress_callback(Box::new(move |progress, network| {
+                let per_epoch = start.elapsed().as_secs_f64() / progress.epoch as f64;
+                let _ = tx_clone.send(StreamMessage::Progress(ProgressEvent {
+                    epoch: progress.epoch,
+                    loss: progress.loss,
+                    loss_ema: progress.loss_ema,
+                    accuracy: accuracy_data.as_ref().map(|(inputs, targets)| {
+                        network.accuracy(inputs, targets, AccuracyMode::Classification)
+                    }),
+                    eta_secs: per_epoch * epochs.saturating_sub(progress.epoch) as f64,
+                    lr: network.learning_rate,
+                }));
+            }));
+
+            // Train the network
+            let watchdog = state_clone.watch_for_stalls(&mut controller, &job);
+            let result = controller
+                .train(inputs.clone(), targets.clone())
+                .map_err(|e| {
+                    ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "training_failed", e.to_string())
+                })
+                .and_then(|report| match &watchdog {
+                    Some(watchdog) => watchdog.check().map(|()| report),
+                    None => Ok(report),
+                });
+            let report = match result {
+                Ok(report) => report,
+                Err(error) => {
+                    tracing::warn!(job_id, error = %error.message, "training failed");
+                    state_clone.notify_job_end(callback_url, job_id, &Err(error.clone()));
+                    let _ = tx.send(StreamMessage::Error(ErrorResponse {
+                        code: error.code.to_string(),
+                        error: error.message,
+                        request_id,
+                    }));
+                    return;
+                }
+            };
+            let network = controller.into_network();
+            let accuracy = network.accuracy(&inputs, &targets, AccuracyMode::Classification);
 
-        // Train the network
-        if let Ok(()) = controller.train(inputs, targets) {
             // Store model after training
             let model_id = Uuid::new_v4().to_string();
-            let stored_model = StoredModel {
-                network: controller.into_network(),
+            let stored_model = StoredModel::new(
+                network,
+                ModelMetadata {
+                    example: example_name.clone(),
+                    epochs,
+                    learning_rate,
+                    final_loss: report.final_loss,
+                    epochs_run: report.epochs_run,
+                    duration_ms: report.duration_ms,
+                    accuracy,
+                    name: None,
+                    tags: Vec::new(),
+                    notes: None,
+                    history: report.history.iter().map(HistoryPoint::from).collect(),
+                    created_at: models::timestamp_now(),
+                },
+            );
+            tracing::info!(
+                job_id,
+                model_id = %model_id,
+                final_loss = report.final_loss,
+                duration_ms = report.duration_ms,
+                "streaming training finished"
+            );
+            if let Some(path) = state_clone.store_model(model_id.clone(), stored_model) {
+                let _ = tx.send(StreamMessage::Checkpoint(CheckpointEvent {
+                    model_id: model_id.clone(),
+                    path: path.display().to_string(),
+                }));
+            }
+
+            let response = TrainResponse {
+                model_id,
                 example: example_name,
                 epochs,
-                learning_rate,
+                final_loss: report.final_loss,
+                epochs_run: report.epochs_run,
+                duration_ms: report.duration_ms,
+                accuracy,
             };
-            state_clone
-                .models
-                .lock()
-                .unwrap()
-                .insert(model_id, stored_model);
-        }
+            state_clone.notify_job_end(callback_url, job_id, &Ok(response.clone()));
+            let _ = tx.send(StreamMessage::Complete(response));
+        })
+        .await;
     });
 
-    // Create SSE stream from std mpsc receiver
-    let stream = stream::unfold(rx, |rx| async move {
-        // Convert std::sync::mpsc to async stream
-        match rx.try_recv() {
-            Ok((epoch, loss)) => {
-                let data = serde_json::json!({
-                    "epoch": epoch,
-                    "loss": loss
-                });
-                Some((
-                    Ok::<_, Infallible>(Event::default().data(data.to_string())),
-                    rx
-                ))
+    // Create SSE stream from std mpsc receiver, sending a heartbeat event
+    // only when no progress has arrived for a full heartbeat interval
+    let heartbeat = tokio::time::Duration::from_millis(
+        params.heartbeat_ms.unwrap_or(DEFAULT_HEARTBEAT_MS),
+    );
+    let stream = stream::unfold(
+        (rx, tokio::time::Instant::now()),
+        move |(rx, mut last_heartbeat)| async move {
+            loop {
+                // Convert std::sync::mpsc to async stream
+                match rx.try_recv() {
+                    Ok(message) => {
+                        return Some((
+                            Ok::<_, Infallible>(message.into_event()),
+                            (rx, last_heartbeat),
+                        ));
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                        if last_heartbeat.elapsed() >= heartbeat {
+                            last_heartbeat = tokio::time::Instant::now();
+                            return Some((
+                                Ok::<_, Infallible>(StreamMessage::Heartbeat.into_event()),
+                                (rx, last_heartbeat),
+                            ));
+                        }
+                        // Wait a bit and try again
+                        tokio::time::sleep(tokio::time::Duration::from_millis(
+                            STREAM_POLL_INTERVAL_MS,
+                        ))
+                        .await;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => return None,
+                }
             }
-            Err(std::sync::mpsc::TryRecvError::Empty) => {
-                // Wait a bit and try again
-                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-                Some((
-                    Ok::<_, Infallible>(Event::default().comment("heartbeat")),
-                    rx
-                ))
-            }
-            Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
-        }
-    });
+        },
+    );
 
-    Ok(Sse::new(stream))
+    Ok(([(jobs::JOB_ID_HEADER, job_id.to_string())], Sse::new(stream)))
 }
 
-/// Run the web server on the specified address
-pub async fn run_server(addr: &str) -> Result<(), anyhow::Error> {
-    use tower_http::services::ServeDir;
-    use tower_http::cors::CorsLayer;
+/// Run the web server until SIGINT or SIGTERM
+///
+/// Accepts a full `ServerConfig`, or just an address such as
+/// `"127.0.0.1:3000"` to run with default settings.
+pub async fn run_server(config: impl Into<ServerConfig>) -> Result<(), anyhow::Error> {
+    run_server_with_shutdown(config, shutdown_signal()).await
+}
+
+/// Run the web server until `shutdown` completes
+///
+/// On shutdown the server refuses new training requests, stops running
+/// trainings after their current epoch, and waits up to
+/// `shutdown_timeout_secs` for them to store (and persist) their models.
+pub async fn run_server_with_shutdown(
+    config: impl Into<ServerConfig>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), anyhow::Error> {
+    let config = config.into();
+    let addr = config.addr.clone();
+    let static_dir = config.static_dir.clone();
+    let shutdown_timeout = std::time::Duration::from_secs(config.shutdown_timeout_secs);
+    let cors = cors_layer(&config)?;
+    let state = AppState::new(config)?;
+    let jobs = state.jobs.clone();
+    spawn_model_eviction(state.clone());
 
-    let state = AppState::new();
+    // Training routes are expensive, so they are rate limited per client
+    let training_routes = Router::new()
+        .route("/api/train", post(train))
+        .route("/api/train/stream", post(train_stream))
+        .route("/api/train/inline", post(train_inline))
+        .route_layer(middleware::from_fn_with_state(state.clone(), rate_limit::limit_training));
 
     // API routes
     let api_routes = Router::new()
         .route("/health", get(health))
         .route("/api/examples", get(list_examples))
-        .route("/api/train", post(train))
-        .route("/api/train/stream", post(train_stream))
+        .route("/api/examples/:name", get(example_detail))
+        .merge(training_routes)
+        .route("/api/datasets", post(datasets::upload_dataset).get(datasets::list_datasets))
         .route("/api/eval", post(eval))
-        .route("/api/models/:id", get(model_info))
+        .route("/api/eval/ensemble", post(eval_ensemble))
+        .route("/api/models", get(models::list_models))
+        .route("/api/models/compare", get(models::compare))
+        .route("/api/compare", post(models::compare_inputs))
+        .route("/api/models/:id", get(model_info).patch(models::update_model))
+        .route("/api/models/:id/confusion", post(models::confusion))
+        .route("/api/models/:id/evaluate", post(models::evaluate))
+        .route("/api/models/:id/eval_stream", post(models::eval_stream))
+        .route("/api/models/:id/stats", get(models::model_stats))
+        .route("/api/models/:id/weights", get(models::model_weights))
+        .route("/api/models/:id/history", get(models::model_history))
+        .route("/api/jobs/:id", get(jobs::job_status).delete(jobs::cancel_job))
+        .route("/api/jobs/:id/checkpoint", get(jobs::job_checkpoint))
+        .route("/api/openapi.json", get(openapi::openapi_json))
+        .route("/api/docs", get(openapi::docs))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth::require_api_key))
         .with_state(state);
 
-    // Static file serving for future web UI
+    // Static file serving for the web UI
     let app = api_routes
-        .nest_service("/", ServeDir::new("static").fallback(ServeDir::new("static/index.html")))
-        .layer(CorsLayer::permissive());
+        .merge(static_files::routes(&static_dir))
+        .layer(cors)
+        .layer(middleware::from_fn(logging::trace_requests));
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
     println!("Server running on http://{}", addr);
     println!("API endpoints available at /api/*");
-    println!("Static files served from ./static/");
+    if static_files::uses_embedded(&static_dir) {
+        println!("Static files served from the embedded copy ({} not found)", static_dir.display());
+    } else {
+        println!("Static files served from {}", static_dir.display());
+    }
+
+    let (stopping_tx, stopping_rx) = tokio::sync::oneshot::channel::<()>();
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            println!("Shutting down, stopping {} running training(s)...", jobs.running());
+            jobs.shutdown();
+            let _ = stopping_tx.send(());
+            if !jobs.wait_idle(shutdown_timeout).await {
+                tracing::warn!("timed out waiting for trainings to finish");
+            }
+        });
 
-    axum::serve(listener, app).await?;
+    // Bound the whole shutdown, including connections that stay open
+    tokio::select! {
+        result = server => result?,
+        _ = async {
+            if stopping_rx.await.is_ok() {
+                tokio::time::sleep(shutdown_timeout).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        } => {
+            tracing::warn!("graceful shutdown timed out, exiting");
+        }
+    }
 
     Ok(())
 }
+
+/// Periodically evict expired models when a TTL is configured
+///
+/// The model cap is also enforced whenever a model is stored, so this only
+/// matters for models that age out while no training happens.
+fn spawn_model_eviction(state: AppState) {
+    let Some(ttl) = state.config.model_ttl_secs else {
+        return;
+    };
+
+    let period = std::time::Duration::from_secs(ttl.clamp(1, 60));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            state.evict_models();
+        }
+    });
+}
+
+/// Keep the job's latest checkpoint every `checkpoint_interval` epochs, if requested
+fn record_job_checkpoints(
+    controller: &mut TrainingController,
+    job: &JobGuard,
+    req: &TrainRequest,
+    example_name: &str,
+) {
+    let Some(interval) = req.checkpoint_interval else {
+        return;
+    };
+
+    let record = job.checkpoint_recorder();
+    let job_id = job.id();
+    let example = example_name.to_string();
+    let total_epochs = req.epochs;
+    controller.add_callback(Box::new(move |epoch, _loss, network| {
+        if !epoch.is_multiple_of(interval) {
+            return;
+        }
+        let metadata = CheckpointMetadata {
+            version: "1.0".to_string(),
+            example: example.clone(),
+            epoch,
+            total_epochs,
+            learning_rate: network.learning_rate,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            history: Vec::new(),
+        };
+        let mut bytes = Vec::new();
+        match network.write_checkpoint(&mut bytes, metadata, CheckpointFormat::Bin) {
+            Ok(()) => record(JobCheckpoint { epoch, bytes }),
+            Err(e) => tracing::warn!(
+                job_id,
+                epoch,
+                error = %format!("{:#}", e),
+                "failed to checkpoint job"
+            ),
+        }
+    }));
+}
+
+/// Train a fresh network on the request's data as part of `job`
+fn train_network(
+    state: &AppState,
+    job: &JobGuard,
+    data: &TrainingData,
+    req: &TrainRequest,
+    hidden: Activation,
+    output: Activation,
+) -> Result<(Network, TrainingReport), ApiError> {
+    let network = new_network(data.architecture.clone(), req.learning_rate, req.seed)
+        .with_activations(hidden, output);
+
+    let config = TrainingConfig {
+        epochs: req.epochs,
+        checkpoint_interval: None,
+        checkpoint_path: None,
+        verbose: false,
+        example_name: Some(data.name.clone()),
+        loss_ema: req.loss_ema,
+        ..Default::default()
+    };
+
+    let mut controller = TrainingController::new(network, config);
+    controller.set_stop_flag(job.stop_flag());
+    record_job_checkpoints(&mut controller, job, req, &data.name);
+    let watchdog = state.watch_for_stalls(&mut controller, job);
+    let report = controller
+        .train(data.inputs.clone(), data.targets.clone())
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    if let Some(watchdog) = &watchdog {
+        watchdog.check()?;
+    }
+
+    Ok((controller.into_network(), report))
+}
+
+/// Resolve the hidden and output activations a training request names
+///
+/// Both default to sigmoid. Softmax normalizes a whole layer, so it is only
+/// accepted for the output layer.
+fn request_activations(req: &TrainRequest) -> Result<(Activation, Activation), ApiError> {
+    let resolve = |name: &Option<String>| match name {
+        Some(name) => Activation::from_name(name).ok_or_else(|| {
+            let supported: Vec<&str> = ACTIVATIONS.iter().map(|a| a.name).collect();
+            ApiError::bad_request(format!(
+                "Unknown activation: {}. Supported: {}",
+                name,
+                supported.join(", ")
+            ))
+        }),
+        None => Ok(SIGMOID),
+    };
+
+    let hidden = resolve(&req.hidden_activation)?;
+    let output = resolve(&req.output_activation)?;
+    if hidden == SOFTMAX {
+        return Err(ApiError::bad_request(
+            "softmax is only supported as the output activation",
+        ));
+    }
+    Ok((hidden, output))
+}
+
+/// Create a network for training, seeded when a seed is given
+fn new_network(architecture: Vec<usize>, learning_rate: f64, seed: Option<u64>) -> Network {
+    match seed {
+        Some(seed) => Network::new_seeded(architecture, SIGMOID, learning_rate, seed),
+        None => Network::new(architecture, SIGMOID, learning_rate),
+    }
+}
+
+/// Resolve when the process receives SIGINT (Ctrl+C) or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Build the CORS layer from the `cors_*` settings
+///
+/// Permissive when none of them are set. Otherwise only the listed origins
+/// (any, with `"*"`) and methods (any, when none are listed) are allowed.
+/// Requested headers are echoed back in preflight responses, so JSON and
+/// API key headers pass.
+fn cors_layer(config: &ServerConfig) -> Result<tower_http::cors::CorsLayer, anyhow::Error> {
+    use axum::http::{HeaderValue, Method};
+    use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+    if config.cors_origins.is_empty() && config.cors_methods.is_empty() && !config.cors_allow_credentials {
+        return Ok(CorsLayer::permissive());
+    }
+
+    let origins = if config.cors_origins.is_empty() || config.cors_origins.iter().any(|o| o == "*") {
+        // Browsers never send credentials to a wildcard origin
+        anyhow::ensure!(
+            !config.cors_allow_credentials,
+            "cors_allow_credentials requires explicit cors_origins, not \"*\""
+        );
+        AllowOrigin::any()
+    } else {
+        let origins = config
+            .cors_origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .map_err(|_| anyhow::anyhow!("Invalid CORS origin: {}", origin))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let methods = if config.cors_methods.is_empty() {
+        AllowMethods::mirror_request()
+    } else {
+        let methods = config
+            .cors_methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                    .map_err(|_| anyhow::anyhow!("Invalid CORS method: {}", method))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AllowMethods::list(methods)
+    };
+
+    Ok(CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(AllowHeaders::mirror_request())
+        .allow_credentials(config.cors_allow_credentials))
+}
@@ -0,0 +1,109 @@
+// Request logging and trace IDs
+//
+// Every request gets an ID, taken from its `x-request-id` header or
+// generated. The ID is echoed in the response header, included in error
+// bodies, and attached to the request's log lines along with the method,
+// path, status and latency.
+
+use anyhow::Context;
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Instant;
+use tracing::Instrument;
+use tracing_subscriber::filter::LevelFilter;
+
+/// Header carrying the request ID in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-provided request ID that is accepted as is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => anyhow::bail!("Unknown log format: {}. Expected pretty or json", s),
+        }
+    }
+}
+
+/// Install the global logger with the given level (e.g. `info`) and format
+///
+/// Does nothing if a logger is already installed.
+pub fn init(level: &str, format: LogFormat) -> anyhow::Result<()> {
+    let level = LevelFilter::from_str(level)
+        .with_context(|| format!("Invalid log level: {}", level))?;
+    let builder = tracing_subscriber::fmt().with_max_level(level);
+
+    // An existing logger (e.g. from an embedding application) wins
+    let _ = match format {
+        LogFormat::Pretty => builder.try_init(),
+        LogFormat::Json => builder.json().try_init(),
+    };
+    Ok(())
+}
+
+/// ID of the request being handled, if called while handling one
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Middleware assigning a request ID and logging each request
+pub(crate) async fn trace_requests(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(String::from)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let start = Instant::now();
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request).instrument(span.clone()))
+        .await;
+
+    span.in_scope(|| {
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = start.elapsed().as_millis() as u64,
+            "request finished"
+        )
+    });
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    response
+}
@@ -2,6 +2,8 @@
 // REST API server with CLI argument parsing
 
 use clap::Parser;
+use neural_net_server::config::ServerConfig;
+use std::path::PathBuf;
 
 /// Neural Network REST API Server
 ///
@@ -11,20 +13,68 @@ use clap::Parser;
 #[command(name = "neural-net-server")]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// TOML configuration file; NN_SERVER_* environment variables override it
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
     /// Host address to bind to (e.g., 0.0.0.0 for all interfaces, 127.0.0.1 for localhost)
-    #[arg(short = 'H', long, default_value = "127.0.0.1")]
-    host: String,
+    #[arg(short = 'H', long)]
+    host: Option<String>,
 
     /// Port number to listen on
-    #[arg(short, long, default_value = "3000")]
-    port: u16,
+    #[arg(short, long)]
+    port: Option<u16>,
+
+    /// API key required for training and other mutating requests (repeatable)
+    #[arg(long = "api-key")]
+    api_keys: Vec<String>,
+}
+
+/// Build the configuration: file, then environment, then command-line flags
+fn load_config(args: Args) -> anyhow::Result<ServerConfig> {
+    let config = match &args.config {
+        Some(path) => ServerConfig::from_toml(path)?,
+        None => ServerConfig::default(),
+    };
+    let mut config = config.with_env()?;
+
+    if args.host.is_some() || args.port.is_some() {
+        let (default_host, default_port) = config
+            .addr
+            .rsplit_once(':')
+            .unwrap_or(("127.0.0.1", "3000"));
+        let host = args.host.unwrap_or_else(|| default_host.to_string());
+        let port = args
+            .port
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| default_port.to_string());
+        config.addr = format!("{}:{}", host, port);
+    }
+
+    if !args.api_keys.is_empty() {
+        config.api_keys = args.api_keys;
+    }
+
+    Ok(config)
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    let addr = format!("{}:{}", args.host, args.port);
+    let config = match load_config(args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration error: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+    let addr = config.addr.clone();
+
+    if let Err(e) = neural_net_server::logging::init(&config.log_level, config.log_format) {
+        eprintln!("Configuration error: {:#}", e);
+        std::process::exit(1);
+    }
 
     println!("Neural Network Server");
     println!("Version: {}", env!("CARGO_PKG_VERSION"));
@@ -38,9 +88,16 @@ async fn main() {
     println!("  - Train (stream):  POST http://{}/api/train/stream", addr);
     println!("  - Evaluate:        POST http://{}/api/eval", addr);
     println!("  - Model Info:      GET  http://{}/api/models/:id", addr);
+    println!("  - API Docs:        http://{}/api/docs", addr);
+    println!("  - OpenAPI Spec:    http://{}/api/openapi.json", addr);
     println!();
 
-    if let Err(e) = neural_net_server::run_server(&addr).await {
+    if !config.api_keys.is_empty() {
+        println!("API key authentication enabled for mutating requests");
+        println!();
+    }
+
+    if let Err(e) = neural_net_server::run_server(config).await {
         eprintln!("Server error: {}", e);
         std::process::exit(1);
     }
@@ -0,0 +1,1015 @@
+// Trained models and their metadata
+//
+// Models live in memory and, when a model directory is configured, on disk
+// as two files: `<id>.json`, a regular checkpoint the CLI can load, and
+// `<id>.meta.json`, holding training results and user-editable metadata.
+// Models found in the directory at startup are loaded back.
+//
+// With `max_models` or `model_ttl_secs` configured, models unused for longer
+// than the TTL, and the least recently used ones beyond the cap, are evicted
+// from memory and disk. Requests for an evicted model get a 404 with the
+// code `model_evicted`.
+
+use crate::AppState;
+use crate::api::{
+    ClassMetrics, CompareResponse, ConfusionRequest, ConfusionResponse, EvalStreamComplete,
+    EvalStreamOutput, EvalStreamRequest, EvaluateRequest, EvaluateResponse, EvaluatedSample, HistoryPoint, LayerStats, MatrixData, ModelInfoResponse, ModelScore, ModelStatsResponse,
+    ModelWeightsResponse,    PairwiseCompareRequest, PairwiseCompareResponse, SampleComparison, UpdateModelRequest,
+};
+use crate::datasets::Dataset;
+use crate::error::ApiError;
+use crate::prediction_cache::PredictionCache;
+use anyhow::Context;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{
+        Json,
+        sse::{Event, Sse},
+    },
+};
+use neural_network::{
+    checkpoint::CheckpointMetadata,
+    examples,
+    loss::Loss,
+    matrix::Matrix,
+    metrics::{AccuracyMode, class_of},
+    network::{ForwardBuffers, Network},
+    training::TrainingProgress,
+};
+use serde::{Deserialize, Serialize};
+use futures::{Stream, stream};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+
+/// Longest model name accepted
+const MAX_NAME_LEN: usize = 100;
+
+/// Most tags a model can carry
+const MAX_TAGS: usize = 20;
+
+/// Longest single tag accepted
+const MAX_TAG_LEN: usize = 32;
+
+/// Longest notes accepted
+const MAX_NOTES_LEN: usize = 2000;
+
+/// Suffix of the metadata file stored next to each checkpoint
+const METADATA_SUFFIX: &str = ".meta.json";
+
+/// A trained model held by the server
+#[derive(Clone)]
+pub(crate) struct StoredModel {
+    pub network: Network,
+    pub metadata: ModelMetadata,
+    /// Outputs of recent evaluations, created on the first cached eval
+    pub predictions: Option<PredictionCache>,
+}
+
+/// Everything known about a model besides its weights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ModelMetadata {
+    pub example: String,
+    pub epochs: u32,
+    pub learning_rate: f64,
+    pub final_loss: f64,
+    pub epochs_run: u32,
+    pub duration_ms: u64,
+    pub accuracy: f64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Loss at every epoch of the training run
+    #[serde(default)]
+    pub history: Vec<HistoryPoint>,
+    /// When training finished, as an RFC 3339 UTC timestamp; metadata files
+    /// written before it was recorded use the file's modification time
+    #[serde(default)]
+    pub created_at: String,
+}
+
+/// Models in memory, with the bookkeeping needed to evict them
+#[derive(Default)]
+pub(crate) struct ModelStore {
+    models: HashMap<String, StoredModel>,
+    last_accessed: HashMap<String, Instant>,
+    evicted: HashSet<String>,
+}
+
+impl ModelStore {
+    pub(crate) fn new(models: HashMap<String, StoredModel>) -> Self {
+        let now = Instant::now();
+        Self {
+            last_accessed: models.keys().map(|id| (id.clone(), now)).collect(),
+            models,
+            evicted: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, model_id: String, model: StoredModel) {
+        self.last_accessed.insert(model_id.clone(), Instant::now());
+        self.evicted.remove(&model_id);
+        self.models.insert(model_id, model);
+    }
+
+    pub(crate) fn contains_key(&self, model_id: &str) -> bool {
+        self.models.contains_key(model_id)
+    }
+
+    /// Look up a model for use, marking it as recently used
+    pub(crate) fn get(&mut self, model_id: &str) -> Result<&mut StoredModel, ApiError> {
+        match self.models.get_mut(model_id) {
+            Some(model) => {
+                self.last_accessed.insert(model_id.to_string(), Instant::now());
+                Ok(model)
+            }
+            None if self.evicted.contains(model_id) => Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                "model_evicted",
+                "Model was evicted to free space; train it again",
+            )),
+            None => Err(ApiError::not_found("Model not found")),
+        }
+    }
+
+    /// All models, without marking them as used
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &StoredModel)> {
+        self.models.iter()
+    }
+
+    /// Remove models unused for longer than `ttl`, then the least recently
+    /// used ones until at most `max_models` remain
+    ///
+    /// Returns the IDs of the evicted models.
+    pub(crate) fn evict(&mut self, ttl: Option<Duration>, max_models: Option<usize>) -> Vec<String> {
+        let mut by_age: Vec<(String, Instant)> = self
+            .last_accessed
+            .iter()
+            .map(|(id, accessed)| (id.clone(), *accessed))
+            .collect();
+        // Oldest first; ties broken by ID so eviction order is stable
+        by_age.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        let now = Instant::now();
+        let expired = by_age
+            .iter()
+            .take_while(|(_, accessed)| ttl.is_some_and(|ttl| now.duration_since(*accessed) > ttl))
+            .count();
+        let over_cap = max_models.map_or(0, |max| by_age.len().saturating_sub(max));
+
+        let evicted: Vec<String> = by_age
+            .into_iter()
+            .take(expired.max(over_cap))
+            .map(|(id, _)| id)
+            .collect();
+        for id in &evicted {
+            self.models.remove(id);
+            self.last_accessed.remove(id);
+            self.evicted.insert(id.clone());
+        }
+        evicted
+    }
+}
+
+impl StoredModel {
+    pub(crate) fn new(network: Network, metadata: ModelMetadata) -> Self {
+        Self {
+            network,
+            metadata,
+            predictions: None,
+        }
+    }
+
+    /// Run the network on `input`, through a prediction cache of
+    /// `cache_size` entries when one is configured
+    pub(crate) fn predict(
+        &mut self,
+        input: &[f64],
+        cache_size: Option<usize>,
+    ) -> Result<Vec<f64>, ApiError> {
+        let network = &self.network;
+        let predict = || {
+            network
+                .predict(input)
+                .map_err(|e| ApiError::bad_request(e.to_string()))
+        };
+        match cache_size {
+            Some(capacity) => self
+                .predictions
+                .get_or_insert_with(|| PredictionCache::new(capacity))
+                .get_or_insert_with(input, predict),
+            None => predict(),
+        }
+    }
+
+    /// Apply an update request; a new learning rate also goes to the network
+    fn update(&mut self, req: UpdateModelRequest) -> Result<(), ApiError> {
+        let learning_rate = req.learning_rate;
+        self.metadata.update(req)?;
+        if let Some(learning_rate) = learning_rate {
+            self.network.learning_rate = learning_rate;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn info(&self, model_id: String) -> ModelInfoResponse {
+        let network = &self.network;
+        let total_parameters = network.weights.iter().map(|w| w.rows * w.cols).sum::<usize>()
+            + network.biases.iter().map(|b| b.rows).sum::<usize>();
+        let metadata = self.metadata.clone();
+
+        ModelInfoResponse {
+            model_id,
+            example: metadata.example,
+            architecture: network.layers.clone(),
+            epochs: metadata.epochs,
+            learning_rate: metadata.learning_rate,
+            total_parameters,
+            final_loss: metadata.final_loss,
+            epochs_run: metadata.epochs_run,
+            duration_ms: metadata.duration_ms,
+            accuracy: metadata.accuracy,
+            name: metadata.name,
+            tags: metadata.tags,
+            notes: metadata.notes,
+            created_at: metadata.created_at,
+        }
+    }
+
+    /// Write the checkpoint and metadata files for this model
+    pub(crate) fn save(&self, dir: &std::path::Path, model_id: &str) -> anyhow::Result<()> {
+        let checkpoint = CheckpointMetadata {
+            version: "1.0".to_string(),
+            example: self.metadata.example.clone(),
+            epoch: self.metadata.epochs_run,
+            total_epochs: self.metadata.epochs,
+            learning_rate: self.metadata.learning_rate,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            history: Vec::new(),
+        };
+        self.network
+            .save_checkpoint(&dir.join(format!("{}.json", model_id)), checkpoint)?;
+        self.metadata.save(dir, model_id)
+    }
+}
+
+/// The current time as a model timestamp
+pub(crate) fn timestamp_now() -> String {
+    format_timestamp(chrono::Utc::now())
+}
+
+/// Fixed-width RFC 3339 UTC timestamp, so timestamps sort chronologically
+/// as strings
+fn format_timestamp(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+impl ModelMetadata {
+    /// Write just the metadata file for a model
+    pub(crate) fn save(&self, dir: &std::path::Path, model_id: &str) -> anyhow::Result<()> {
+        let path = dir.join(format!("{}{}", model_id, METADATA_SUFFIX));
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write model metadata {}", path.display()))
+    }
+
+    /// Apply a metadata update, leaving fields the request omits unchanged
+    fn update(&mut self, req: UpdateModelRequest) -> Result<(), ApiError> {
+        if let Some(name) = &req.name {
+            let name = name.trim();
+            if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+                return Err(ApiError::bad_request(format!(
+                    "Model names must be 1 to {} characters",
+                    MAX_NAME_LEN
+                )));
+            }
+        }
+        if let Some(tags) = &req.tags {
+            if tags.len() > MAX_TAGS {
+                return Err(ApiError::bad_request(format!(
+                    "A model can have at most {} tags",
+                    MAX_TAGS
+                )));
+            }
+            if tags
+                .iter()
+                .any(|tag| tag.trim().is_empty() || tag.chars().count() > MAX_TAG_LEN)
+            {
+                return Err(ApiError::bad_request(format!(
+                    "Tags must be 1 to {} characters",
+                    MAX_TAG_LEN
+                )));
+            }
+        }
+        if let Some(notes) = &req.notes
+            && notes.chars().count() > MAX_NOTES_LEN
+        {
+            return Err(ApiError::bad_request(format!(
+                "Notes must be at most {} characters",
+                MAX_NOTES_LEN
+            )));
+        }
+        if let Some(learning_rate) = req.learning_rate
+            && !(learning_rate > 0.0 && learning_rate.is_finite())
+        {
+            return Err(ApiError::bad_request(format!(
+                "learning_rate must be a positive number, got {}",
+                learning_rate
+            )));
+        }
+
+        if let Some(name) = req.name {
+            self.name = Some(name.trim().to_string());
+        }
+        if let Some(tags) = req.tags {
+            self.tags = tags.iter().map(|tag| tag.trim().to_string()).collect();
+        }
+        if let Some(notes) = req.notes {
+            self.notes = Some(notes);
+        }
+        if let Some(learning_rate) = req.learning_rate {
+            self.learning_rate = learning_rate;
+        }
+
+        Ok(())
+    }
+}
+
+/// Delete the files of a persisted model
+pub(crate) fn delete_model_files(dir: &std::path::Path, model_id: &str) {
+    for path in [
+        dir.join(format!("{}.json", model_id)),
+        dir.join(format!("{}{}", model_id, METADATA_SUFFIX)),
+    ] {
+        if let Err(e) = std::fs::remove_file(&path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(path = %path.display(), error = %e, "failed to delete model file");
+        }
+    }
+}
+
+/// Load every persisted model in `dir`
+///
+/// A missing directory holds no models. Models whose files can't be read
+/// are skipped with a warning rather than stopping the server.
+pub(crate) fn load_models(dir: &std::path::Path) -> anyhow::Result<HashMap<String, StoredModel>> {
+    let mut models = HashMap::new();
+    if !dir.exists() {
+        return Ok(models);
+    }
+
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read model directory {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let Some(model_id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(METADATA_SUFFIX))
+        else {
+            continue;
+        };
+
+        match load_model(dir, model_id) {
+            Ok(model) => {
+                models.insert(model_id.to_string(), model);
+            }
+            Err(e) => tracing::warn!(model_id, error = %format!("{:#}", e), "skipping stored model"),
+        }
+    }
+
+    Ok(models)
+}
+
+fn load_model(dir: &std::path::Path, model_id: &str) -> anyhow::Result<StoredModel> {
+    let metadata_path = dir.join(format!("{}{}", model_id, METADATA_SUFFIX));
+    let json = std::fs::read_to_string(&metadata_path)
+        .with_context(|| format!("Failed to read {}", metadata_path.display()))?;
+    let mut metadata: ModelMetadata = serde_json::from_str(&json)
+        .with_context(|| format!("Failed to parse {}", metadata_path.display()))?;
+    if metadata.created_at.is_empty() {
+        let modified = std::fs::metadata(&metadata_path)?.modified()?;
+        metadata.created_at = format_timestamp(modified.into());
+    }
+    let (network, _) = Network::load_checkpoint_auto(&dir.join(format!("{}.json", model_id)))?;
+
+    Ok(StoredModel::new(network, metadata))
+}
+
+/// Filters for listing models
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(crate) struct ListParams {
+    /// Only list models carrying this tag
+    tag: Option<String>,
+}
+
+/// List stored models
+#[utoipa::path(
+    get,
+    path = "/api/models",
+    params(ListParams),
+    responses((status = 200, description = "Stored models, oldest first", body = [ModelInfoResponse]))
+)]
+pub(crate) async fn list_models(
+    State(state): State<AppState>,
+    Query(params): Query<ListParams>,
+) -> Json<Vec<ModelInfoResponse>> {
+    let models = state.models.lock().unwrap();
+    let mut infos: Vec<ModelInfoResponse> = models
+        .iter()
+        .filter(|(_, model)| match &params.tag {
+            Some(tag) => model.metadata.tags.contains(tag),
+            None => true,
+        })
+        .map(|(id, model)| model.info(id.clone()))
+        .collect();
+    // Oldest first, with ties between models created in the same
+    // microsecond broken by ID
+    infos.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.model_id.cmp(&b.model_id)));
+
+    Json(infos)
+}
+
+/// Update a model's name, tags, notes or learning rate
+#[utoipa::path(
+    patch,
+    path = "/api/models/{id}",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    request_body = UpdateModelRequest,
+    responses(
+        (status = 200, description = "Updated model details", body = ModelInfoResponse),
+        (status = 400, description = "Name, tags or notes too long or empty, or a learning rate that isn't positive", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn update_model(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(req): Json<UpdateModelRequest>,
+) -> Result<Json<ModelInfoResponse>, ApiError> {
+    // The checkpoint file holds the learning rate, the rest is metadata
+    let network_changed = req.learning_rate.is_some();
+    let model = {
+        let mut models = state.models.lock().unwrap();
+        let model = models.get(&model_id)?;
+        model.update(req)?;
+        model.clone()
+    };
+
+    if let Some(dir) = &state.config.model_dir {
+        let saved = if network_changed {
+            model.save(dir, &model_id)
+        } else {
+            model.metadata.save(dir, &model_id)
+        };
+        saved.map_err(|e| ApiError::internal(format!("{:#}", e)))?;
+    }
+
+    Ok(Json(model.info(model_id)))
+}
+
+/// Confusion matrix and per-class metrics for a classifier model
+#[utoipa::path(
+    post,
+    path = "/api/models/{id}/confusion",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    request_body = ConfusionRequest,
+    responses(
+        (status = 200, description = "Confusion matrix and per-class metrics", body = ConfusionResponse),
+        (status = 400, description = "Single-output model or mismatched dimensions", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn confusion(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(req): Json<ConfusionRequest>,
+) -> Result<Json<ConfusionResponse>, ApiError> {
+    let network = state
+        .models
+        .lock()
+        .unwrap()
+        .get(&model_id)?
+        .network
+        .clone();
+
+    let input_width = network.layers[0];
+    let output_width = network.layers[network.layers.len() - 1];
+    if output_width < 2 {
+        return Err(ApiError::bad_request(
+            "Confusion matrices need a multi-output (one-hot) model",
+        ));
+    }
+    if req.inputs.is_empty() || req.inputs.len() != req.targets.len() {
+        return Err(ApiError::bad_request(
+            "Provide the same non-zero number of inputs and targets",
+        ));
+    }
+    if let Some(row) = req.inputs.iter().position(|input| input.len() != input_width) {
+        return Err(ApiError::bad_request(format!(
+            "Input {} has {} values, expected {}",
+            row,
+            req.inputs[row].len(),
+            input_width
+        )));
+    }
+    if let Some(row) = req.targets.iter().position(|target| target.len() != output_width) {
+        return Err(ApiError::bad_request(format!(
+            "Target {} has {} values, expected {}",
+            row,
+            req.targets[row].len(),
+            output_width
+        )));
+    }
+
+    let report = network.classification_report(&req.inputs, &req.targets);
+    Ok(Json(ConfusionResponse {
+        confusion_matrix: report.confusion_matrix,
+        classes: report
+            .classes
+            .into_iter()
+            .map(|c| ClassMetrics {
+                class: c.class,
+                precision: c.precision,
+                recall: c.recall,
+                f1: c.f1,
+                support: c.support,
+            })
+            .collect(),
+        accuracy: report.accuracy,
+    }))
+}
+
+/// Score a model on a built-in example or uploaded dataset
+#[utoipa::path(
+    post,
+    path = "/api/models/{id}/evaluate",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    request_body = EvaluateRequest,
+    responses(
+        (status = 200, description = "Loss, accuracy and per-sample results", body = EvaluateResponse),
+        (status = 400, description = "Unknown example, or no example for a model trained on inline data", body = ErrorResponse),
+        (status = 404, description = "Model or dataset not found", body = ErrorResponse),
+        (status = 422, description = "Model doesn't fit the example's data", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn evaluate(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(req): Json<EvaluateRequest>,
+) -> Result<Json<EvaluateResponse>, ApiError> {
+    let (network, trained_on) = {
+        let mut models = state.models.lock().unwrap();
+        let model = models.get(&model_id)?;
+        (model.network.clone(), model.metadata.example.clone())
+    };
+    let example = req.example.unwrap_or(trained_on);
+    let Dataset { inputs, targets } = evaluation_data(&state, &example)?;
+
+    let input_width = network.layers[0];
+    let output_width = network.layers[network.layers.len() - 1];
+    if inputs[0].len() != input_width || targets[0].len() != output_width {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "dimension_mismatch",
+            format!(
+                "Model has {} inputs and {} outputs, but {} has {} and {}",
+                input_width,
+                output_width,
+                example,
+                inputs[0].len(),
+                targets[0].len()
+            ),
+        ));
+    }
+
+    let samples: Vec<EvaluatedSample> = inputs
+        .iter()
+        .zip(&targets)
+        .map(|(input, target)| {
+            let output = network.predict(input).map_err(|e| ApiError::bad_request(e.to_string()))?;
+            Ok(EvaluatedSample {
+                input: input.clone(),
+                target: target.clone(),
+                correct: class_of(&output) == class_of(target),
+                output,
+            })
+        })
+        .collect::<Result<_, ApiError>>()?;
+    let loss = samples
+        .iter()
+        .map(|sample| Loss::MeanSquaredError.compute(&sample.output, &sample.target))
+        .sum::<f64>()
+        / samples.len() as f64;
+    let accuracy = samples.iter().filter(|sample| sample.correct).count() as f64 / samples.len() as f64;
+    let confusion_matrix = (output_width > 1)
+        .then(|| network.classification_report(&inputs, &targets).confusion_matrix);
+
+    Ok(Json(EvaluateResponse {
+        model_id,
+        example,
+        loss,
+        accuracy,
+        samples,
+        confusion_matrix,
+    }))
+}
+
+/// Outputs computed ahead of the client before the stream applies backpressure
+const EVAL_STREAM_BUFFER: usize = 64;
+
+/// Stream a model's outputs for many inputs as Server-Sent Events
+///
+/// Every input is checked before streaming starts. Each SSE event names
+/// its type in the `event:` field:
+/// - `output` for each input in request order, with data `EvalStreamOutput`
+/// - `complete` after the last output, with data `EvalStreamComplete`
+#[utoipa::path(
+    post,
+    path = "/api/models/{id}/eval_stream",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    request_body = EvalStreamRequest,
+    responses(
+        (status = 200, description = "Server-Sent Events stream of outputs", content_type = "text/event-stream", body = String),
+        (status = 400, description = "No inputs, wrong number of input values or a non-finite input value", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn eval_stream(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(req): Json<EvalStreamRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let network = state.models.lock().unwrap().get(&model_id)?.network.clone();
+
+    let input_width = network.layers[0];
+    if req.inputs.is_empty() {
+        return Err(ApiError::bad_request("Provide at least one input"));
+    }
+    if let Some(row) = req.inputs.iter().position(|input| input.len() != input_width) {
+        return Err(ApiError::bad_request(format!(
+            "Input {} has {} values, expected {}",
+            row,
+            req.inputs[row].len(),
+            input_width
+        )));
+    }
+    if let Some(row) = req.inputs.iter().position(|input| input.iter().any(|x| !x.is_finite())) {
+        return Err(ApiError::bad_request(format!("Input {} has non-finite values", row)));
+    }
+
+    // Outputs are computed on a blocking thread and handed over through a
+    // bounded channel; a client that disconnects closes it and stops the work
+    let (tx, rx) = tokio::sync::mpsc::channel::<Event>(EVAL_STREAM_BUFFER);
+    tokio::task::spawn_blocking(move || {
+        let mut buffers = ForwardBuffers::new(&network);
+        for (index, input) in req.inputs.iter().enumerate() {
+            let output = network.feed_forward_buffered(input, &mut buffers).data.clone();
+            let data = serde_json::to_string(&EvalStreamOutput { index, output })
+                .expect("stream events serialize to JSON");
+            if tx.blocking_send(Event::default().event("output").data(data)).is_err() {
+                return;
+            }
+        }
+        let data = serde_json::to_string(&EvalStreamComplete {
+            count: req.inputs.len(),
+        })
+        .expect("stream events serialize to JSON");
+        let _ = tx.blocking_send(Event::default().event("complete").data(data));
+    });
+
+    let stream = stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (Ok(event), rx))
+    });
+    Ok(Sse::new(stream))
+}
+
+/// Inputs and targets of a built-in example or an uploaded `dataset:<id>`
+fn evaluation_data(state: &AppState, name: &str) -> Result<Dataset, ApiError> {
+    if let Some(dataset_id) = name.strip_prefix("dataset:") {
+        return state
+            .datasets
+            .lock()
+            .unwrap()
+            .get(dataset_id)
+            .cloned()
+            .ok_or_else(|| ApiError::not_found(format!("Dataset {} not found", dataset_id)));
+    }
+    if name.starts_with("inline:") {
+        return Err(ApiError::bad_request(
+            "Model was trained on inline data; name an example to evaluate it on",
+        ));
+    }
+    let example = examples::get_example(name)
+        .ok_or_else(|| ApiError::bad_request(format!("Unknown example: {}", name)))?;
+    Ok(Dataset {
+        inputs: example.inputs,
+        targets: example.targets,
+    })
+}
+
+/// Per-layer weight statistics of a model
+#[utoipa::path(
+    get,
+    path = "/api/models/{id}/stats",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    responses(
+        (status = 200, description = "Per-layer weight statistics and total norm", body = ModelStatsResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn model_stats(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<ModelStatsResponse>, ApiError> {
+    let (layers, prediction_cache): (Vec<LayerStats>, _) = {
+        let mut models = state.models.lock().unwrap();
+        let model = models.get(&model_id)?;
+        let layers = model
+            .network
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(layer, weights)| LayerStats {
+                layer,
+                rows: weights.rows,
+                cols: weights.cols,
+                mean: weights.mean(),
+                std: weights.std_dev(),
+                min: weights.min(),
+                max: weights.max(),
+                norm: weights.norm(),
+            })
+            .collect();
+        (layers, model.predictions.as_ref().map(PredictionCache::stats))
+    };
+    let total_norm = layers.iter().map(|l| l.norm * l.norm).sum::<f64>().sqrt();
+
+    Ok(Json(ModelStatsResponse {
+        model_id,
+        layers,
+        total_norm,
+        prediction_cache,
+    }))
+}
+
+impl From<&Matrix> for MatrixData {
+    fn from(matrix: &Matrix) -> Self {
+        Self {
+            rows: matrix.rows,
+            cols: matrix.cols,
+            data: matrix.data.clone(),
+        }
+    }
+}
+
+/// Raw weights and biases of a model
+///
+/// Finer-grained than a checkpoint export, for debugging and client-side
+/// visualization.
+#[utoipa::path(
+    get,
+    path = "/api/models/{id}/weights",
+    params(("id" = String, Path, description = "Model ID returned by training")),
+    responses(
+        (status = 200, description = "One weight matrix and one bias vector per layer", body = ModelWeightsResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn model_weights(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<ModelWeightsResponse>, ApiError> {
+    let mut models = state.models.lock().unwrap();
+    let network = &models.get(&model_id)?.network;
+
+    Ok(Json(ModelWeightsResponse {
+        weights: network.weights.iter().map(MatrixData::from).collect(),
+        biases: network.biases.iter().map(MatrixData::from).collect(),
+        model_id,
+    }))
+}
+
+impl From<&TrainingProgress> for HistoryPoint {
+    fn from(progress: &TrainingProgress) -> Self {
+        Self {
+            epoch: progress.epoch,
+            loss: progress.loss,
+            val_loss: progress.val_loss,
+        }
+    }
+}
+
+/// Downsampling of a model's training history
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(crate) struct HistoryParams {
+    /// Keep every `stride`-th epoch, plus the last one (default 1)
+    stride: Option<usize>,
+}
+
+/// Loss history of a model's training run
+///
+/// Downsampled server-side so long runs don't produce huge payloads: with
+/// stride `s` the response holds epochs `s, 2s, 3s, ...` and always ends
+/// with the last epoch run.
+#[utoipa::path(
+    get,
+    path = "/api/models/{id}/history",
+    params(("id" = String, Path, description = "Model ID returned by training"), HistoryParams),
+    responses(
+        (status = 200, description = "Loss per kept epoch, oldest first", body = Vec<HistoryPoint>),
+        (status = 400, description = "Stride is zero", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn model_history(
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Query(params): Query<HistoryParams>,
+) -> Result<Json<Vec<HistoryPoint>>, ApiError> {
+    let stride = params.stride.unwrap_or(1);
+    if stride == 0 {
+        return Err(ApiError::bad_request("stride must be at least 1"));
+    }
+    let mut models = state.models.lock().unwrap();
+    let history = &models.get(&model_id)?.metadata.history;
+
+    Ok(Json(downsample(history, stride)))
+}
+
+/// Every `stride`-th point of `history`, plus the last one
+fn downsample(history: &[HistoryPoint], stride: usize) -> Vec<HistoryPoint> {
+    let last = history.len().saturating_sub(1);
+    history
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| (i + 1) % stride == 0 || *i == last)
+        .map(|(_, point)| point.clone())
+        .collect()
+}
+
+/// Models and example to compare
+#[derive(Deserialize, utoipa::IntoParams)]
+pub(crate) struct CompareParams {
+    /// First model ID
+    a: String,
+    /// Second model ID
+    b: String,
+    /// Built-in example whose samples both models are run on
+    example: String,
+}
+
+/// Compare two models on a built-in example
+#[utoipa::path(
+    get,
+    path = "/api/models/compare",
+    params(CompareParams),
+    responses(
+        (status = 200, description = "Per-sample outputs, scores and weight differences", body = CompareResponse),
+        (status = 400, description = "Unknown example, or a model doesn't fit the example's data", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn compare(
+    State(state): State<AppState>,
+    Query(params): Query<CompareParams>,
+) -> Result<Json<CompareResponse>, ApiError> {
+    let example = examples::get_example(&params.example)
+        .ok_or_else(|| ApiError::bad_request(format!("Unknown example: {}", params.example)))?;
+    let (a, b) = {
+        let mut models = state.models.lock().unwrap();
+        let a = models.get(&params.a)?.network.clone();
+        let b = models.get(&params.b)?.network.clone();
+        (a, b)
+    };
+
+    let input_width = example.inputs[0].len();
+    let target_width = example.targets[0].len();
+    for (id, network) in [(&params.a, &a), (&params.b, &b)] {
+        let layers = &network.layers;
+        if layers[0] != input_width || layers[layers.len() - 1] != target_width {
+            return Err(ApiError::bad_request(format!(
+                "Model {} has {} inputs and {} outputs, but {} has {} and {}",
+                id,
+                layers[0],
+                layers[layers.len() - 1],
+                example.name,
+                input_width,
+                target_width
+            )));
+        }
+    }
+
+    let predict = |network: &Network, input: &[f64]| {
+        network.predict(input).map_err(|e| ApiError::bad_request(e.to_string()))
+    };
+    let samples: Vec<SampleComparison> = example
+        .inputs
+        .iter()
+        .zip(&example.targets)
+        .map(|(input, target)| {
+            Ok(SampleComparison {
+                input: input.clone(),
+                target: target.clone(),
+                output_a: predict(&a, input)?,
+                output_b: predict(&b, input)?,
+            })
+        })
+        .collect::<Result<_, ApiError>>()?;
+
+    let score = |model_id: &str, network: &Network, output: fn(&SampleComparison) -> &Vec<f64>| {
+        let loss = samples
+            .iter()
+            .map(|sample| Loss::MeanSquaredError.compute(output(sample), &sample.target))
+            .sum::<f64>()
+            / samples.len() as f64;
+        ModelScore {
+            model_id: model_id.to_string(),
+            loss,
+            accuracy: network.accuracy(&example.inputs, &example.targets, AccuracyMode::Classification),
+        }
+    };
+
+    let weight_deltas = (a.layers == b.layers).then(|| {
+        a.weights
+            .iter()
+            .zip(&b.weights)
+            .map(|(wa, wb)| {
+                wa.data
+                    .iter()
+                    .zip(&wb.data)
+                    .map(|(x, y)| (x - y).abs())
+                    .fold(0.0, f64::max)
+            })
+            .collect()
+    });
+
+    Ok(Json(CompareResponse {
+        example: example.name.to_string(),
+        a: score(&params.a, &a, |sample| &sample.output_a),
+        b: score(&params.b, &b, |sample| &sample.output_b),
+        samples,
+        weight_deltas,
+    }))
+}
+
+/// Run two models on the same inputs and diff their outputs
+#[utoipa::path(
+    post,
+    path = "/api/compare",
+    request_body = PairwiseCompareRequest,
+    responses(
+        (status = 200, description = "Both models' outputs and their difference for each input", body = PairwiseCompareResponse),
+        (status = 400, description = "No inputs, models with different input or output sizes, wrong number of inputs or non-finite input values", body = ErrorResponse),
+        (status = 404, description = "Model not found", body = ErrorResponse)
+    )
+)]
+pub(crate) async fn compare_inputs(
+    State(state): State<AppState>,
+    Json(req): Json<PairwiseCompareRequest>,
+) -> Result<Json<PairwiseCompareResponse>, ApiError> {
+    if req.inputs.is_empty() {
+        return Err(ApiError::bad_request("A comparison needs at least one input"));
+    }
+    let (a, b) = {
+        let mut models = state.models.lock().unwrap();
+        let a = models.get(&req.model_a)?.network.clone();
+        let b = models.get(&req.model_b)?.network.clone();
+        (a, b)
+    };
+
+    let shape = |network: &Network| (network.layers[0], network.layers[network.layers.len() - 1]);
+    if shape(&a) != shape(&b) {
+        return Err(ApiError::bad_request(format!(
+            "Models {} and {} must share input and output sizes",
+            req.model_a, req.model_b
+        )));
+    }
+
+    // Wrong input sizes and non-finite values are rejected by `predict`
+    let predict_all = |network: &Network| {
+        req.inputs
+            .iter()
+            .map(|input| network.predict(input))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ApiError::bad_request(e.to_string()))
+    };
+    let outputs_a = predict_all(&a)?;
+    let outputs_b = predict_all(&b)?;
+    let diff = outputs_a
+        .iter()
+        .zip(&outputs_b)
+        .map(|(output_a, output_b)| output_a.iter().zip(output_b).map(|(x, y)| x - y).collect())
+        .collect();
+
+    Ok(Json(PairwiseCompareResponse {
+        outputs_a,
+        outputs_b,
+        diff,
+    }))
+}
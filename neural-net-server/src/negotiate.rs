@@ -0,0 +1,172 @@
+// MessagePack content negotiation
+//
+// Eval endpoints accept MessagePack request bodies (`Content-Type:
+// application/msgpack`) and answer in MessagePack when the client sends
+// `Accept: application/msgpack`, which saves parsing and bytes for clients
+// making many small calls. Both encodings use the same serde types, and JSON
+// stays the default. Error bodies are always JSON.
+
+use crate::error::ApiError;
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Media type of MessagePack bodies
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Older media type some MessagePack clients send
+const MSGPACK_LEGACY_CONTENT_TYPE: &str = "application/x-msgpack";
+
+/// Encoding of a request or response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    Json,
+    MsgPack,
+}
+
+impl BodyFormat {
+    /// Encoding of a request body, from its `Content-Type`
+    fn of_request(headers: &HeaderMap) -> Result<Self, ApiError> {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        if is_msgpack(media_type) {
+            Ok(BodyFormat::MsgPack)
+        } else if media_type == "application/json" || media_type.ends_with("+json") {
+            Ok(BodyFormat::Json)
+        } else {
+            Err(ApiError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_media_type",
+                format!(
+                    "Unsupported content type {:?}; expected application/json or {}",
+                    content_type, MSGPACK_CONTENT_TYPE
+                ),
+            ))
+        }
+    }
+
+    /// Encoding the client wants back: MessagePack if its `Accept` header
+    /// lists it, JSON otherwise
+    fn accepted(headers: &HeaderMap) -> Self {
+        let accepts_msgpack = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|media_range| is_msgpack(media_range.split(';').next().unwrap_or_default().trim()));
+
+        if accepts_msgpack {
+            BodyFormat::MsgPack
+        } else {
+            BodyFormat::Json
+        }
+    }
+}
+
+fn is_msgpack(media_type: &str) -> bool {
+    media_type == MSGPACK_CONTENT_TYPE || media_type == MSGPACK_LEGACY_CONTENT_TYPE
+}
+
+/// Request body decoded from JSON or MessagePack, with the encoding the
+/// client accepts for the response
+#[derive(Debug)]
+pub struct Negotiated<T> {
+    pub body: T,
+    pub response_format: BodyFormat,
+}
+
+#[async_trait]
+impl<S, T> FromRequest<S> for Negotiated<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let response_format = BodyFormat::accepted(request.headers());
+
+        let body = match BodyFormat::of_request(request.headers()).map_err(IntoResponse::into_response)? {
+            // Same rejections as every other JSON endpoint
+            BodyFormat::Json => Json::<T>::from_request(request, state)
+                .await
+                .map_err(IntoResponse::into_response)?
+                .0,
+            BodyFormat::MsgPack => {
+                let bytes = Bytes::from_request(request, state)
+                    .await
+                    .map_err(IntoResponse::into_response)?;
+                rmp_serde::from_slice(&bytes).map_err(|e| {
+                    ApiError::bad_request(format!("Invalid MessagePack body: {}", e)).into_response()
+                })?
+            }
+        };
+
+        Ok(Negotiated { body, response_format })
+    }
+}
+
+/// Response body encoded as JSON or MessagePack
+#[derive(Debug)]
+pub struct Encoded<T>(pub BodyFormat, pub T);
+
+impl<T: Serialize> IntoResponse for Encoded<T> {
+    fn into_response(self) -> Response {
+        match self.0 {
+            BodyFormat::Json => Json(self.1).into_response(),
+            BodyFormat::MsgPack => match rmp_serde::to_vec_named(&self.1) {
+                Ok(bytes) => (
+                    [(header::CONTENT_TYPE, HeaderValue::from_static(MSGPACK_CONTENT_TYPE))],
+                    bytes,
+                )
+                    .into_response(),
+                Err(e) => ApiError::internal(format!("Failed to encode response: {}", e)).into_response(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_request_format_follows_content_type() {
+        let of = |content_type| BodyFormat::of_request(&headers(&[(header::CONTENT_TYPE, content_type)]));
+
+        assert_eq!(of("application/json").unwrap(), BodyFormat::Json);
+        assert_eq!(of("application/json; charset=utf-8").unwrap(), BodyFormat::Json);
+        assert_eq!(of("application/msgpack").unwrap(), BodyFormat::MsgPack);
+        assert_eq!(of("application/x-msgpack").unwrap(), BodyFormat::MsgPack);
+        assert_eq!(of("text/csv").unwrap_err().status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(BodyFormat::of_request(&HeaderMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_response_format_follows_accept() {
+        let accepted = |accept| BodyFormat::accepted(&headers(&[(header::ACCEPT, accept)]));
+
+        assert_eq!(accepted("application/msgpack"), BodyFormat::MsgPack);
+        assert_eq!(accepted("application/msgpack, application/json"), BodyFormat::MsgPack);
+        assert_eq!(accepted("application/json"), BodyFormat::Json);
+        assert_eq!(accepted("text/html"), BodyFormat::Json);
+        assert_eq!(BodyFormat::accepted(&HeaderMap::new()), BodyFormat::Json);
+    }
+}
@@ -0,0 +1,116 @@
+// OpenAPI specification and interactive documentation
+
+use crate::AppState;
+use crate::api::{
+    CheckpointEvent, ClassMetrics, CompareResponse, ConfusionRequest, ConfusionResponse,
+    DatasetInfo, EnsembleMethod, EnsembleRequest, EnsembleResponse, ErrorResponse, EvalRequest,
+    EvalResponse, EvalStreamComplete, EvalStreamOutput, EvalStreamRequest, EvaluateRequest,
+    EvaluateResponse, EvaluatedSample, ExampleDetail, ExampleInfo, HealthResponse, HistoryPoint,
+    JobNotification, JobOutcome, JobState, JobStatusResponse, LayerStats, MatrixData, ModelInfoResponse,
+    ModelOutput, ModelScore, ModelStatsResponse, ModelWeightsResponse, PairwiseCompareRequest, PairwiseCompareResponse,
+    PredictionCacheStats, ProgressEvent, SampleComparison, TrainRequest, TrainResponse,
+    UpdateModelRequest,
+};
+use crate::error::ApiError;
+use crate::static_files;
+use axum::{
+    extract::State,
+    response::{Html, Json},
+};
+use utoipa::OpenApi;
+
+/// OpenAPI document describing every REST endpoint
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Neural Network Server",
+        description = "Train and evaluate small neural networks over HTTP"
+    ),
+    paths(
+        crate::health,
+        crate::list_examples,
+        crate::example_detail,
+        crate::train,
+        crate::train_stream,
+        crate::train_inline,
+        crate::eval,
+        crate::eval_ensemble,
+        crate::model_info,
+        crate::models::list_models,
+        crate::models::update_model,
+        crate::models::confusion,
+        crate::models::evaluate,
+        crate::models::eval_stream,
+        crate::models::model_stats,
+        crate::models::model_weights,
+        crate::models::model_history,
+        crate::models::compare,
+        crate::models::compare_inputs,
+        crate::jobs::job_status,
+        crate::jobs::cancel_job,
+        crate::jobs::job_checkpoint,
+        crate::datasets::upload_dataset,
+        crate::datasets::list_datasets,
+    ),
+    components(schemas(
+        HealthResponse,
+        ExampleInfo,
+        ExampleDetail,
+        TrainRequest,
+        TrainResponse,
+        ProgressEvent,
+        CheckpointEvent,
+        JobState,
+        JobStatusResponse,
+        JobOutcome,
+        JobNotification,
+        EvalRequest,
+        EvalResponse,
+        EnsembleMethod,
+        EnsembleRequest,
+        EnsembleResponse,
+        ModelOutput,
+        ModelInfoResponse,
+        UpdateModelRequest,
+        ConfusionRequest,
+        ConfusionResponse,
+        EvaluateRequest,
+        EvaluateResponse,
+        EvaluatedSample,
+        EvalStreamRequest,
+        EvalStreamOutput,
+        EvalStreamComplete,
+        ClassMetrics,
+        ModelStatsResponse,
+        HistoryPoint,
+        LayerStats,
+        PredictionCacheStats,
+        MatrixData,
+        ModelWeightsResponse,
+        CompareResponse,
+        ModelScore,
+        SampleComparison,
+        PairwiseCompareRequest,
+        PairwiseCompareResponse,
+        DatasetInfo,
+        ErrorResponse,
+    ))
+)]
+pub struct ApiDoc;
+
+/// Serve the OpenAPI specification
+pub(crate) async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Serve the Swagger UI page from the configured static directory
+pub(crate) async fn docs(State(state): State<AppState>) -> Result<Html<String>, ApiError> {
+    let static_dir = &state.config.static_dir;
+    static_files::read_to_string(static_dir, "docs.html")
+        .await
+        .map(Html)
+        .ok_or_else(|| {
+            let path = static_dir.join("docs.html");
+            ApiError::not_found(format!("API docs page not found at {}", path.display()))
+        })
+}
@@ -0,0 +1,151 @@
+// Per-model cache of evaluation results
+//
+// Dashboards tend to evaluate the same inputs over and over, so with
+// `prediction_cache_size` configured each stored model remembers the outputs
+// of its most recently used inputs. Inputs are keyed after rounding to
+// multiples of `INPUT_QUANTUM`, so values that differ only by float noise,
+// or 0.0 and -0.0, share an entry. A stored model's network never changes,
+// and retraining produces a new model with an empty cache, so entries never
+// go stale.
+
+use crate::api::PredictionCacheStats;
+use std::collections::HashMap;
+
+/// Inputs closer than this share a cache entry
+const INPUT_QUANTUM: f64 = 1e-9;
+
+/// Least recently used cache of network outputs, keyed by quantized input
+#[derive(Debug, Clone)]
+pub(crate) struct PredictionCache {
+    capacity: usize,
+    entries: HashMap<Vec<u64>, Entry>,
+    /// Incremented on every lookup, to order entries by last use
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    output: Vec<f64>,
+    last_used: u64,
+}
+
+impl PredictionCache {
+    /// An empty cache holding at most `capacity` outputs
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cached output for `input`, or the result of `predict`, which is
+    /// cached when it succeeds
+    pub(crate) fn get_or_insert_with<E>(
+        &mut self,
+        input: &[f64],
+        predict: impl FnOnce() -> Result<Vec<f64>, E>,
+    ) -> Result<Vec<f64>, E> {
+        self.clock += 1;
+        let key = quantize(input);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+            self.hits += 1;
+            return Ok(entry.output.clone());
+        }
+
+        self.misses += 1;
+        let output = predict()?;
+        if self.capacity == 0 {
+            return Ok(output);
+        }
+        if self.entries.len() >= self.capacity
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                output: output.clone(),
+                last_used: self.clock,
+            },
+        );
+        Ok(output)
+    }
+
+    pub(crate) fn stats(&self) -> PredictionCacheStats {
+        PredictionCacheStats {
+            capacity: self.capacity,
+            entries: self.entries.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+}
+
+/// Cache key of an input: each value rounded to a multiple of `INPUT_QUANTUM`
+fn quantize(input: &[f64]) -> Vec<u64> {
+    // Adding 0.0 turns -0.0 into 0.0, so both signs share a key
+    input
+        .iter()
+        .map(|value| ((value / INPUT_QUANTUM).round() + 0.0).to_bits())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(cache: &mut PredictionCache, input: &[f64]) -> Vec<f64> {
+        cache
+            .get_or_insert_with(input, || Ok::<_, ()>(vec![input.iter().sum()]))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_repeated_inputs_hit() {
+        let mut cache = PredictionCache::new(4);
+
+        assert_eq!(lookup(&mut cache, &[1.0, 2.0]), [3.0]);
+        assert_eq!(lookup(&mut cache, &[1.0, 2.0]), [3.0]);
+        // Float noise and signed zeros land on the same key
+        lookup(&mut cache, &[0.1 + 0.2, -0.0]);
+        lookup(&mut cache, &[0.3, 0.0]);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 2, 2));
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let mut cache = PredictionCache::new(2);
+        lookup(&mut cache, &[1.0]);
+        lookup(&mut cache, &[2.0]);
+        lookup(&mut cache, &[1.0]);
+        lookup(&mut cache, &[3.0]);
+
+        // 2.0 was evicted, 1.0 kept
+        lookup(&mut cache, &[1.0]);
+        lookup(&mut cache, &[2.0]);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 4, 2));
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let mut cache = PredictionCache::new(2);
+
+        assert!(cache.get_or_insert_with(&[f64::NAN], || Err("not finite")).is_err());
+        assert!(cache.get_or_insert_with(&[f64::NAN], || Err("not finite")).is_err());
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
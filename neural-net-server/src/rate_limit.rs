@@ -0,0 +1,190 @@
+// Per-client rate limiting for expensive endpoints
+
+use crate::AppState;
+use crate::error::ApiError;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Rate limit settings for training endpoints
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Sustained number of requests allowed per client per minute.
+    /// This is also the burst size.
+    pub requests_per_minute: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 5,
+        }
+    }
+}
+
+/// Token bucket for a single client
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Every bucket refills completely within this long, after which it is
+/// the same as a fresh one
+const REFILL_PERIOD: Duration = Duration::from_secs(60);
+
+/// Buckets of the clients seen recently
+#[derive(Debug)]
+struct Buckets {
+    clients: HashMap<IpAddr, Bucket>,
+    last_sweep: Instant,
+}
+
+/// Token-bucket rate limiter keyed by client IP
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(Buckets {
+                clients: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take a token for `client`, or return how long until one is available
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let capacity = self.config.requests_per_minute.max(1) as f64;
+        let refill_per_sec = capacity / REFILL_PERIOD.as_secs_f64();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        // Drop buckets that have refilled to capacity, so the map only holds
+        // clients seen within the last refill period
+        if now.duration_since(buckets.last_sweep) >= REFILL_PERIOD {
+            buckets
+                .clients
+                .retain(|_, bucket| now.duration_since(bucket.last_refill) < REFILL_PERIOD);
+            buckets.last_sweep = now;
+        }
+        let bucket = buckets.clients.entry(client).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / refill_per_sec;
+            Err(Duration::from_secs_f64(wait))
+        }
+    }
+}
+
+/// Middleware limiting how often a client may start training
+pub async fn limit_training(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = &state.rate_limiter else {
+        return next.run(request).await;
+    };
+
+    match limiter.check(addr.ip()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let mut response = ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                "Too many training requests, try again later",
+            )
+            .into_response();
+            let retry_after = retry_after_secs(wait).to_string();
+            if let Ok(value) = HeaderValue::from_str(&retry_after) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+            response
+        }
+    }
+}
+
+/// Whole seconds to send in `Retry-After`, rounded up so a client that waits
+/// that long finds a token
+fn retry_after_secs(wait: Duration) -> u64 {
+    (wait.as_secs_f64().ceil() as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_limits() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_minute: 3,
+        });
+        let client: IpAddr = "127.0.0.1".parse().unwrap();
+
+        assert!(limiter.check(client).is_ok());
+        assert!(limiter.check(client).is_ok());
+        assert!(limiter.check(client).is_ok());
+        let wait = limiter.check(client).expect_err("Fourth request should be limited");
+        assert!(wait > Duration::ZERO);
+
+        // Other clients have their own bucket
+        let other: IpAddr = "10.0.0.1".parse().unwrap();
+        assert!(limiter.check(other).is_ok());
+    }
+
+    #[test]
+    fn test_refilled_buckets_are_evicted() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_minute: 3,
+        });
+        let start = Instant::now();
+        for i in 0..100u8 {
+            let client = IpAddr::from([10, 0, 0, i]);
+            assert!(limiter.check_at(client, start).is_ok());
+        }
+        assert_eq!(limiter.buckets.lock().unwrap().clients.len(), 100);
+
+        // Once a refill period has passed, only the newly seen client is kept
+        let later = start + REFILL_PERIOD;
+        let client: IpAddr = "127.0.0.1".parse().unwrap();
+        assert!(limiter.check_at(client, later).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().clients.len(), 1);
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        assert_eq!(retry_after_secs(Duration::from_millis(1)), 1);
+        assert_eq!(retry_after_secs(Duration::from_millis(1900)), 2);
+        assert_eq!(retry_after_secs(Duration::from_secs(20)), 20);
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
+    }
+}
@@ -0,0 +1,72 @@
+// Serving the web UI
+//
+// Files come from the configured static directory. With the `embed-static`
+// feature the crate's static/ directory is also compiled into the binary and
+// served whenever the configured directory doesn't exist, so the server can
+// be deployed as a single file while development still picks up edits on
+// disk. Unknown paths get index.html in both modes so client-side routes work.
+
+use axum::Router;
+use std::path::Path;
+use tower_http::services::{ServeDir, ServeFile};
+
+/// Whether files are served from the embedded copy instead of `static_dir`
+pub(crate) fn uses_embedded(static_dir: &Path) -> bool {
+    cfg!(feature = "embed-static") && !static_dir.is_dir()
+}
+
+/// Routes serving the web UI
+pub(crate) fn routes(static_dir: &Path) -> Router {
+    #[cfg(feature = "embed-static")]
+    if uses_embedded(static_dir) {
+        return Router::new().fallback(embedded::serve);
+    }
+
+    Router::new().nest_service(
+        "/",
+        ServeDir::new(static_dir).fallback(ServeFile::new(static_dir.join("index.html"))),
+    )
+}
+
+/// Read a text file of the web UI, e.g. `docs.html`
+pub(crate) async fn read_to_string(static_dir: &Path, name: &str) -> Option<String> {
+    #[cfg(feature = "embed-static")]
+    if uses_embedded(static_dir) {
+        return embedded::read_to_string(name);
+    }
+
+    tokio::fs::read_to_string(static_dir.join(name)).await.ok()
+}
+
+#[cfg(feature = "embed-static")]
+mod embedded {
+    use axum::http::{StatusCode, Uri, header};
+    use axum::response::{IntoResponse, Response};
+
+    #[derive(rust_embed::RustEmbed)]
+    #[folder = "static/"]
+    struct Assets;
+
+    /// Serve an embedded file, falling back to index.html
+    pub(super) async fn serve(uri: Uri) -> Response {
+        let mut path = uri.path().trim_start_matches('/').to_string();
+        if path.is_empty() || path.ends_with('/') {
+            path.push_str("index.html");
+        }
+
+        let found = Assets::get(&path).or_else(|| Assets::get("index.html"));
+        match found {
+            Some(file) => (
+                [(header::CONTENT_TYPE, file.metadata.mimetype().to_string())],
+                file.data,
+            )
+                .into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    pub(super) fn read_to_string(name: &str) -> Option<String> {
+        let file = Assets::get(name)?;
+        String::from_utf8(file.data.into_owned()).ok()
+    }
+}
@@ -0,0 +1,125 @@
+// Watchdog for training runs that stop making progress
+//
+// With `stall_timeout_secs` configured, every training run gets a watchdog
+// thread that expects a heartbeat after each completed epoch. If none arrives
+// within the timeout, the watchdog sets the job's stop flag, so training
+// stops at the next epoch boundary, and the run fails with a
+// `training_stalled` error instead of storing a model.
+
+use crate::error::ApiError;
+use axum::http::StatusCode;
+use neural_network::training::TrainingController;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// Stops a training run when no epoch completes within a timeout
+pub(crate) struct Watchdog {
+    timeout: Duration,
+    fired: Arc<AtomicBool>,
+}
+
+impl Watchdog {
+    /// Watch `controller`, setting `stop` if no epoch completes within `timeout`
+    /// of the previous one (or of this call, for the first epoch)
+    ///
+    /// The watchdog thread exits when it fires or the controller is dropped.
+    pub(crate) fn watch(
+        controller: &mut TrainingController,
+        timeout: Duration,
+        stop: Arc<AtomicBool>,
+    ) -> Self {
+        let (heartbeat, heartbeats) = mpsc::channel();
+        controller.add_progress_callback(Box::new(move |_, _| {
+            let _ = heartbeat.send(());
+        }));
+
+        let fired = Arc::new(AtomicBool::new(false));
+        let fired_flag = fired.clone();
+        std::thread::spawn(move || {
+            loop {
+                match heartbeats.recv_timeout(timeout) {
+                    Ok(()) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                    Err(RecvTimeoutError::Timeout) => {
+                        tracing::warn!(?timeout, "training stalled; stopping it");
+                        fired_flag.store(true, Ordering::SeqCst);
+                        stop.store(true, Ordering::SeqCst);
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self { timeout, fired }
+    }
+
+    /// Fails with a `training_stalled` error if the watchdog stopped the run
+    pub(crate) fn check(&self) -> Result<(), ApiError> {
+        if !self.fired.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        Err(ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "training_stalled",
+            format!("No epoch completed within {:?}, so training was stopped", self.timeout),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neural_network::activations::SIGMOID;
+    use neural_network::network::Network;
+    use neural_network::training::TrainingConfig;
+
+    fn controller(epochs: u32) -> TrainingController {
+        let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1);
+        TrainingController::new(network, TrainingConfig {
+            epochs,
+            ..Default::default()
+        })
+    }
+
+    fn train(controller: &mut TrainingController) -> u32 {
+        controller
+            .train(vec![vec![0.0, 1.0], vec![1.0, 1.0]], vec![vec![0.0], vec![1.0]])
+            .unwrap()
+            .epochs_run
+    }
+
+    #[test]
+    fn test_stalled_training_is_stopped() {
+        let mut controller = controller(10);
+        // Epoch 3 hangs for longer than the timeout
+        controller.add_pre_epoch_callback(Box::new(|epoch, _| {
+            if epoch == 3 {
+                std::thread::sleep(Duration::from_millis(500));
+            }
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+        controller.set_stop_flag(stop.clone());
+        let watchdog = Watchdog::watch(&mut controller, Duration::from_millis(100), stop.clone());
+
+        // The stalled epoch finishes, then training stops before the next one
+        assert_eq!(train(&mut controller), 3);
+        assert!(stop.load(Ordering::SeqCst));
+        let error = watchdog.check().unwrap_err();
+        assert_eq!(error.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.code, "training_stalled");
+    }
+
+    #[test]
+    fn test_steady_training_is_left_alone() {
+        let mut controller = controller(50);
+        let stop = Arc::new(AtomicBool::new(false));
+        controller.set_stop_flag(stop.clone());
+        let watchdog = Watchdog::watch(&mut controller, Duration::from_secs(5), stop.clone());
+
+        assert_eq!(train(&mut controller), 50);
+        assert!(!stop.load(Ordering::SeqCst));
+        assert!(watchdog.check().is_ok());
+    }
+}
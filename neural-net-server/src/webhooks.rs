@@ -0,0 +1,122 @@
+// Job completion webhooks
+//
+// A training request may name a `callback_url`. When the job ends, the
+// server POSTs a `JobNotification` there from a background task, retrying
+// failed deliveries a few times, so a slow or unreachable receiver never
+// holds up training or the response to the request.
+
+use crate::api::{ErrorResponse, JobNotification, JobOutcome, TrainResponse};
+use crate::error::ApiError;
+use std::time::Duration;
+
+/// Delivery attempts per notification, including the first
+pub const WEBHOOK_ATTEMPTS: u32 = 3;
+
+/// Time allowed for each delivery attempt
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause before the first retry, doubled before each later one
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// HTTP client used to deliver notifications
+pub(crate) fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .expect("Webhook client should build")
+}
+
+/// Check a callback URL is an absolute http or https URL
+pub(crate) fn validate_callback_url(url: &str) -> Result<(), ApiError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| ApiError::bad_request(format!("Invalid callback_url {}: {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(ApiError::bad_request(format!(
+            "callback_url must use http or https, got {}",
+            parsed.scheme()
+        )));
+    }
+    Ok(())
+}
+
+impl JobNotification {
+    /// Notification for a job that ended with `result`
+    pub(crate) fn new(job_id: u64, result: &Result<TrainResponse, ApiError>) -> Self {
+        match result {
+            Ok(response) => Self {
+                job_id,
+                status: JobOutcome::Completed,
+                model_id: Some(response.model_id.clone()),
+                final_loss: Some(response.final_loss),
+                duration_ms: Some(response.duration_ms),
+                error: None,
+            },
+            Err(e) => Self {
+                job_id,
+                status: if e.code == "job_cancelled" {
+                    JobOutcome::Cancelled
+                } else {
+                    JobOutcome::Failed
+                },
+                model_id: None,
+                final_loss: None,
+                duration_ms: None,
+                error: Some(ErrorResponse {
+                    code: e.code.to_string(),
+                    error: e.message.clone(),
+                    request_id: None,
+                }),
+            },
+        }
+    }
+}
+
+/// Deliver a notification in the background
+///
+/// Must be called from within the server's runtime, which includes its
+/// blocking training threads.
+pub(crate) fn notify(client: &reqwest::Client, url: String, notification: JobNotification) {
+    let client = client.clone();
+    tokio::spawn(async move {
+        let mut delay = WEBHOOK_RETRY_DELAY;
+        for attempt in 1..=WEBHOOK_ATTEMPTS {
+            let result = client
+                .post(&url)
+                .json(&notification)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => {
+                    tracing::info!(job_id = notification.job_id, url = %url, "webhook delivered");
+                    return;
+                }
+                Err(e) => tracing::warn!(
+                    job_id = notification.job_id,
+                    url = %url,
+                    attempt,
+                    error = %e,
+                    "webhook delivery failed"
+                ),
+            }
+            if attempt < WEBHOOK_ATTEMPTS {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_callback_url() {
+        assert!(validate_callback_url("http://127.0.0.1:9000/done").is_ok());
+        assert!(validate_callback_url("https://example.com/hooks/train").is_ok());
+        assert!(validate_callback_url("ftp://example.com/done").is_err());
+        assert!(validate_callback_url("file:///etc/passwd").is_err());
+        assert!(validate_callback_url("/relative/path").is_err());
+    }
+}
@@ -276,6 +276,7 @@ async function startApiTraining() {
         const reader = response.body.getReader();
         const decoder = new TextDecoder();
         let buffer = '';
+        let eventType = 'progress';
 
         updateStatus('Training on server (live updates)...', 'training');
 
@@ -288,9 +289,18 @@ async function startApiTraining() {
             buffer = lines.pop();
 
             for (const line of lines) {
-                if (line.startsWith('data: ')) {
+                if (line.startsWith('event: ')) {
+                    eventType = line.substring(7).trim();
+                } else if (line.startsWith('data: ')) {
                     const data = JSON.parse(line.substring(6));
-                    updateTrainingProgress(data.epoch, data.loss, epochs);
+                    if (eventType === 'progress') {
+                        updateTrainingProgress(data.epoch, data.loss, epochs);
+                    } else if (eventType === 'error') {
+                        throw new Error(data.error);
+                    }
+                } else if (line === '') {
+                    // A blank line ends the event
+                    eventType = 'progress';
                 }
             }
         }
@@ -0,0 +1,66 @@
+// Integration tests for choosing hidden and output activations
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn quadrant_request(hidden: Option<&str>, output: Option<&str>) -> TrainRequest {
+    TrainRequest {
+        example: Some("quadrant".to_string()),
+        epochs: 500,
+        learning_rate: 0.1,
+        seed: Some(7),
+        hidden_activation: hidden.map(String::from),
+        output_activation: output.map(String::from),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_softmax_output_activation() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3056").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3056");
+
+    let trained = client
+        .train(&quadrant_request(Some("tanh"), Some("softmax")))
+        .await
+        .expect("Should train with softmax output");
+    let output = client.eval(&trained.model_id, vec![0.7, -0.4]).await.unwrap().output;
+    assert_eq!(output.len(), 4);
+    let sum: f64 = output.iter().sum();
+    assert!((sum - 1.0).abs() < 1e-6, "Softmax outputs should sum to 1, got {}", sum);
+    assert!(output.iter().all(|p| *p >= 0.0));
+
+    // Sigmoid outputs are independent, so they don't generally sum to 1
+    let sigmoid = client.train(&quadrant_request(None, None)).await.unwrap();
+    let output = client.eval(&sigmoid.model_id, vec![0.7, -0.4]).await.unwrap().output;
+    assert!((output.iter().sum::<f64>() - 1.0).abs() > 1e-6);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_invalid_activations_are_rejected() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3057").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let http = reqwest::Client::new();
+    for (request, message) in [
+        (quadrant_request(None, Some("swish")), "Unknown activation: swish"),
+        (quadrant_request(Some("softmax"), None), "only supported as the output"),
+    ] {
+        let response = http
+            .post("http://127.0.0.1:3057/api/train")
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains(message), "{}", body);
+    }
+
+    handle.abort();
+}
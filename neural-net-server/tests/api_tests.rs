@@ -195,3 +195,47 @@ async fn test_eval_nonexistent_model() {
 
     handle.abort();
 }
+
+#[tokio::test]
+async fn test_train_response_includes_report() {
+    let handle = start_test_server(3016).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let request_body = json!({
+        "example": "and",
+        "epochs": 5000,
+        "learning_rate": 0.5
+    });
+
+    let response = client
+        .post("http://127.0.0.1:3016/api/train")
+        .json(&request_body)
+        .send()
+        .await
+        .expect("Should get response");
+
+    assert!(response.status().is_success());
+
+    let body: serde_json::Value = response.json().await.expect("Should parse JSON");
+    let final_loss = body["final_loss"].as_f64().expect("Should return final_loss");
+    assert!(final_loss.is_finite(), "final_loss should be finite");
+    assert_eq!(body["epochs_run"], 5000);
+    assert!(body["duration_ms"].is_u64(), "Should return duration_ms");
+    assert_eq!(body["accuracy"].as_f64(), Some(1.0), "AND should be learned after 5000 epochs");
+
+    // Model info reports the same training summary
+    let model_id = body["model_id"].as_str().unwrap();
+    let info: serde_json::Value = client
+        .get(format!("http://127.0.0.1:3016/api/models/{}", model_id))
+        .send()
+        .await
+        .expect("Should get response")
+        .json()
+        .await
+        .expect("Should parse JSON");
+    assert_eq!(info["final_loss"], body["final_loss"]);
+    assert_eq!(info["accuracy"], body["accuracy"]);
+
+    handle.abort();
+}
@@ -0,0 +1,111 @@
+// Integration tests for API key authentication
+use neural_net_server::config::ServerConfig;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const API_KEY: &str = "test-secret-key";
+
+async fn start_auth_server(port: u16) -> tokio::task::JoinHandle<Result<(), anyhow::Error>> {
+    let config = ServerConfig {
+        addr: format!("127.0.0.1:{}", port),
+        api_keys: vec![API_KEY.to_string()],
+        ..Default::default()
+    };
+    tokio::spawn(async move {
+        neural_net_server::run_server(config).await
+    })
+}
+
+fn train_body() -> serde_json::Value {
+    json!({
+        "example": "and",
+        "epochs": 10,
+        "learning_rate": 0.5
+    })
+}
+
+#[tokio::test]
+async fn test_train_without_key_is_rejected() {
+    let handle = start_auth_server(3032).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post("http://127.0.0.1:3032/api/train")
+        .json(&train_body())
+        .send()
+        .await
+        .expect("Should get response");
+
+    assert_eq!(response.status(), 401, "Missing key should return 401");
+    let body: serde_json::Value = response.json().await.expect("Should parse JSON error");
+    assert_eq!(body["code"], "unauthorized");
+
+    // A wrong key is forbidden, and the key is not echoed back
+    let response = client
+        .post("http://127.0.0.1:3032/api/train")
+        .header("Authorization", "Bearer wrong-key")
+        .json(&train_body())
+        .send()
+        .await
+        .expect("Should get response");
+
+    assert_eq!(response.status(), 403, "Wrong key should return 403");
+    let text = response.text().await.unwrap();
+    assert!(!text.contains("wrong-key"), "Error should not echo the key");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_train_with_key_succeeds() {
+    let handle = start_auth_server(3033).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post("http://127.0.0.1:3033/api/train")
+        .header("Authorization", format!("Bearer {}", API_KEY))
+        .json(&train_body())
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(response.status().is_success(), "Bearer key should be accepted");
+
+    let response = client
+        .post("http://127.0.0.1:3033/api/train")
+        .header("X-Api-Key", API_KEY)
+        .json(&train_body())
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(response.status().is_success(), "X-Api-Key should be accepted");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_read_only_endpoints_stay_open() {
+    let handle = start_auth_server(3034).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get("http://127.0.0.1:3034/api/examples")
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(response.status().is_success(), "GET /api/examples should not require a key");
+
+    let response = client
+        .get("http://127.0.0.1:3034/health")
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(response.status().is_success(), "Health should not require a key");
+
+    handle.abort();
+}
@@ -0,0 +1,67 @@
+// Integration tests for the typed API client
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn start_test_server(port: u16) -> tokio::task::JoinHandle<Result<(), anyhow::Error>> {
+    let addr = format!("127.0.0.1:{}", port);
+    tokio::spawn(async move {
+        neural_net_server::run_server(&addr).await
+    })
+}
+
+#[tokio::test]
+async fn test_client_end_to_end() {
+    let handle = start_test_server(3030).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3030");
+
+    // list_examples
+    let examples = client.list_examples().await.expect("Should list examples");
+    assert!(examples.iter().any(|ex| ex.name == "xor"), "Should include xor");
+
+    // train
+    let trained = client
+        .train(&TrainRequest {
+            example: Some("and".to_string()),
+            epochs: 100,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .expect("Should train model");
+    assert_eq!(trained.example, "and");
+    assert_eq!(trained.epochs_run, 100);
+
+    // eval
+    let eval = client
+        .eval(&trained.model_id, vec![1.0, 1.0])
+        .await
+        .expect("Should evaluate model");
+    assert_eq!(eval.output.len(), 1);
+
+    // model_info
+    let info = client
+        .model_info(&trained.model_id)
+        .await
+        .expect("Should fetch model info");
+    assert_eq!(info.model_id, trained.model_id);
+    assert_eq!(info.architecture, vec![2, 2, 1]);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_client_reports_server_errors() {
+    let handle = start_test_server(3031).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3031");
+
+    let result = client.model_info("nonexistent-model-id").await;
+    assert!(result.is_err(), "Unknown model should be an error");
+
+    handle.abort();
+}
@@ -0,0 +1,150 @@
+// Integration tests for comparing two models
+use neural_net_server::api::{PairwiseCompareRequest, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn xor_request(epochs: u32) -> TrainRequest {
+    TrainRequest {
+        example: Some("xor".to_string()),
+        epochs,
+        learning_rate: 0.5,
+        seed: Some(42),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_compare_trained_and_untrained_models() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3058").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3058";
+    let client = NeuralNetClient::new(base);
+    let trained = client.train(&xor_request(10000)).await.expect("Should train");
+    let barely = client.train(&xor_request(1)).await.expect("Should train");
+
+    let comparison = client
+        .compare_models(&trained.model_id, &barely.model_id, "xor")
+        .await
+        .expect("Should compare models");
+
+    assert_eq!(comparison.example, "xor");
+    assert_eq!(comparison.a.model_id, trained.model_id);
+    assert!(comparison.a.accuracy > comparison.b.accuracy);
+    assert!(comparison.a.loss < comparison.b.loss);
+    assert_eq!(comparison.samples.len(), 4);
+    for sample in &comparison.samples {
+        assert_eq!(sample.output_a.len(), 1);
+        assert_eq!(sample.output_b.len(), 1);
+    }
+
+    // Same architecture and seed, so the deltas show how far training moved
+    let deltas = comparison.weight_deltas.expect("Matching architectures have deltas");
+    assert_eq!(deltas.len(), 2);
+    assert!(deltas.iter().all(|delta| *delta > 0.0));
+
+    // A model that doesn't fit the example's data is rejected
+    let quadrant = client
+        .train(&TrainRequest {
+            example: Some("quadrant".to_string()),
+            ..xor_request(1)
+        })
+        .await
+        .unwrap();
+    let http = reqwest::Client::new();
+    let response = http
+        .get(format!("{}/api/models/compare", base))
+        .query(&[("a", trained.model_id.as_str()), ("b", quadrant.model_id.as_str()), ("example", "xor")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "bad_request");
+
+    let response = http
+        .get(format!("{}/api/models/compare", base))
+        .query(&[("a", trained.model_id.as_str()), ("b", "missing"), ("example", "xor")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_compare_outputs_on_custom_inputs() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3079").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3079";
+    let client = NeuralNetClient::new(base);
+    let and_request = |seed| TrainRequest {
+        example: Some("and".to_string()),
+        epochs: 500,
+        learning_rate: 0.5,
+        seed: Some(seed),
+        ..Default::default()
+    };
+    let a = client.train(&and_request(1)).await.expect("Should train");
+    let b = client.train(&and_request(2)).await.expect("Should train");
+
+    let request = PairwiseCompareRequest {
+        model_a: a.model_id.clone(),
+        model_b: b.model_id.clone(),
+        inputs: vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]],
+    };
+    let comparison = client.compare_outputs(&request).await.expect("Should compare");
+
+    assert_eq!(comparison.outputs_a.len(), 3);
+    assert_eq!(comparison.outputs_b.len(), 3);
+    assert_eq!(comparison.diff.len(), 3);
+    for ((output_a, output_b), diff) in comparison.outputs_a.iter().zip(&comparison.outputs_b).zip(&comparison.diff) {
+        assert_eq!(diff.len(), 1);
+        assert!((diff[0] - (output_a[0] - output_b[0])).abs() < 1e-12);
+    }
+    // Different seeds give different networks
+    assert!(comparison.diff.iter().any(|diff| diff[0] != 0.0));
+
+    // Models must fit the inputs and each other
+    let http = reqwest::Client::new();
+    let post = |request: &PairwiseCompareRequest| {
+        http.post(format!("{}/api/compare", base)).json(request).send()
+    };
+    let response = post(&PairwiseCompareRequest {
+        inputs: vec![vec![1.0]],
+        ..request.clone()
+    })
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let quadrant = client
+        .train(&TrainRequest {
+            example: Some("quadrant".to_string()),
+            ..xor_request(1)
+        })
+        .await
+        .unwrap();
+    let response = post(&PairwiseCompareRequest {
+        model_b: quadrant.model_id,
+        ..request.clone()
+    })
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("share input and output sizes"));
+
+    let response = post(&PairwiseCompareRequest {
+        model_b: "missing".to_string(),
+        ..request
+    })
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.abort();
+}
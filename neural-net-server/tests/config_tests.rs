@@ -0,0 +1,273 @@
+// Integration tests for server configuration loading
+use neural_net_server::config::ServerConfig;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/server.toml")
+}
+
+#[test]
+fn test_load_toml_fixture() {
+    let config = ServerConfig::from_toml(&fixture()).expect("Fixture should parse");
+
+    assert_eq!(config.addr, "127.0.0.1:4000");
+    assert_eq!(config.static_dir, PathBuf::from("public"));
+    assert_eq!(config.model_dir, Some(PathBuf::from("models")));
+    assert_eq!(config.cors_origins, vec!["http://allowed.example"]);
+    assert_eq!(config.max_epochs, Some(5000));
+    assert_eq!(config.max_concurrent_trainings, Some(2));
+    assert_eq!(config.api_keys, vec!["file-key"]);
+    assert_eq!(config.rate_limit.unwrap().requests_per_minute, 10);
+}
+
+#[test]
+fn test_toml_missing_keys_use_defaults() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("partial.toml");
+    std::fs::write(&path, "max_epochs = 100\n").unwrap();
+
+    let config = ServerConfig::from_toml(&path).unwrap();
+
+    assert_eq!(config.max_epochs, Some(100));
+    assert_eq!(config.addr, ServerConfig::default().addr);
+    assert!(config.cors_origins.is_empty());
+}
+
+#[test]
+fn test_toml_rejects_unknown_keys() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("typo.toml");
+    std::fs::write(&path, "max_epoch = 100\n").unwrap();
+
+    assert!(ServerConfig::from_toml(&path).is_err());
+}
+
+#[test]
+fn test_env_overrides_file() {
+    let env: HashMap<&str, &str> = HashMap::from([
+        ("NN_SERVER_ADDR", "0.0.0.0:8080"),
+        ("NN_SERVER_MAX_EPOCHS", "200"),
+        ("NN_SERVER_CORS_ORIGINS", "http://a.example, http://b.example"),
+    ]);
+
+    let config = ServerConfig::from_toml(&fixture())
+        .unwrap()
+        .with_overrides(|key| env.get(key).map(|v| v.to_string()))
+        .unwrap();
+
+    // Overridden by the environment
+    assert_eq!(config.addr, "0.0.0.0:8080");
+    assert_eq!(config.max_epochs, Some(200));
+    assert_eq!(config.cors_origins, vec!["http://a.example", "http://b.example"]);
+
+    // Untouched values still come from the file
+    assert_eq!(config.max_concurrent_trainings, Some(2));
+    assert_eq!(config.api_keys, vec!["file-key"]);
+}
+
+#[test]
+fn test_log_settings() {
+    use neural_net_server::logging::LogFormat;
+
+    let config = ServerConfig::default();
+    assert_eq!(config.log_level, "info");
+    assert_eq!(config.log_format, LogFormat::Pretty);
+
+    let env: HashMap<&str, &str> =
+        HashMap::from([("NN_SERVER_LOG_LEVEL", "debug"), ("NN_SERVER_LOG_FORMAT", "json")]);
+    let config = config
+        .with_overrides(|key| env.get(key).map(|v| v.to_string()))
+        .unwrap();
+    assert_eq!(config.log_level, "debug");
+    assert_eq!(config.log_format, LogFormat::Json);
+
+    let result = ServerConfig::default()
+        .with_overrides(|key| (key == "NN_SERVER_LOG_FORMAT").then(|| "xml".to_string()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_webhooks_setting() {
+    assert!(ServerConfig::default().webhooks);
+
+    let config = ServerConfig::default()
+        .with_overrides(|key| (key == "NN_SERVER_WEBHOOKS").then(|| "false".to_string()))
+        .unwrap();
+    assert!(!config.webhooks);
+
+    let result = ServerConfig::default()
+        .with_overrides(|key| (key == "NN_SERVER_WEBHOOKS").then(|| "sometimes".to_string()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cors_settings_from_env() {
+    let env: HashMap<&str, &str> = HashMap::from([
+        ("NN_SERVER_CORS_METHODS", "GET, POST"),
+        ("NN_SERVER_CORS_ALLOW_CREDENTIALS", "true"),
+    ]);
+    let config = ServerConfig::default()
+        .with_overrides(|key| env.get(key).map(|v| v.to_string()))
+        .unwrap();
+
+    assert_eq!(config.cors_methods, vec!["GET", "POST"]);
+    assert!(config.cors_allow_credentials);
+}
+
+#[test]
+fn test_invalid_env_value_is_an_error() {
+    let result = ServerConfig::default()
+        .with_overrides(|key| (key == "NN_SERVER_MAX_EPOCHS").then(|| "lots".to_string()));
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_restrictive_cors_rejects_other_origins() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3037".to_string(),
+        cors_origins: vec!["http://allowed.example".to_string()],
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    let allowed = client
+        .get("http://127.0.0.1:3037/health")
+        .header("Origin", "http://allowed.example")
+        .send()
+        .await
+        .expect("Should get response");
+    assert_eq!(
+        allowed.headers().get("access-control-allow-origin").unwrap(),
+        "http://allowed.example"
+    );
+
+    let other = client
+        .get("http://127.0.0.1:3037/health")
+        .header("Origin", "http://evil.example")
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(
+        other.headers().get("access-control-allow-origin").is_none(),
+        "Other origins should not be allowed"
+    );
+
+    let preflight = client
+        .request(reqwest::Method::OPTIONS, "http://127.0.0.1:3037/api/train")
+        .header("Origin", "http://evil.example")
+        .header("Access-Control-Request-Method", "POST")
+        .send()
+        .await
+        .expect("Should get response");
+    assert!(preflight.headers().get("access-control-allow-origin").is_none());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_max_epochs_is_enforced() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3038".to_string(),
+        max_epochs: Some(100),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:3038/api/train")
+        .json(&json!({
+            "example": "and",
+            "epochs": 1000,
+            "learning_rate": 0.5
+        }))
+        .send()
+        .await
+        .expect("Should get response");
+
+    assert_eq!(response.status(), 400);
+
+    handle.abort();
+}
+
+/// Send a CORS preflight for a POST to `url` from `origin`
+async fn preflight(url: &str, origin: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .request(reqwest::Method::OPTIONS, url)
+        .header("Origin", origin)
+        .header("Access-Control-Request-Method", "POST")
+        .header("Access-Control-Request-Headers", "content-type")
+        .send()
+        .await
+        .expect("Should get response")
+}
+
+#[tokio::test]
+async fn test_scoped_cors_answers_preflights() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3071".to_string(),
+        cors_origins: vec!["http://allowed.example".to_string()],
+        cors_methods: vec!["GET".to_string(), "POST".to_string()],
+        cors_allow_credentials: true,
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    for endpoint in ["/api/train", "/api/eval", "/api/datasets"] {
+        let url = format!("http://127.0.0.1:3071{}", endpoint);
+
+        let allowed = preflight(&url, "http://allowed.example").await;
+        assert!(allowed.status().is_success(), "{}: {}", endpoint, allowed.status());
+        let headers = allowed.headers();
+        assert_eq!(headers["access-control-allow-origin"], "http://allowed.example");
+        assert_eq!(headers["access-control-allow-methods"], "GET,POST");
+        assert_eq!(headers["access-control-allow-headers"], "content-type");
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+
+        let other = preflight(&url, "http://evil.example").await;
+        assert!(
+            other.headers().get("access-control-allow-origin").is_none(),
+            "{} should not allow other origins",
+            endpoint
+        );
+    }
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_wildcard_cors_origin() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3072".to_string(),
+        cors_origins: vec!["*".to_string()],
+        cors_methods: vec!["POST".to_string()],
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let response = preflight("http://127.0.0.1:3072/api/train", "http://anywhere.example").await;
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.headers()["access-control-allow-methods"], "POST");
+
+    handle.abort();
+
+    // Credentials can't be offered to every origin
+    let config = ServerConfig {
+        addr: "127.0.0.1:3072".to_string(),
+        cors_origins: vec!["*".to_string()],
+        cors_allow_credentials: true,
+        ..Default::default()
+    };
+    let error = neural_net_server::run_server(config).await.unwrap_err();
+    assert!(error.to_string().contains("cors_allow_credentials"), "{}", error);
+}
@@ -0,0 +1,93 @@
+// Integration tests for the confusion matrix endpoint
+use neural_net_server::api::{ConfusionRequest, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_network::examples;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_quadrant_confusion_matrix() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3047").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3047");
+    let trained = client
+        .train(&TrainRequest {
+            example: Some("quadrant".to_string()),
+            epochs: 2000,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .expect("Should train");
+
+    let quadrant = examples::get_example("quadrant").unwrap();
+    let report = client
+        .confusion(
+            &trained.model_id,
+            &ConfusionRequest {
+                inputs: quadrant.inputs.clone(),
+                targets: quadrant.targets.clone(),
+            },
+        )
+        .await
+        .expect("Should compute confusion matrix");
+
+    assert_eq!(report.confusion_matrix.len(), 4);
+    assert!(report.confusion_matrix.iter().all(|row| row.len() == 4));
+    let total: usize = report.confusion_matrix.iter().flatten().sum();
+    assert_eq!(total, quadrant.inputs.len());
+
+    assert_eq!(report.classes.len(), 4);
+    assert!(report.classes.iter().all(|c| c.support == 3));
+    assert_eq!(report.accuracy, trained.accuracy);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_confusion_validation() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3048").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3048");
+    let http = reqwest::Client::new();
+    let request = |example: &str| TrainRequest {
+        example: Some(example.to_string()),
+        epochs: 10,
+        learning_rate: 0.5,
+        ..Default::default()
+    };
+    let xor = client.train(&request("xor")).await.unwrap();
+    let quadrant = client.train(&request("quadrant")).await.unwrap();
+
+    let post = |model_id: &str, body: serde_json::Value| {
+        http.post(format!("http://127.0.0.1:3048/api/models/{}/confusion", model_id))
+            .json(&body)
+            .send()
+    };
+
+    // Single-output models aren't one-hot classifiers
+    let single = post(&xor.model_id, json!({"inputs": [[0.0, 1.0]], "targets": [[1.0]]}))
+        .await
+        .unwrap();
+    assert_eq!(single.status(), 400);
+
+    for body in [
+        json!({"inputs": [], "targets": []}),
+        json!({"inputs": [[1.0, 1.0]], "targets": []}),
+        json!({"inputs": [[1.0]], "targets": [[1.0, 0.0, 0.0, 0.0]]}),
+        json!({"inputs": [[1.0, 1.0]], "targets": [[1.0, 0.0]]}),
+    ] {
+        let response = post(&quadrant.model_id, body.clone()).await.unwrap();
+        assert_eq!(response.status(), 400, "{} should be rejected", body);
+    }
+
+    let missing = post("does-not-exist", json!({"inputs": [[1.0, 1.0]], "targets": [[1.0, 0.0, 0.0, 0.0]]}))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    handle.abort();
+}
@@ -0,0 +1,116 @@
+// Integration tests for dataset upload and training on uploaded data
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const XOR_CSV: &str = "x1,x2,y\n0,0,0\n0,1,1\n1,0,1\n1,1,0\n";
+
+#[tokio::test]
+async fn test_upload_train_and_eval_dataset() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3041").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3041");
+
+    let dataset = client
+        .upload_dataset(XOR_CSV, 2, 1)
+        .await
+        .expect("Should upload dataset");
+    assert_eq!(dataset.rows, 4);
+    assert_eq!(dataset.inputs, 2);
+    assert_eq!(dataset.targets, 1);
+
+    let listed = client.list_datasets().await.expect("Should list datasets");
+    assert!(listed.iter().any(|d| d.dataset_id == dataset.dataset_id));
+
+    let trained = client
+        .train(&TrainRequest {
+            dataset_id: Some(dataset.dataset_id.clone()),
+            architecture: Some(vec![2, 3, 1]),
+            epochs: 200,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .expect("Should train on dataset");
+    assert_eq!(trained.example, format!("dataset:{}", dataset.dataset_id));
+
+    let output = client
+        .eval(&trained.model_id, vec![1.0, 0.0])
+        .await
+        .expect("Should evaluate model");
+    assert_eq!(output.output.len(), 1);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_invalid_dataset_rows_are_rejected() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3042").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post("http://127.0.0.1:3042/api/datasets?inputs=2&targets=1")
+        .body("0,0,0\n0,1\n1,0,abc\n")
+        .send()
+        .await
+        .expect("Should get response");
+    assert_eq!(response.status(), 422);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "invalid_dataset");
+    let message = body["error"].as_str().unwrap();
+    assert!(message.contains("Row 2"), "Should name row 2: {}", message);
+    assert!(message.contains("Row 3"), "Should name row 3: {}", message);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_dataset_training_requires_architecture() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3043").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3043");
+    let dataset = client.upload_dataset(XOR_CSV, 2, 1).await.unwrap();
+
+    // Missing architecture
+    let result = client
+        .train(&TrainRequest {
+            dataset_id: Some(dataset.dataset_id.clone()),
+            epochs: 10,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await;
+    assert!(result.is_err());
+
+    // Both example and dataset
+    let result = client
+        .train(&TrainRequest {
+            example: Some("xor".to_string()),
+            dataset_id: Some(dataset.dataset_id.clone()),
+            architecture: Some(vec![2, 3, 1]),
+            epochs: 10,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await;
+    assert!(result.is_err());
+
+    // Architecture that doesn't match the data
+    let result = client
+        .train(&TrainRequest {
+            dataset_id: Some(dataset.dataset_id),
+            architecture: Some(vec![3, 3, 1]),
+            epochs: 10,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await;
+    assert!(result.is_err());
+
+    handle.abort();
+}
@@ -0,0 +1,57 @@
+// Tests that the shared API types match the server's wire format
+use neural_net_server::api::{TrainRequest, TrainResponse};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[test]
+fn test_train_request_wire_format() {
+    let request = TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: 100,
+        learning_rate: 0.5,
+        ..Default::default()
+    };
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({ "example": "xor", "epochs": 100, "learning_rate": 0.5 })
+    );
+
+    let round_trip: TrainRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(round_trip.example.as_deref(), Some("xor"));
+    assert_eq!(round_trip.epochs, 100);
+}
+
+#[tokio::test]
+async fn test_server_accepts_shared_train_request() {
+    let handle = tokio::spawn(async {
+        neural_net_server::run_server("127.0.0.1:3036").await
+    });
+    sleep(Duration::from_millis(100)).await;
+
+    let request = TrainRequest {
+        example: Some("or".to_string()),
+        epochs: 20,
+        learning_rate: 0.5,
+        ..Default::default()
+    };
+
+    // Send the serialized shared type as a raw body
+    let body = serde_json::to_string(&request).unwrap();
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:3036/api/train")
+        .header("content-type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("Should get response");
+
+    assert!(response.status().is_success());
+
+    let trained: TrainResponse = response.json().await.expect("Should parse shared response type");
+    assert_eq!(Some(trained.example), request.example);
+    assert_eq!(trained.epochs, request.epochs);
+
+    handle.abort();
+}
@@ -0,0 +1,102 @@
+// Integration tests for ensemble evaluation
+use neural_net_server::api::{EnsembleMethod, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_ensemble_of_seeded_xor_models() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3049").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3049");
+    let mut model_ids = Vec::new();
+    for seed in [1, 2] {
+        let trained = client
+            .train(&TrainRequest {
+                example: Some("xor".to_string()),
+                epochs: 500,
+                learning_rate: 0.5,
+                seed: Some(seed),
+                ..Default::default()
+            })
+            .await
+            .expect("Should train");
+        model_ids.push(trained.model_id);
+    }
+
+    let mean = client
+        .eval_ensemble(model_ids.clone(), vec![1.0, 0.0], EnsembleMethod::Mean)
+        .await
+        .expect("Should evaluate ensemble");
+    assert_eq!(mean.method, EnsembleMethod::Mean);
+    assert_eq!(mean.outputs.len(), 2);
+    assert_eq!(mean.outputs[0].model_id, model_ids[0]);
+
+    let first = mean.outputs[0].output[0];
+    let second = mean.outputs[1].output[0];
+    assert_ne!(first, second, "Different seeds should give different models");
+    let combined = mean.output[0];
+    assert!(
+        first.min(second) <= combined && combined <= first.max(second),
+        "Mean {} should lie between {} and {}",
+        combined,
+        first,
+        second
+    );
+
+    let vote = client
+        .eval_ensemble(model_ids, vec![1.0, 0.0], EnsembleMethod::Vote)
+        .await
+        .expect("Should evaluate ensemble");
+    assert!(vote.output == vec![0.0] || vote.output == vec![1.0]);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_ensemble_validation() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3050").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3050");
+    let request = |example: &str| TrainRequest {
+        example: Some(example.to_string()),
+        epochs: 10,
+        learning_rate: 0.5,
+        ..Default::default()
+    };
+    let xor = client.train(&request("xor")).await.unwrap();
+    let quadrant = client.train(&request("quadrant")).await.unwrap();
+
+    let http = reqwest::Client::new();
+    let post = |body: serde_json::Value| {
+        http.post("http://127.0.0.1:3050/api/eval/ensemble")
+            .json(&body)
+            .send()
+    };
+
+    // Unknown ids are listed in the error
+    let missing = post(json!({
+        "model_ids": [xor.model_id, "ghost-1", "ghost-2"],
+        "input": [1.0, 0.0]
+    }))
+    .await
+    .unwrap();
+    assert_eq!(missing.status(), 404);
+    let message = missing.text().await.unwrap();
+    assert!(message.contains("ghost-1") && message.contains("ghost-2"), "{}", message);
+    assert!(!message.contains(&xor.model_id));
+
+    for body in [
+        json!({"model_ids": [], "input": [1.0, 0.0]}),
+        json!({"model_ids": [xor.model_id, quadrant.model_id], "input": [1.0, 0.0]}),
+        json!({"model_ids": [xor.model_id], "input": [1.0]}),
+    ] {
+        let response = post(body.clone()).await.unwrap();
+        assert_eq!(response.status(), 400, "{} should be rejected", body);
+    }
+
+    handle.abort();
+}
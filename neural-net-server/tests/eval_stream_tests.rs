@@ -0,0 +1,80 @@
+// Integration tests for streaming a model's outputs over many inputs
+use neural_net_server::api::{EvalStreamOutput, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3081";
+
+#[tokio::test]
+async fn test_eval_stream_sends_one_result_per_input() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3081").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+    let http = reqwest::Client::new();
+
+    let request = TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: 10,
+        learning_rate: 0.5,
+        seed: Some(1),
+        ..Default::default()
+    };
+    let model = client.train(&request).await.unwrap();
+    let inputs: Vec<Vec<f64>> = (0..100).map(|i| vec![(i % 2) as f64, (i / 50) as f64]).collect();
+
+    let mut response = http
+        .post(format!("{}/api/models/{}/eval_stream", BASE, model.model_id))
+        .json(&json!({ "inputs": inputs }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().contains("text/event-stream"));
+
+    let mut body = String::new();
+    while let Some(chunk) = response.chunk().await.unwrap() {
+        body.push_str(&String::from_utf8_lossy(&chunk));
+    }
+
+    // Each `event:` line is followed by its `data:` line
+    let lines: Vec<&str> = body.lines().collect();
+    let mut outputs = Vec::new();
+    let mut complete = None;
+    for pair in lines.windows(2) {
+        match (pair[0], pair[1].strip_prefix("data: ")) {
+            ("event: output", Some(data)) => {
+                outputs.push(serde_json::from_str::<EvalStreamOutput>(data).unwrap())
+            }
+            ("event: complete", Some(data)) => {
+                complete = Some(serde_json::from_str::<serde_json::Value>(data).unwrap())
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(outputs.len(), 100);
+    assert_eq!(complete, Some(json!({ "count": 100 })));
+    for (i, result) in outputs.iter().enumerate() {
+        assert_eq!(result.index, i);
+        assert_eq!(result.output, client.eval(&model.model_id, inputs[i].clone()).await.unwrap().output);
+    }
+
+    // Inputs are checked before anything is streamed
+    let response = http
+        .post(format!("{}/api/models/{}/eval_stream", BASE, model.model_id))
+        .json(&json!({ "inputs": [[0.0, 1.0], [1.0]] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let response = http
+        .post(format!("{}/api/models/missing/eval_stream", BASE))
+        .json(&json!({ "inputs": [[0.0, 1.0]] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.abort();
+}
@@ -0,0 +1,91 @@
+// Integration tests for scoring stored models on examples
+use neural_net_server::api::{ErrorResponse, EvaluateRequest, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3073";
+
+fn request(example: &str, epochs: u32) -> TrainRequest {
+    TrainRequest {
+        example: Some(example.to_string()),
+        epochs,
+        learning_rate: 0.5,
+        seed: Some(1),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_evaluate_scores_stored_models() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3073").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+
+    // A trained AND model gets every sample right on its own example
+    let and = client.train(&request("and", 5000)).await.unwrap();
+    let report = client.evaluate_model(&and.model_id, &EvaluateRequest::default()).await.unwrap();
+    assert_eq!(report.example, "and");
+    assert_eq!(report.accuracy, 1.0);
+    assert_eq!(report.samples.len(), 4);
+    assert!(report.samples.iter().all(|sample| sample.correct));
+    assert!(report.loss < 0.05, "loss {}", report.loss);
+    assert!(report.confusion_matrix.is_none(), "Single-output models have no confusion matrix");
+
+    // An undertrained XOR model misses some
+    let xor = client.train(&request("xor", 1)).await.unwrap();
+    let report = client.evaluate_model(&xor.model_id, &EvaluateRequest::default()).await.unwrap();
+    assert!(report.accuracy < 1.0, "accuracy {}", report.accuracy);
+    assert!(report.samples.iter().any(|sample| !sample.correct));
+
+    // The example can be overridden, here with another two-input gate
+    let report = client
+        .evaluate_model(
+            &and.model_id,
+            &EvaluateRequest {
+                example: Some("or".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.example, "or");
+    assert!(report.accuracy < 1.0, "AND is not OR");
+
+    // Multi-class models also get a confusion matrix
+    let quadrant = client.train(&request("quadrant", 10)).await.unwrap();
+    let report = client.evaluate_model(&quadrant.model_id, &EvaluateRequest::default()).await.unwrap();
+    let matrix = report.confusion_matrix.expect("Multi-output models have a confusion matrix");
+    assert_eq!(matrix.len(), 4);
+    assert_eq!(matrix.iter().flatten().sum::<usize>(), report.samples.len());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_evaluate_rejects_mismatched_examples() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3074").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new("http://127.0.0.1:3074");
+    let http = reqwest::Client::new();
+    let and = client.train(&request("and", 10)).await.unwrap();
+    let url = format!("http://127.0.0.1:3074/api/models/{}/evaluate", and.model_id);
+
+    // Iris has four inputs and three outputs
+    let response = http.post(&url).json(&serde_json::json!({"example": "iris"})).send().await.unwrap();
+    assert_eq!(response.status(), 422);
+    let error: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(error.code, "dimension_mismatch");
+
+    let response = http.post(&url).json(&serde_json::json!({"example": "nand"})).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = http
+        .post("http://127.0.0.1:3074/api/models/missing/evaluate")
+        .json(&serde_json::json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.abort();
+}
@@ -0,0 +1,95 @@
+// Integration tests for evicting models by TTL and LRU cap
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn xor_request() -> TrainRequest {
+    TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: 10,
+        learning_rate: 0.5,
+        ..Default::default()
+    }
+}
+
+/// Status and error code of a model lookup
+async fn lookup(base: &str, model_id: &str) -> (u16, serde_json::Value) {
+    let response = reqwest::get(format!("{}/api/models/{}", base, model_id))
+        .await
+        .unwrap();
+    (response.status().as_u16(), response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_least_recently_used_model_is_evicted_over_cap() {
+    let model_dir = tempfile::TempDir::new().unwrap();
+    let config = ServerConfig {
+        addr: "127.0.0.1:3053".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        max_models: Some(2),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3053";
+    let client = NeuralNetClient::new(base);
+    let first = client.train(&xor_request()).await.expect("Should train");
+    let second = client.train(&xor_request()).await.expect("Should train");
+    let third = client.train(&xor_request()).await.expect("Should train");
+
+    let (status, body) = lookup(base, &first.model_id).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["code"], "model_evicted");
+    assert!(!model_dir.path().join(format!("{}.json", first.model_id)).exists());
+    assert!(!model_dir.path().join(format!("{}.meta.json", first.model_id)).exists());
+
+    for kept in [&second, &third] {
+        client
+            .model_info(&kept.model_id)
+            .await
+            .expect("Recent models should remain");
+        assert!(model_dir.path().join(format!("{}.json", kept.model_id)).exists());
+    }
+
+    // Using the older model makes the newer one the eviction candidate
+    client.eval(&second.model_id, vec![1.0, 0.0]).await.unwrap();
+    client.model_info(&second.model_id).await.unwrap();
+    let fourth = client.train(&xor_request()).await.expect("Should train");
+    assert_eq!(lookup(base, &third.model_id).await.0, 404);
+    client.model_info(&second.model_id).await.expect("Recently used model should remain");
+    client.model_info(&fourth.model_id).await.expect("Newest model should remain");
+
+    // Unknown IDs are still plain 404s
+    let (status, body) = lookup(base, "never-existed").await;
+    assert_eq!(status, 404);
+    assert_eq!(body["code"], "not_found");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_unused_models_expire_after_ttl() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3054".to_string(),
+        model_ttl_secs: Some(1),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3054";
+    let client = NeuralNetClient::new(base);
+    let trained = client.train(&xor_request()).await.expect("Should train");
+    client.model_info(&trained.model_id).await.expect("Fresh model should exist");
+
+    sleep(Duration::from_millis(3000)).await;
+
+    let (status, body) = lookup(base, &trained.model_id).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["code"], "model_evicted");
+
+    handle.abort();
+}
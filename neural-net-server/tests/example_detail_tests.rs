@@ -0,0 +1,45 @@
+// Integration tests for the example detail endpoint
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_example_detail_full_and_truncated() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3052".to_string(),
+        max_example_rows: 20,
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3052");
+
+    let xor = client.example_detail("xor").await.expect("Should get xor");
+    assert_eq!(xor.samples, 4);
+    assert!(!xor.truncated);
+    assert_eq!(xor.inputs.len(), 4);
+    assert_eq!(xor.targets.len(), 4);
+    assert_eq!(xor.inputs[1], vec![0.0, 1.0]);
+    assert_eq!(xor.targets[1], vec![1.0]);
+    assert_eq!(xor.architecture, vec![2, 3, 1]);
+    assert!(xor.recommended_epochs > 0);
+    assert!(xor.recommended_learning_rate > 0.0);
+    assert_eq!(xor.activation, "sigmoid");
+
+    let iris = client.example_detail("iris").await.expect("Should get iris");
+    assert!(iris.truncated);
+    assert!(iris.samples > 20);
+    assert_eq!(iris.inputs.len(), 20);
+    assert_eq!(iris.targets.len(), 20);
+    assert!(iris.inputs.iter().all(|row| row.len() == 4));
+    assert!(iris.targets.iter().all(|row| row.len() == 3));
+
+    let missing = reqwest::get("http://127.0.0.1:3052/api/examples/digits")
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    handle.abort();
+}
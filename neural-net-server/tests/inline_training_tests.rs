@@ -0,0 +1,115 @@
+// Integration tests for training on data sent inline with the request
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn nand_request() -> TrainRequest {
+    TrainRequest {
+        inputs: Some(vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]]),
+        targets: Some(vec![vec![1.0], vec![1.0], vec![1.0], vec![0.0]]),
+        architecture: Some(vec![2, 3, 1]),
+        epochs: 5000,
+        learning_rate: 0.5,
+        seed: Some(7),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_train_on_inline_nand_table() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3063").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3063";
+    let client = NeuralNetClient::new(base);
+    let request = nand_request();
+    let trained = client.train(&request).await.expect("Should train on inline data");
+
+    assert!(trained.example.starts_with("inline:"));
+    assert_eq!(trained.accuracy, 1.0);
+    let info = client.model_info(&trained.model_id).await.unwrap();
+    assert_eq!(info.example, trained.example);
+    assert_eq!(info.architecture, vec![2, 3, 1]);
+
+    // The same data gets the same fingerprint
+    let again = client.train(&request).await.unwrap();
+    assert_eq!(again.example, trained.example);
+
+    let inputs = request.inputs.unwrap();
+    let targets = request.targets.unwrap();
+    for (input, target) in inputs.into_iter().zip(targets) {
+        let output = client.eval(&trained.model_id, input.clone()).await.unwrap().output;
+        assert_eq!(output[0].round(), target[0], "NAND{:?} = {}", input, output[0]);
+    }
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_inline_data_is_validated() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3064").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let train = |body: serde_json::Value| {
+        let client = client.clone();
+        async move {
+            let response = client
+                .post("http://127.0.0.1:3064/api/train")
+                .json(&body)
+                .send()
+                .await
+                .unwrap();
+            let status = response.status().as_u16();
+            let body: serde_json::Value = response.json().await.unwrap();
+            (status, body)
+        }
+    };
+
+    // Inline data can't be combined with an example
+    let (status, _) = train(json!({
+        "example": "xor", "inputs": [[0.0, 1.0]], "targets": [[1.0]],
+        "architecture": [2, 2, 1], "epochs": 10, "learning_rate": 0.5
+    }))
+    .await;
+    assert_eq!(status, 400);
+
+    // An architecture is required
+    let (status, body) = train(json!({
+        "inputs": [[0.0, 1.0]], "targets": [[1.0]], "epochs": 10, "learning_rate": 0.5
+    }))
+    .await;
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("architecture"));
+
+    // Ragged rows name the offending row
+    let (status, body) = train(json!({
+        "inputs": [[0.0, 1.0], [1.0]], "targets": [[1.0], [0.0]],
+        "architecture": [2, 2, 1], "epochs": 10, "learning_rate": 0.5
+    }))
+    .await;
+    assert_eq!(status, 422);
+    assert_eq!(body["code"], "invalid_dataset");
+    assert!(body["error"].as_str().unwrap().contains("Row 2"));
+
+    // Too many samples
+    let (status, body) = train(json!({
+        "inputs": vec![[0.0, 1.0]; 257], "targets": vec![[1.0]; 257],
+        "architecture": [2, 2, 1], "epochs": 10, "learning_rate": 0.5
+    }))
+    .await;
+    assert_eq!(status, 422);
+    assert!(body["error"].as_str().unwrap().contains("256"));
+
+    // Too many features
+    let (status, _) = train(json!({
+        "inputs": [vec![0.0; 65]], "targets": [[1.0]],
+        "architecture": [65, 2, 1], "epochs": 10, "learning_rate": 0.5
+    }))
+    .await;
+    assert_eq!(status, 422);
+
+    handle.abort();
+}
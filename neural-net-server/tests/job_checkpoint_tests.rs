@@ -0,0 +1,85 @@
+// Integration tests for downloading checkpoints of running jobs
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use neural_network::network::Network;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3055";
+
+/// Long enough that the job is still running while the test inspects it
+const EPOCHS: u32 = 1_000_000;
+
+/// Start a streaming training run and return its job ID and open response
+async fn start_job(checkpoint_interval: Option<u32>) -> (u64, reqwest::Response) {
+    let request = TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: EPOCHS,
+        learning_rate: 0.5,
+        checkpoint_interval,
+        ..Default::default()
+    };
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/train/stream", BASE))
+        .json(&request)
+        .send()
+        .await
+        .expect("Should start training");
+    assert_eq!(response.status(), 200);
+
+    let job_id = response
+        .headers()
+        .get("x-job-id")
+        .expect("Stream response should name its job")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    (job_id, response)
+}
+
+#[tokio::test]
+async fn test_download_checkpoint_of_running_job() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3055").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new(BASE);
+
+    // A job without a checkpoint interval never has one to offer
+    let (plain_job, _plain_stream) = start_job(None).await;
+    let conflict = reqwest::get(format!("{}/api/jobs/{}/checkpoint", BASE, plain_job))
+        .await
+        .unwrap();
+    assert_eq!(conflict.status(), 409);
+    let body: serde_json::Value = conflict.json().await.unwrap();
+    assert_eq!(body["code"], "no_checkpoint");
+
+    // Poll until the first periodic checkpoint appears
+    let (job_id, _stream) = start_job(Some(100)).await;
+    let mut checkpoint = None;
+    for _ in 0..500 {
+        match client.job_checkpoint(job_id).await {
+            Ok(found) => {
+                checkpoint = Some(found);
+                break;
+            }
+            Err(_) => sleep(Duration::from_millis(10)).await,
+        }
+    }
+    let checkpoint = checkpoint.expect("Job should produce a checkpoint");
+
+    assert!(checkpoint.epoch >= 1 && checkpoint.epoch < EPOCHS);
+    assert_eq!(checkpoint.epoch % 100, 0);
+    let (network, metadata) = Network::load_checkpoint_from_slice(&checkpoint.bytes)
+        .expect("Checkpoint bytes should load");
+    assert_eq!(metadata.epoch, checkpoint.epoch);
+    assert_eq!(metadata.total_epochs, EPOCHS);
+    assert_eq!(network.layers, vec![2, 3, 1]);
+
+    let missing = reqwest::get(format!("{}/api/jobs/999999/checkpoint", BASE))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    handle.abort();
+}
@@ -0,0 +1,100 @@
+// Integration tests for queueing training jobs beyond the concurrency limit
+use neural_net_server::api::{JobState, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3066";
+
+/// XOR on a wide network, so each epoch takes long enough for jobs to overlap
+fn xor_request(epochs: u32) -> TrainRequest {
+    TrainRequest {
+        example: Some("xor".to_string()),
+        architecture: Some(vec![2, 64, 64, 1]),
+        epochs,
+        learning_rate: 0.5,
+        ..Default::default()
+    }
+}
+
+/// Start a streaming training job, returning its ID and a task that reads
+/// the stream to the end and yields the body and the time it finished
+async fn start_stream(epochs: u32) -> (u64, tokio::task::JoinHandle<(String, Instant)>) {
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/train/stream", BASE))
+        .json(&xor_request(epochs))
+        .send()
+        .await
+        .expect("Should start streaming");
+    assert_eq!(response.status(), 200);
+    let job_id = response.headers()["x-job-id"].to_str().unwrap().parse().unwrap();
+    let reader = tokio::spawn(async move {
+        let body = response.text().await.unwrap();
+        (body, Instant::now())
+    });
+    (job_id, reader)
+}
+
+#[tokio::test]
+async fn test_jobs_queue_beyond_concurrency_limit() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3066".to_string(),
+        max_concurrent_trainings: Some(1),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+
+    // A long job takes the only slot
+    let first = tokio::spawn(async {
+        let trained = NeuralNetClient::new(BASE).train(&xor_request(2_000)).await;
+        (trained, Instant::now())
+    });
+    sleep(Duration::from_millis(200)).await;
+
+    // Later jobs wait in line instead of being rejected. They train long
+    // enough that finishing times can't be confused by when each response
+    // happens to be read.
+    let (second_id, second) = start_stream(200).await;
+    let (third_id, third) = start_stream(200).await;
+    let (fourth_id, fourth) = start_stream(200).await;
+
+    let status = client.job_status(second_id).await.unwrap();
+    assert_eq!((status.status, status.position), (JobState::Queued, Some(1)));
+    let status = client.job_status(third_id).await.unwrap();
+    assert_eq!((status.status, status.position), (JobState::Queued, Some(2)));
+
+    // Job IDs are sequential, so the running job is the one before
+    let first_id = second_id - 1;
+    assert_eq!(client.job_status(first_id).await.unwrap().status, JobState::Running);
+    let error = client.cancel_job(first_id).await.unwrap_err();
+    assert!(error.to_string().contains("job_running"), "{}", error);
+
+    // A queued job can be cancelled, and its stream ends with an error
+    let cancelled = client.cancel_job(fourth_id).await.unwrap();
+    assert_eq!(cancelled.status, JobState::Cancelled);
+    let (body, _) = fourth.await.unwrap();
+    assert!(body.contains("event: queued"));
+    assert!(body.contains("job_cancelled"));
+    assert!(!body.contains("event: progress"));
+
+    // All jobs complete, in the order they were submitted
+    let (trained, first_done) = first.await.unwrap();
+    trained.expect("First job should complete");
+    let (second_body, second_done) = second.await.unwrap();
+    let (third_body, third_done) = third.await.unwrap();
+    for body in [&second_body, &third_body] {
+        assert!(body.starts_with("event: queued"), "Queued jobs should say so first");
+        assert!(body.contains("event: complete"));
+    }
+    assert!(second_body.contains("\"position\":1"));
+    assert!(third_body.contains("\"position\":2"));
+    assert!(first_done <= second_done && second_done <= third_done);
+
+    // Finished jobs are gone
+    assert!(client.job_status(second_id).await.is_err());
+
+    handle.abort();
+}
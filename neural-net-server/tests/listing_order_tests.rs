@@ -0,0 +1,41 @@
+// Integration tests for the ordering of listing endpoints
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use neural_network::examples;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3082";
+
+#[tokio::test]
+async fn test_listings_have_a_stable_order() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3082").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+
+    let mut trained = Vec::new();
+    for example in ["and", "or", "xor", "and", "parity3"] {
+        let request = TrainRequest {
+            example: Some(example.to_string()),
+            epochs: 1,
+            learning_rate: 0.5,
+            ..Default::default()
+        };
+        trained.push(client.train(&request).await.unwrap().model_id);
+    }
+
+    // Models are stored in a hash map but listed oldest first
+    let ids = |models: Vec<neural_net_server::api::ModelInfoResponse>| -> Vec<String> {
+        models.into_iter().map(|model| model.model_id).collect()
+    };
+    let first = ids(client.list_models(None).await.unwrap());
+    let second = ids(client.list_models(None).await.unwrap());
+    assert_eq!(first, trained);
+    assert_eq!(first, second);
+
+    // Examples keep the library's fixed order
+    let names: Vec<String> = client.list_examples().await.unwrap().into_iter().map(|e| e.name).collect();
+    assert_eq!(names, examples::list_examples());
+
+    handle.abort();
+}
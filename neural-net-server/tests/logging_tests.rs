@@ -0,0 +1,43 @@
+// Integration tests for request IDs
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_request_ids_are_generated_and_propagated() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3059").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    // Generated when the client doesn't send one, and unique per request
+    let first = client.get("http://127.0.0.1:3059/health").send().await.unwrap();
+    let second = client.get("http://127.0.0.1:3059/health").send().await.unwrap();
+    let first_id = first.headers().get("x-request-id").expect("Response should carry an ID");
+    let second_id = second.headers().get("x-request-id").unwrap();
+    assert!(!first_id.is_empty());
+    assert_ne!(first_id, second_id);
+
+    // A client-provided ID is echoed back
+    let response = client
+        .get("http://127.0.0.1:3059/health")
+        .header("x-request-id", "trace-abc-123")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["x-request-id"], "trace-abc-123");
+
+    // Error bodies name the request too
+    let response = client
+        .get("http://127.0.0.1:3059/api/models/does-not-exist")
+        .header("x-request-id", "trace-def-456")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers()["x-request-id"], "trace-def-456");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["request_id"], "trace-def-456");
+    assert_eq!(body["code"], "not_found");
+
+    handle.abort();
+}
@@ -0,0 +1,78 @@
+// Integration tests for the training history of stored models
+use neural_net_server::api::{ErrorResponse, HistoryPoint, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3075";
+
+fn request() -> TrainRequest {
+    TrainRequest {
+        example: Some("and".to_string()),
+        epochs: 500,
+        learning_rate: 0.5,
+        seed: Some(2),
+        ..Default::default()
+    }
+}
+
+/// Check a downsampled 500-epoch history and that its loss goes down
+fn check_history(history: &[HistoryPoint]) {
+    assert_eq!(history.len(), 500 / 10);
+    let epochs: Vec<u32> = history.iter().map(|point| point.epoch).collect();
+    assert_eq!(epochs, (1..=50).map(|i| i * 10).collect::<Vec<_>>());
+
+    for pair in history.windows(2) {
+        assert!(pair[1].loss <= pair[0].loss + 1e-3, "loss rose: {:?}", pair);
+    }
+    assert!(history[49].loss < history[0].loss / 2.0, "{:?}", history);
+}
+
+#[tokio::test]
+async fn test_history_of_trained_models() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3075").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+
+    // Models trained through a job
+    let trained = client.train(&request()).await.unwrap();
+    check_history(&client.model_history(&trained.model_id, 10).await.unwrap());
+
+    // The full history has every epoch, and uneven strides keep the last one
+    let full = client.model_history(&trained.model_id, 1).await.unwrap();
+    assert_eq!(full.len(), 500);
+    assert_eq!(full[499].loss, trained.final_loss);
+    let uneven = client.model_history(&trained.model_id, 150).await.unwrap();
+    let epochs: Vec<u32> = uneven.iter().map(|point| point.epoch).collect();
+    assert_eq!(epochs, [150, 300, 450, 500]);
+
+    // Models trained over the SSE stream
+    let body = reqwest::Client::new()
+        .post(format!("{}/api/train/stream", BASE))
+        .json(&request())
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let complete = body
+        .split("event: complete\n")
+        .nth(1)
+        .and_then(|rest| rest.lines().next())
+        .and_then(|line| line.strip_prefix("data: "))
+        .expect("Stream should complete");
+    let complete: serde_json::Value = serde_json::from_str(complete).unwrap();
+    let model_id = complete["model_id"].as_str().unwrap();
+    check_history(&client.model_history(model_id, 10).await.unwrap());
+
+    // A zero stride is rejected
+    let response = reqwest::get(format!("{}/api/models/{}/history?stride=0", BASE, model_id))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let error: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(error.code, "bad_request");
+
+    handle.abort();
+}
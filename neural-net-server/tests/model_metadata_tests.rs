@@ -0,0 +1,217 @@
+// Integration tests for model names, tags, notes and learning rates
+use neural_net_server::api::{TrainRequest, UpdateModelRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use neural_network::network::Network;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn xor_request() -> TrainRequest {
+    TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: 100,
+        learning_rate: 0.5,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_patch_filter_and_persist_across_restart() {
+    let model_dir = tempfile::TempDir::new().unwrap();
+    let config = ServerConfig {
+        addr: "127.0.0.1:3044".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let handle = tokio::spawn(async move {
+        neural_net_server::run_server_with_shutdown(config, async {
+            let _ = shutdown_rx.await;
+        })
+        .await
+    });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3044");
+    let tagged = client.train(&xor_request()).await.expect("Should train");
+    let untagged = client.train(&xor_request()).await.expect("Should train");
+
+    let updated = client
+        .update_model(
+            &tagged.model_id,
+            &UpdateModelRequest {
+                name: Some("xor baseline".to_string()),
+                tags: Some(vec!["demo".to_string()]),
+                notes: Some("First try".to_string()),
+                learning_rate: None,
+            },
+        )
+        .await
+        .expect("Should update model");
+    assert_eq!(updated.name.as_deref(), Some("xor baseline"));
+    assert_eq!(updated.tags, vec!["demo"]);
+
+    // Omitted fields are left alone
+    let renamed = client
+        .update_model(
+            &tagged.model_id,
+            &UpdateModelRequest {
+                name: Some("xor v2".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should rename model");
+    assert_eq!(renamed.name.as_deref(), Some("xor v2"));
+    assert_eq!(renamed.tags, vec!["demo"]);
+    assert_eq!(renamed.notes.as_deref(), Some("First try"));
+
+    let all = client.list_models(None).await.expect("Should list models");
+    assert_eq!(all.len(), 2);
+
+    let demos = client.list_models(Some("demo")).await.expect("Should list models");
+    assert_eq!(demos.len(), 1);
+    assert_eq!(demos[0].model_id, tagged.model_id);
+    let created_at = client.model_info(&tagged.model_id).await.unwrap().created_at;
+
+    shutdown_tx.send(()).unwrap();
+    handle.await.unwrap().expect("Server should shut down cleanly");
+
+    // Metadata files written before creation times were recorded lack one
+    let legacy_path = model_dir.path().join(format!("{}.meta.json", untagged.model_id));
+    let mut legacy: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&legacy_path).unwrap()).unwrap();
+    legacy.as_object_mut().unwrap().remove("created_at");
+    std::fs::write(&legacy_path, legacy.to_string()).unwrap();
+
+    // A new server over the same directory restores models and metadata
+    let config = ServerConfig {
+        addr: "127.0.0.1:3045".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3045");
+    let restored = client
+        .model_info(&tagged.model_id)
+        .await
+        .expect("Model should survive a restart");
+    assert_eq!(restored.name.as_deref(), Some("xor v2"));
+    assert_eq!(restored.tags, vec!["demo"]);
+    assert_eq!(restored.notes.as_deref(), Some("First try"));
+    assert_eq!(restored.final_loss, tagged.final_loss);
+    assert_eq!(restored.created_at, created_at);
+
+    let plain = client.model_info(&untagged.model_id).await.unwrap();
+    assert!(plain.name.is_none());
+    assert!(plain.tags.is_empty());
+    // and fall back to the file's modification time
+    assert!(chrono::DateTime::parse_from_rfc3339(&plain.created_at).is_ok(), "{}", plain.created_at);
+
+    client
+        .eval(&tagged.model_id, vec![1.0, 0.0])
+        .await
+        .expect("Restored model should evaluate");
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_patch_validation() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3046").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3046");
+    let trained = client.train(&xor_request()).await.expect("Should train");
+    let url = format!("http://127.0.0.1:3046/api/models/{}", trained.model_id);
+    let http = reqwest::Client::new();
+
+    for body in [
+        json!({"name": "x".repeat(101)}),
+        json!({"name": "   "}),
+        json!({"tags": [""]}),
+        json!({"learning_rate": 0.0}),
+        json!({"learning_rate": -0.1}),
+    ] {
+        let response = http.patch(&url).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 400, "{} should be rejected", body);
+    }
+
+    let missing = http
+        .patch("http://127.0.0.1:3046/api/models/does-not-exist")
+        .json(&json!({"name": "ghost"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    // Rejected updates change nothing
+    let info = client.model_info(&trained.model_id).await.unwrap();
+    assert!(info.name.is_none());
+    assert!(info.tags.is_empty());
+    assert_eq!(info.learning_rate, 0.5);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_patch_learning_rate() {
+    let model_dir = tempfile::TempDir::new().unwrap();
+    let config = ServerConfig {
+        addr: "127.0.0.1:3080".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3080");
+    let trained = client.train(&xor_request()).await.expect("Should train");
+
+    let updated = client
+        .update_model(
+            &trained.model_id,
+            &UpdateModelRequest {
+                learning_rate: Some(0.05),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Should update learning rate");
+    assert_eq!(updated.learning_rate, 0.05);
+
+    let info = client.model_info(&trained.model_id).await.unwrap();
+    assert_eq!(info.learning_rate, 0.05);
+
+    // The saved checkpoint carries the new rate into further training
+    let checkpoint = model_dir.path().join(format!("{}.json", trained.model_id));
+    let (network, metadata) = Network::load_checkpoint(&checkpoint).unwrap();
+    assert_eq!(network.learning_rate, 0.05);
+    assert_eq!(metadata.learning_rate, 0.05);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_trained_models_record_creation_time() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3083").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3083");
+    let before = chrono::Utc::now();
+    let trained = client.train(&xor_request()).await.expect("Should train");
+
+    let info = client.model_info(&trained.model_id).await.unwrap();
+    let created_at = chrono::DateTime::parse_from_rfc3339(&info.created_at)
+        .unwrap_or_else(|e| panic!("{} is not RFC 3339: {}", info.created_at, e));
+    assert!(created_at >= before && created_at <= chrono::Utc::now());
+
+    let listed = client.list_models(None).await.unwrap();
+    assert_eq!(listed[0].created_at, info.created_at);
+
+    handle.abort();
+}
@@ -0,0 +1,44 @@
+// Integration tests for model parameter statistics
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_stats_for_each_layer_of_a_trained_model() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3065").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3065");
+    let trained = client
+        .train(&TrainRequest {
+            example: Some("xor".to_string()),
+            architecture: Some(vec![2, 4, 3, 1]),
+            epochs: 500,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .expect("Should train");
+
+    let stats = client.model_stats(&trained.model_id).await.expect("Should get stats");
+    assert_eq!(stats.model_id, trained.model_id);
+    assert_eq!(stats.layers.len(), 3);
+    for (i, (layer, (rows, cols))) in stats.layers.iter().zip([(4, 2), (3, 4), (1, 3)]).enumerate() {
+        assert_eq!(layer.layer, i);
+        assert_eq!((layer.rows, layer.cols), (rows, cols));
+        for value in [layer.mean, layer.std, layer.min, layer.max, layer.norm] {
+            assert!(value.is_finite(), "Layer {} has a non-finite statistic", i);
+        }
+        assert!(layer.min <= layer.mean && layer.mean <= layer.max);
+        assert!(layer.std >= 0.0 && layer.norm > 0.0);
+    }
+
+    let squares: f64 = stats.layers.iter().map(|l| l.norm * l.norm).sum();
+    assert!((stats.total_norm - squares.sqrt()).abs() < 1e-9);
+
+    let error = client.model_stats("missing").await.unwrap_err();
+    assert!(error.to_string().contains("not_found") || error.to_string().contains("not found"));
+
+    handle.abort();
+}
@@ -0,0 +1,47 @@
+// Integration tests for fetching a model's raw weights
+use neural_net_server::api::TrainRequest;
+use neural_net_server::client::NeuralNetClient;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_weights_of_a_trained_model() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3085").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = NeuralNetClient::new("http://127.0.0.1:3085");
+    let layers = vec![2, 4, 3, 1];
+    let trained = client
+        .train(&TrainRequest {
+            example: Some("xor".to_string()),
+            architecture: Some(layers.clone()),
+            epochs: 200,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .expect("Should train");
+
+    let weights = client.model_weights(&trained.model_id).await.expect("Should get weights");
+    assert_eq!(weights.model_id, trained.model_id);
+    assert_eq!(weights.weights.len(), layers.len() - 1);
+    assert_eq!(weights.biases.len(), layers.len() - 1);
+    for (i, (w, b)) in weights.weights.iter().zip(&weights.biases).enumerate() {
+        assert_eq!((w.rows, w.cols), (layers[i + 1], layers[i]));
+        assert_eq!((b.rows, b.cols), (layers[i + 1], 1));
+        assert_eq!(w.data.len(), w.rows * w.cols);
+        assert_eq!(b.data.len(), b.rows);
+    }
+
+    // The stats endpoint sees the same parameters
+    let stats = client.model_stats(&trained.model_id).await.unwrap();
+    for (w, layer) in weights.weights.iter().zip(&stats.layers) {
+        let norm = w.data.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!((norm - layer.norm).abs() < 1e-9);
+    }
+
+    let error = client.model_weights("missing").await.unwrap_err();
+    assert!(error.to_string().contains("not_found") || error.to_string().contains("not found"));
+
+    handle.abort();
+}
@@ -0,0 +1,108 @@
+// Integration tests for MessagePack requests and responses on eval endpoints
+use neural_net_server::api::{
+    EnsembleMethod, EnsembleRequest, EnsembleResponse, ErrorResponse, EvalRequest, EvalResponse,
+    TrainRequest,
+};
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::negotiate::MSGPACK_CONTENT_TYPE;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3070";
+
+#[tokio::test]
+async fn test_eval_speaks_msgpack() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3070").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let trained = NeuralNetClient::new(BASE)
+        .train(&TrainRequest {
+            example: Some("xor".to_string()),
+            epochs: 100,
+            learning_rate: 0.5,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let request = EvalRequest {
+        model_id: trained.model_id.clone(),
+        input: vec![1.0, 0.0],
+    };
+    let http = reqwest::Client::new();
+
+    // MessagePack in, MessagePack out
+    let response = http
+        .post(format!("{}/api/eval", BASE))
+        .header(CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+        .header(ACCEPT, MSGPACK_CONTENT_TYPE)
+        .body(rmp_serde::to_vec_named(&request).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[CONTENT_TYPE], MSGPACK_CONTENT_TYPE);
+    let msgpack = response.bytes().await.unwrap();
+    let decoded: EvalResponse = rmp_serde::from_slice(&msgpack).unwrap();
+
+    // JSON stays the default and gives the same answer in more bytes
+    let response = http.post(format!("{}/api/eval", BASE)).json(&request).send().await.unwrap();
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    let json = response.bytes().await.unwrap();
+    let expected: EvalResponse = serde_json::from_slice(&json).unwrap();
+    assert_eq!(decoded.output, expected.output);
+    assert!(msgpack.len() < json.len(), "{} vs {} bytes", msgpack.len(), json.len());
+
+    // Encodings can be mixed
+    let response = http
+        .post(format!("{}/api/eval", BASE))
+        .header(ACCEPT, MSGPACK_CONTENT_TYPE)
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+    let decoded: EvalResponse = rmp_serde::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(decoded.output, expected.output);
+
+    // Ensembles too
+    let ensemble = EnsembleRequest {
+        model_ids: vec![trained.model_id.clone(), trained.model_id],
+        input: vec![1.0, 0.0],
+        method: EnsembleMethod::Mean,
+    };
+    let response = http
+        .post(format!("{}/api/eval/ensemble", BASE))
+        .header(CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+        .header(ACCEPT, MSGPACK_CONTENT_TYPE)
+        .body(rmp_serde::to_vec_named(&ensemble).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let decoded: EnsembleResponse = rmp_serde::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(decoded.output, expected.output);
+
+    // Other body types are refused with a structured error
+    let response = http
+        .post(format!("{}/api/eval", BASE))
+        .header(CONTENT_TYPE, "text/csv")
+        .body("1,0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 415);
+    let error: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(error.code, "unsupported_media_type");
+
+    // Undecodable MessagePack is a bad request
+    let response = http
+        .post(format!("{}/api/eval", BASE))
+        .header(CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+        .body(vec![0xc1])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.abort();
+}
@@ -0,0 +1,156 @@
+// Tests that the OpenAPI spec matches the real request/response types
+use neural_net_server::api::*;
+use neural_net_server::openapi::ApiDoc;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::time::sleep;
+use utoipa::OpenApi;
+
+fn spec() -> serde_json::Value {
+    serde_json::to_value(ApiDoc::openapi()).unwrap()
+}
+
+/// Deserialize a schema's example into `T` and check it serializes back unchanged
+fn check_example<T: DeserializeOwned + Serialize>(spec: &serde_json::Value, name: &str) {
+    let example = spec["components"]["schemas"][name]["example"].clone();
+    assert!(!example.is_null(), "{} should have an example", name);
+
+    let value: T = serde_json::from_value(example.clone())
+        .unwrap_or_else(|e| panic!("{} example should deserialize: {}", name, e));
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        example,
+        "{} example should round-trip",
+        name
+    );
+
+    // The example uses only documented properties and covers the required ones
+    let schema = &spec["components"]["schemas"][name];
+    let properties = schema["properties"].as_object().unwrap();
+    for key in example.as_object().unwrap().keys() {
+        assert!(
+            properties.contains_key(key),
+            "{} example has undocumented property {}",
+            name,
+            key
+        );
+    }
+    for required in schema["required"].as_array().into_iter().flatten() {
+        let required = required.as_str().unwrap();
+        assert!(
+            example.get(required).is_some(),
+            "{} example is missing required property {}",
+            name,
+            required
+        );
+    }
+}
+
+#[test]
+fn test_schema_examples_match_rust_types() {
+    let spec = spec();
+
+    check_example::<HealthResponse>(&spec, "HealthResponse");
+    check_example::<ExampleInfo>(&spec, "ExampleInfo");
+    check_example::<ExampleDetail>(&spec, "ExampleDetail");
+    check_example::<TrainRequest>(&spec, "TrainRequest");
+    check_example::<TrainResponse>(&spec, "TrainResponse");
+    check_example::<ProgressEvent>(&spec, "ProgressEvent");
+    check_example::<CheckpointEvent>(&spec, "CheckpointEvent");
+    check_example::<JobStatusResponse>(&spec, "JobStatusResponse");
+    check_example::<JobNotification>(&spec, "JobNotification");
+    check_example::<EvalRequest>(&spec, "EvalRequest");
+    check_example::<EvalResponse>(&spec, "EvalResponse");
+    check_example::<EnsembleRequest>(&spec, "EnsembleRequest");
+    check_example::<EnsembleResponse>(&spec, "EnsembleResponse");
+    check_example::<ModelOutput>(&spec, "ModelOutput");
+    check_example::<ModelInfoResponse>(&spec, "ModelInfoResponse");
+    check_example::<UpdateModelRequest>(&spec, "UpdateModelRequest");
+    check_example::<ConfusionRequest>(&spec, "ConfusionRequest");
+    check_example::<ConfusionResponse>(&spec, "ConfusionResponse");
+    check_example::<EvaluateRequest>(&spec, "EvaluateRequest");
+    check_example::<EvaluateResponse>(&spec, "EvaluateResponse");
+    check_example::<EvaluatedSample>(&spec, "EvaluatedSample");
+    check_example::<ClassMetrics>(&spec, "ClassMetrics");
+    check_example::<ModelStatsResponse>(&spec, "ModelStatsResponse");
+    check_example::<LayerStats>(&spec, "LayerStats");
+    check_example::<PredictionCacheStats>(&spec, "PredictionCacheStats");
+    check_example::<MatrixData>(&spec, "MatrixData");
+    check_example::<ModelWeightsResponse>(&spec, "ModelWeightsResponse");
+    check_example::<HistoryPoint>(&spec, "HistoryPoint");
+    check_example::<CompareResponse>(&spec, "CompareResponse");
+    check_example::<ModelScore>(&spec, "ModelScore");
+    check_example::<SampleComparison>(&spec, "SampleComparison");
+    check_example::<PairwiseCompareRequest>(&spec, "PairwiseCompareRequest");
+    check_example::<PairwiseCompareResponse>(&spec, "PairwiseCompareResponse");
+    check_example::<DatasetInfo>(&spec, "DatasetInfo");
+    check_example::<ErrorResponse>(&spec, "ErrorResponse");
+}
+
+#[test]
+fn test_every_endpoint_is_documented() {
+    let spec = spec();
+    let paths = spec["paths"].as_object().unwrap();
+
+    for (path, method) in [
+        ("/health", "get"),
+        ("/api/examples", "get"),
+        ("/api/examples/{name}", "get"),
+        ("/api/train", "post"),
+        ("/api/train/stream", "post"),
+        ("/api/train/inline", "post"),
+        ("/api/eval", "post"),
+        ("/api/eval/ensemble", "post"),
+        ("/api/models", "get"),
+        ("/api/models/{id}", "get"),
+        ("/api/models/{id}", "patch"),
+        ("/api/models/{id}/confusion", "post"),
+        ("/api/models/{id}/evaluate", "post"),
+        ("/api/models/{id}/stats", "get"),
+        ("/api/models/{id}/weights", "get"),
+        ("/api/models/{id}/history", "get"),
+        ("/api/models/compare", "get"),
+        ("/api/compare", "post"),
+        ("/api/jobs/{id}", "get"),
+        ("/api/jobs/{id}", "delete"),
+        ("/api/jobs/{id}/checkpoint", "get"),
+        ("/api/datasets", "post"),
+        ("/api/datasets", "get"),
+    ] {
+        assert!(
+            paths.get(path).and_then(|p| p.get(method)).is_some(),
+            "{} {} should be documented",
+            method.to_uppercase(),
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_spec_and_docs_are_served() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3040").await });
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+
+    let served: serde_json::Value = client
+        .get("http://127.0.0.1:3040/api/openapi.json")
+        .send()
+        .await
+        .expect("Should get spec")
+        .json()
+        .await
+        .expect("Spec should be JSON");
+    assert_eq!(served, spec());
+
+    let docs = client
+        .get("http://127.0.0.1:3040/api/docs")
+        .send()
+        .await
+        .expect("Should get docs page");
+    assert_eq!(docs.status(), 200);
+    assert!(docs.text().await.unwrap().contains("/api/openapi.json"));
+
+    handle.abort();
+}
@@ -0,0 +1,79 @@
+// Integration tests for the per-model cache of eval results
+use neural_net_server::api::{PredictionCacheStats, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn request() -> TrainRequest {
+    TrainRequest {
+        example: Some("xor".to_string()),
+        epochs: 100,
+        learning_rate: 0.5,
+        seed: Some(1),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_repeated_evals_are_served_from_the_cache() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3077".to_string(),
+        prediction_cache_size: Some(2),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new("http://127.0.0.1:3077");
+
+    let model_id = client.train(&request()).await.unwrap().model_id;
+    let stats = client.model_stats(&model_id).await.unwrap();
+    assert_eq!(stats.prediction_cache, None, "The cache is created by the first eval");
+
+    let first = client.eval(&model_id, vec![1.0, 0.0]).await.unwrap();
+    let second = client.eval(&model_id, vec![1.0, 0.0]).await.unwrap();
+    assert_eq!(first.output, second.output);
+    let stats = client.model_stats(&model_id).await.unwrap();
+    assert_eq!(
+        stats.prediction_cache,
+        Some(PredictionCacheStats {
+            capacity: 2,
+            entries: 1,
+            hits: 1,
+            misses: 1,
+        })
+    );
+
+    // Rejected inputs are not cached, and the least recently used input is
+    // dropped once the cache is full
+    assert!(client.eval(&model_id, vec![1.0]).await.is_err());
+    client.eval(&model_id, vec![0.0, 1.0]).await.unwrap();
+    client.eval(&model_id, vec![1.0, 1.0]).await.unwrap();
+    client.eval(&model_id, vec![1.0, 0.0]).await.unwrap();
+    let cache = client.model_stats(&model_id).await.unwrap().prediction_cache.unwrap();
+    assert_eq!((cache.entries, cache.hits, cache.misses), (2, 1, 5));
+
+    // A retrained model starts with an empty cache
+    let retrained = client.train(&request()).await.unwrap().model_id;
+    client.eval(&retrained, vec![1.0, 0.0]).await.unwrap();
+    let cache = client.model_stats(&retrained).await.unwrap().prediction_cache.unwrap();
+    assert_eq!((cache.hits, cache.misses), (0, 1));
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_cache_is_off_by_default() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3078").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new("http://127.0.0.1:3078");
+
+    let model_id = client.train(&request()).await.unwrap().model_id;
+    client.eval(&model_id, vec![1.0, 0.0]).await.unwrap();
+    client.eval(&model_id, vec![1.0, 0.0]).await.unwrap();
+
+    let stats = client.model_stats(&model_id).await.unwrap();
+    assert_eq!(stats.prediction_cache, None);
+
+    handle.abort();
+}
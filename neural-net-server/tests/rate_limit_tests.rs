@@ -0,0 +1,80 @@
+// Integration tests for training endpoint rate limiting
+use neural_net_server::config::ServerConfig;
+use neural_net_server::rate_limit::RateLimitConfig;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn start_limited_server(port: u16) -> tokio::task::JoinHandle<Result<(), anyhow::Error>> {
+    let config = ServerConfig {
+        addr: format!("127.0.0.1:{}", port),
+        rate_limit: Some(RateLimitConfig {
+            requests_per_minute: 5,
+        }),
+        ..Default::default()
+    };
+    tokio::spawn(async move {
+        neural_net_server::run_server(config).await
+    })
+}
+
+#[tokio::test]
+async fn test_rapid_training_requests_are_limited() {
+    let handle = start_limited_server(3035).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let train_body = json!({
+        "example": "and",
+        "epochs": 10,
+        "learning_rate": 0.5
+    });
+
+    let mut statuses = Vec::new();
+    let mut model_id = None;
+    for _ in 0..10 {
+        let response = client
+            .post("http://127.0.0.1:3035/api/train")
+            .json(&train_body)
+            .send()
+            .await
+            .expect("Should get response");
+
+        if response.status() == 429 {
+            assert!(
+                response.headers().get("retry-after").is_some(),
+                "429 should include Retry-After"
+            );
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["code"], "rate_limited");
+            statuses.push(429);
+        } else {
+            statuses.push(response.status().as_u16());
+            let body: serde_json::Value = response.json().await.unwrap();
+            model_id = Some(body["model_id"].as_str().unwrap().to_string());
+        }
+    }
+
+    assert_eq!(statuses.iter().filter(|&&s| s == 200).count(), 5, "Burst of 5 should succeed");
+    assert_eq!(statuses.iter().filter(|&&s| s == 429).count(), 5, "Remaining requests should be limited");
+
+    // Health and eval are not rate limited
+    for _ in 0..10 {
+        let health = client
+            .get("http://127.0.0.1:3035/health")
+            .send()
+            .await
+            .expect("Should get response");
+        assert!(health.status().is_success());
+
+        let eval = client
+            .post("http://127.0.0.1:3035/api/eval")
+            .json(&json!({ "model_id": model_id.as_ref().unwrap(), "input": [1.0, 1.0] }))
+            .send()
+            .await
+            .expect("Should get response");
+        assert!(eval.status().is_success());
+    }
+
+    handle.abort();
+}
@@ -0,0 +1,58 @@
+// Integration tests for graceful shutdown
+use neural_net_server::config::ServerConfig;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_shutdown_stops_training_and_persists_partial_model() {
+    let model_dir = tempfile::TempDir::new().unwrap();
+    let config = ServerConfig {
+        addr: "127.0.0.1:3039".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        shutdown_timeout_secs: 5,
+        ..Default::default()
+    };
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let handle = tokio::spawn(async move {
+        neural_net_server::run_server_with_shutdown(config, async {
+            let _ = shutdown_rx.await;
+        })
+        .await
+    });
+    sleep(Duration::from_millis(100)).await;
+
+    // Start a training job far too long to finish on its own
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:3039/api/train/stream")
+        .json(&json!({
+            "example": "xor",
+            "epochs": 100_000_000,
+            "learning_rate": 0.5
+        }))
+        .send()
+        .await
+        .expect("Should start training");
+    assert_eq!(response.status(), 200);
+    sleep(Duration::from_millis(200)).await;
+
+    shutdown_tx.send(()).unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("Server should exit within the shutdown timeout")
+        .unwrap();
+    assert!(result.is_ok(), "Server should shut down cleanly");
+
+    let saved: Vec<_> = std::fs::read_dir(model_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| !path.to_string_lossy().ends_with(".meta.json"))
+        .collect();
+    assert_eq!(saved.len(), 1, "Partial model should be persisted");
+
+    let (network, metadata) = neural_network::network::Network::load_checkpoint(&saved[0]).unwrap();
+    assert_eq!(network.layers, vec![2, 3, 1]);
+    assert!(metadata.epoch < metadata.total_epochs, "Training should have stopped early");
+}
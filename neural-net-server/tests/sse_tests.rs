@@ -123,3 +123,198 @@ async fn test_sse_invalid_example() {
 
     handle.abort();
 }
+
+/// Count heartbeat events received on a training stream within a fixed window
+async fn count_heartbeats(port: u16, heartbeat_ms: u64, window: Duration) -> usize {
+    let client = reqwest::Client::new();
+
+    let request_body = json!({
+        "example": "iris",
+        "epochs": 1000,
+        "learning_rate": 0.3
+    });
+
+    let mut response = client
+        .post(format!(
+            "http://127.0.0.1:{}/api/train/stream?heartbeat_ms={}",
+            port, heartbeat_ms
+        ))
+        .json(&request_body)
+        .send()
+        .await
+        .expect("Should get response");
+
+    let mut body = String::new();
+    let _ = tokio::time::timeout(window, async {
+        while let Ok(Some(chunk)) = response.chunk().await {
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    })
+    .await;
+
+    body.matches("event: heartbeat").count()
+}
+
+#[tokio::test]
+async fn test_sse_heartbeat_interval_is_configurable() {
+    let handle = start_test_server(3024).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let window = Duration::from_millis(500);
+    let frequent = count_heartbeats(3024, 10, window).await;
+    let sparse = count_heartbeats(3024, 60_000, window).await;
+
+    assert_eq!(sparse, 0, "A long heartbeat interval should not emit heartbeats within the window");
+    assert!(
+        frequent > sparse,
+        "A short heartbeat interval should emit more heartbeats ({} vs {})",
+        frequent,
+        sparse
+    );
+
+    handle.abort();
+}
+
+/// Parse a raw SSE body into `(event type, JSON data)` pairs, skipping comments
+fn parse_events(body: &str) -> Vec<(String, serde_json::Value)> {
+    body.split("\n\n")
+        .filter_map(|block| {
+            let mut event = None;
+            let mut data = None;
+            for line in block.lines() {
+                if let Some(name) = line.strip_prefix("event: ") {
+                    event = Some(name.to_string());
+                } else if let Some(json) = line.strip_prefix("data: ") {
+                    data = Some(serde_json::from_str(json).expect("Event data should be JSON"));
+                }
+            }
+            Some((event?, data?))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sse_typed_events() {
+    let model_dir = tempfile::TempDir::new().unwrap();
+    let config = neural_net_server::config::ServerConfig {
+        addr: "127.0.0.1:3051".to_string(),
+        model_dir: Some(model_dir.path().to_path_buf()),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let body = reqwest::Client::new()
+        .post("http://127.0.0.1:3051/api/train/stream")
+        .json(&json!({
+            "example": "xor",
+            "epochs": 20,
+            "learning_rate": 0.5
+        }))
+        .send()
+        .await
+        .expect("Should get response")
+        .text()
+        .await
+        .expect("Stream should end after training");
+
+    let events = parse_events(&body);
+    let progress: Vec<_> = events.iter().filter(|(name, _)| name == "progress").collect();
+    assert_eq!(progress.len(), 20);
+
+    // Old clients reading only epoch and loss keep working
+    let (_, first) = progress[0];
+    assert_eq!(first["epoch"], 1);
+    assert!(first["loss"].is_f64());
+    assert!(first["accuracy"].is_f64());
+    assert_eq!(first["lr"], 0.5);
+    assert!(progress.iter().any(|(_, data)| data["eta_secs"].is_f64()));
+    assert_eq!(progress[19].1["eta_secs"], 0.0);
+
+    let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names[names.len() - 2..], ["checkpoint", "complete"]);
+
+    let (_, complete) = events.last().unwrap();
+    let model_id = complete["model_id"].as_str().unwrap();
+    assert_eq!(complete["epochs_run"], 20);
+    let (_, checkpoint) = &events[events.len() - 2];
+    assert_eq!(checkpoint["model_id"], model_id);
+    assert!(std::path::Path::new(checkpoint["path"].as_str().unwrap()).exists());
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_sse_progress_reports_loss_ema() {
+    let handle = start_test_server(3062).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = reqwest::Client::new();
+    let body = client
+        .post("http://127.0.0.1:3062/api/train/stream")
+        .json(&json!({
+            "example": "xor",
+            "epochs": 10,
+            "learning_rate": 0.5,
+            "loss_ema": 0.3
+        }))
+        .send()
+        .await
+        .expect("Should get response")
+        .text()
+        .await
+        .expect("Stream should end after training");
+
+    let events = parse_events(&body);
+    let progress: Vec<_> = events.iter().filter(|(name, _)| name == "progress").collect();
+    assert_eq!(progress.len(), 10);
+    assert_eq!(progress[0].1["loss_ema"], progress[0].1["loss"]);
+    assert!(progress.iter().all(|(_, data)| data["loss_ema"].is_f64()));
+
+    let response = client
+        .post("http://127.0.0.1:3062/api/train/stream")
+        .json(&json!({
+            "example": "xor",
+            "epochs": 10,
+            "learning_rate": 0.5,
+            "loss_ema": 1.5
+        }))
+        .send()
+        .await
+        .expect("Should get response");
+    assert_eq!(response.status(), 400);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_sse_keepalives_are_named_heartbeat_events() {
+    let handle = start_test_server(3084).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let body = reqwest::Client::new()
+        .post("http://127.0.0.1:3084/api/train/stream?heartbeat_ms=1")
+        .json(&json!({
+            "example": "iris",
+            "epochs": 200,
+            "learning_rate": 0.3
+        }))
+        .send()
+        .await
+        .expect("Should get response")
+        .text()
+        .await
+        .expect("Stream should end after training");
+
+    // Every event is named, and nothing is sent as a bare comment
+    assert!(!body.lines().any(|line| line.starts_with(':')), "{}", body);
+    let events = parse_events(&body);
+    let names: std::collections::HashSet<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    for name in ["progress", "heartbeat", "complete"] {
+        assert!(names.contains(name), "No {} event in {:?}", name, names);
+    }
+    assert!(events.iter().filter(|(name, _)| name == "heartbeat").all(|(_, data)| *data == json!({})));
+    assert_eq!(events.last().unwrap().0, "complete");
+
+    handle.abort();
+}
@@ -0,0 +1,59 @@
+// Integration tests for serving the web UI
+use neural_net_server::config::ServerConfig;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_embedded_files_are_served_without_static_dir() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3060".to_string(),
+        static_dir: "/nonexistent/static".into(),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3060";
+
+    let response = reqwest::get(format!("{}/", base)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+    let index = response.text().await.unwrap();
+    assert!(index.contains("<html"));
+
+    let response = reqwest::get(format!("{}/styles.css", base)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/css"));
+
+    // Client-side routes get the index page
+    let response = reqwest::get(format!("{}/models/some-model", base)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), index);
+
+    let response = reqwest::get(format!("{}/api/docs", base)).await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_static_dir_on_disk_takes_precedence() {
+    let static_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(static_dir.path().join("index.html"), "<html>local copy</html>").unwrap();
+    let config = ServerConfig {
+        addr: "127.0.0.1:3061".to_string(),
+        static_dir: static_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+
+    let base = "http://127.0.0.1:3061";
+    for path in ["/", "/models/some-model"] {
+        let response = reqwest::get(format!("{}{}", base, path)).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), "<html>local copy</html>");
+    }
+
+    handle.abort();
+}
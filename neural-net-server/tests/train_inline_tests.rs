@@ -0,0 +1,67 @@
+// Integration tests for training without storing the model
+use neural_net_server::api::{ErrorResponse, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_network::checkpoint::Checkpoint;
+use neural_network::network::Network;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const BASE: &str = "http://127.0.0.1:3076";
+
+#[tokio::test]
+async fn test_train_inline_returns_checkpoint_without_storing() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3076").await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new(BASE);
+
+    let request = TrainRequest {
+        example: Some("and".to_string()),
+        epochs: 2000,
+        learning_rate: 0.5,
+        seed: Some(4),
+        ..Default::default()
+    };
+    let body = reqwest::Client::new()
+        .post(format!("{}/api/train/inline", BASE))
+        .json(&request)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+
+    // The body is a complete checkpoint of a trained network
+    let checkpoint: Checkpoint = serde_json::from_str(&body).expect("Body should be a checkpoint");
+    assert_eq!(checkpoint.metadata.example, "and");
+    assert_eq!(checkpoint.metadata.epoch, 2000);
+    assert_eq!(checkpoint.metadata.total_epochs, 2000);
+    let network = Network::from_checkpoint(checkpoint).unwrap();
+    assert_eq!(network.layers, [2, 2, 1]);
+    for (input, target) in [([0.0, 0.0], 0.0), ([0.0, 1.0], 0.0), ([1.0, 0.0], 0.0), ([1.0, 1.0], 1.0)] {
+        let output = network.predict(&input).unwrap();
+        assert_eq!(output[0].round(), target, "{:?} -> {:?}", input, output);
+    }
+
+    // The typed client gets the same checkpoint, and nothing is stored
+    let checkpoint = client.train_inline(&request).await.unwrap();
+    assert_eq!(checkpoint.network.layers, network.layers);
+    assert!(client.list_models(None).await.unwrap().is_empty());
+
+    // Webhooks have no model to report
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/train/inline", BASE))
+        .json(&TrainRequest {
+            callback_url: Some("http://127.0.0.1:9/hook".to_string()),
+            ..request
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let error: ErrorResponse = response.json().await.unwrap();
+    assert_eq!(error.code, "bad_request");
+    assert!(error.error.contains("inline"), "{}", error.error);
+
+    handle.abort();
+}
@@ -0,0 +1,156 @@
+// Integration tests for job completion webhooks
+use axum::{Json, Router, extract::State, http::StatusCode, routing::post};
+use neural_net_server::api::{JobNotification, JobOutcome, TrainRequest};
+use neural_net_server::client::NeuralNetClient;
+use neural_net_server::config::ServerConfig;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, timeout};
+
+/// Webhook target that records every delivery attempt and fails the first
+/// `failures` of them with a 500
+#[derive(Clone)]
+struct Receiver {
+    attempts: Arc<AtomicUsize>,
+    failures: usize,
+    delivered: mpsc::UnboundedSender<JobNotification>,
+}
+
+async fn receive(
+    State(receiver): State<Receiver>,
+    Json(notification): Json<JobNotification>,
+) -> StatusCode {
+    if receiver.attempts.fetch_add(1, Ordering::SeqCst) < receiver.failures {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+    let _ = receiver.delivered.send(notification);
+    StatusCode::NO_CONTENT
+}
+
+/// Serve a webhook target on `addr`, returning its attempt counter and the
+/// notifications it accepted
+async fn start_receiver(
+    addr: &str,
+    failures: usize,
+) -> (Arc<AtomicUsize>, mpsc::UnboundedReceiver<JobNotification>) {
+    let (delivered, notifications) = mpsc::unbounded_channel();
+    let receiver = Receiver {
+        attempts: Arc::new(AtomicUsize::new(0)),
+        failures,
+        delivered,
+    };
+    let attempts = receiver.attempts.clone();
+    let app = Router::new().route("/done", post(receive)).with_state(receiver);
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (attempts, notifications)
+}
+
+fn and_request(callback_url: &str) -> TrainRequest {
+    TrainRequest {
+        example: Some("and".to_string()),
+        epochs: 200,
+        learning_rate: 0.5,
+        callback_url: Some(callback_url.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_reports_finished_jobs() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3067").await });
+    let (attempts, mut notifications) = start_receiver("127.0.0.1:3068", 1).await;
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new("http://127.0.0.1:3067");
+
+    let trained = client.train(&and_request("http://127.0.0.1:3068/done")).await.unwrap();
+
+    // The first delivery fails, so the notification arrives on a retry
+    let notification = timeout(Duration::from_secs(5), notifications.recv())
+        .await
+        .expect("Notification should arrive")
+        .unwrap();
+    assert_eq!(notification.status, JobOutcome::Completed);
+    assert_eq!(notification.model_id.as_deref(), Some(trained.model_id.as_str()));
+    assert_eq!(notification.final_loss, Some(trained.final_loss));
+    assert_eq!(notification.duration_ms, Some(trained.duration_ms));
+    assert!(notification.error.is_none());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    // Streaming jobs notify too
+    let response = reqwest::Client::new()
+        .post("http://127.0.0.1:3067/api/train/stream")
+        .json(&and_request("http://127.0.0.1:3068/done"))
+        .send()
+        .await
+        .unwrap();
+    let job_id: u64 = response.headers()["x-job-id"].to_str().unwrap().parse().unwrap();
+    assert!(response.text().await.unwrap().contains("event: complete"));
+    let notification = timeout(Duration::from_secs(5), notifications.recv())
+        .await
+        .expect("Notification should arrive")
+        .unwrap();
+    assert_eq!((notification.job_id, notification.status), (job_id, JobOutcome::Completed));
+
+    // Only http(s) callbacks are accepted
+    let error = client.train(&and_request("ftp://127.0.0.1/done")).await.unwrap_err();
+    assert!(error.to_string().contains("400"), "{}", error);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_webhook_fires_after_client_disconnects() {
+    let handle = tokio::spawn(async { neural_net_server::run_server("127.0.0.1:3086").await });
+    let (_, mut notifications) = start_receiver("127.0.0.1:3087", 0).await;
+    sleep(Duration::from_millis(100)).await;
+
+    // Give up on the response long before training finishes
+    let request = TrainRequest {
+        epochs: 20000,
+        ..and_request("http://127.0.0.1:3087/done")
+    };
+    let result = reqwest::Client::new()
+        .post("http://127.0.0.1:3086/api/train")
+        .json(&request)
+        .timeout(Duration::from_millis(50))
+        .send()
+        .await;
+    assert!(result.unwrap_err().is_timeout());
+
+    let notification = timeout(Duration::from_secs(60), notifications.recv())
+        .await
+        .expect("Notification should arrive")
+        .unwrap();
+    assert_eq!(notification.status, JobOutcome::Completed);
+    let model_id = notification.model_id.unwrap();
+    let client = NeuralNetClient::new("http://127.0.0.1:3086");
+    assert!(client.list_models(None).await.unwrap().iter().any(|m| m.model_id == model_id));
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn test_webhooks_can_be_disabled() {
+    let config = ServerConfig {
+        addr: "127.0.0.1:3069".to_string(),
+        webhooks: false,
+        ..Default::default()
+    };
+    let handle = tokio::spawn(async move { neural_net_server::run_server(config).await });
+    sleep(Duration::from_millis(100)).await;
+    let client = NeuralNetClient::new("http://127.0.0.1:3069");
+
+    let error = client.train(&and_request("http://127.0.0.1:3068/done")).await.unwrap_err();
+    assert!(error.to_string().contains("Webhooks are disabled"), "{}", error);
+
+    let request = TrainRequest {
+        callback_url: None,
+        ..and_request("")
+    };
+    assert!(client.train(&request).await.is_ok());
+
+    handle.abort();
+}
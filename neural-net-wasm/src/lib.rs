@@ -78,6 +78,7 @@ impl NeuralNetwork {
             checkpoint_path: None,
             verbose: false,
             example_name: Some(example_name.to_string()),
+            ..Default::default()
         };
 
         let mut controller = TrainingController::new(self.network.clone(), config);
@@ -136,6 +137,7 @@ impl NeuralNetwork {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         };
 
         let mut controller = TrainingController::new(self.network.clone(), config);
@@ -151,19 +153,9 @@ impl NeuralNetwork {
 
     /// Evaluate the network on a single input
     pub fn evaluate(&mut self, input: Vec<f64>) -> Result<Vec<f64>, JsValue> {
-        // Validate input dimensions
-        if input.len() != self.network.layers[0] {
-            return Err(JsValue::from_str(&format!(
-                "Invalid input dimensions: expected {}, got {}",
-                self.network.layers[0],
-                input.len()
-            )));
-        }
-
-        let input_matrix = neural_network::matrix::Matrix::from(input);
-        let output = self.network.feed_forward(input_matrix);
-
-        Ok(output.data)
+        self.network
+            .predict(&input)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Get the network architecture
@@ -239,6 +231,68 @@ pub fn get_example_info(name: &str) -> Result<JsValue, JsValue> {
         .map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
+/// Create a network with the given layer sizes, for training in the browser
+#[wasm_bindgen(js_name = newNetwork)]
+pub fn new_network(architecture: Vec<usize>, learning_rate: f64) -> Result<NeuralNetwork, JsValue> {
+    if architecture.len() < 2 || architecture.contains(&0) {
+        return Err(JsValue::from_str(&format!(
+            "Invalid architecture {:?}: need at least two non-empty layers",
+            architecture
+        )));
+    }
+    NeuralNetwork::new(architecture, learning_rate)
+}
+
+/// Train for `epochs` epochs on flattened rows of inputs and targets
+///
+/// Row widths come from the network's input and output layers. Returns the
+/// loss after the last epoch.
+#[wasm_bindgen(js_name = trainEpochs)]
+pub fn train_epochs(
+    network: &mut NeuralNetwork,
+    inputs: Vec<f64>,
+    targets: Vec<f64>,
+    epochs: u32,
+) -> Result<f64, JsValue> {
+    let layers = &network.network.layers;
+    let (input_size, target_size) = (layers[0], layers[layers.len() - 1]);
+    if !inputs.len().is_multiple_of(input_size)
+        || !targets.len().is_multiple_of(target_size)
+        || inputs.len() / input_size != targets.len() / target_size
+    {
+        return Err(JsValue::from_str(&format!(
+            "Expected rows of {} inputs and {} targets, got {} input and {} target values",
+            input_size,
+            target_size,
+            inputs.len(),
+            targets.len()
+        )));
+    }
+    let inputs = inputs.chunks_exact(input_size).map(<[f64]>::to_vec).collect();
+    let targets = targets.chunks_exact(target_size).map(<[f64]>::to_vec).collect();
+
+    let config = TrainingConfig {
+        epochs,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network.network.clone(), config);
+    let report = controller
+        .train(inputs, targets)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    network.network = controller.into_network();
+
+    Ok(report.final_loss)
+}
+
+/// Run the network on one input
+#[wasm_bindgen]
+pub fn predict(network: &NeuralNetwork, input: Vec<f64>) -> Result<Vec<f64>, JsValue> {
+    network
+        .network
+        .predict(&input)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +322,20 @@ mod tests {
         assert_eq!(output.len(), 1);
     }
 
+    #[test]
+    fn test_train_epochs_learns_and() {
+        let mut network = new_network(vec![2, 3, 1], 0.5).unwrap();
+        network.network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+        let inputs = vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0];
+        let targets = vec![0.0, 0.0, 0.0, 1.0];
+
+        let loss = train_epochs(&mut network, inputs, targets, 2000).unwrap();
+
+        assert!(loss < 0.05, "loss {}", loss);
+        assert!(predict(&network, vec![1.0, 1.0]).unwrap()[0] > 0.5);
+        assert!(predict(&network, vec![0.0, 1.0]).unwrap()[0] < 0.5);
+    }
+
     #[test]
     fn test_parameter_count() {
         let network = NeuralNetwork::new(vec![2, 3, 1], 0.5).unwrap();
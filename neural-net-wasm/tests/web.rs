@@ -0,0 +1,31 @@
+// Browser tests, run with `wasm-pack test --headless --firefox neural-net-wasm`
+#![cfg(target_arch = "wasm32")]
+
+use neural_net_wasm::{new_network, predict, train_epochs};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn test_trains_and_in_the_browser() {
+    let mut network = new_network(vec![2, 3, 1], 0.5).unwrap();
+    let inputs = vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0];
+    let targets = vec![0.0, 0.0, 0.0, 1.0];
+
+    let loss = train_epochs(&mut network, inputs.clone(), targets.clone(), 2000).unwrap();
+
+    assert!(loss < 0.05, "loss {}", loss);
+    for (input, target) in inputs.chunks(2).zip(targets) {
+        let output = predict(&network, input.to_vec()).unwrap();
+        assert_eq!(output[0].round(), target, "{:?} -> {:?}", input, output);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_rejects_mismatched_rows() {
+    let mut network = new_network(vec![2, 3, 1], 0.5).unwrap();
+
+    assert!(train_epochs(&mut network, vec![0.0, 1.0, 1.0], vec![1.0], 10).is_err());
+    assert!(predict(&network, vec![1.0]).is_err());
+    assert!(new_network(vec![2], 0.5).is_err());
+}
@@ -0,0 +1,72 @@
+// Allocation budget for the forward pass
+//
+// Counts heap allocations made by one `feed_forward` call through a counting
+// global allocator and fails if the count exceeds the budget. Each layer
+// currently allocates its product, its sum, its activation and a copy kept
+// for backpropagation, on top of recording the input; lower the budget as
+// in-place matrix work lands so the gains can't silently regress.
+// `feed_forward_buffered` reuses its buffers and must not allocate at all.
+use neural_network::activations::SIGMOID;
+use neural_network::matrix::Matrix;
+use neural_network::network::{ForwardBuffers, Network};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Most allocations one `feed_forward` call on a 64-32-10 network may make
+const MAX_FEED_FORWARD_ALLOCATIONS: usize = 11;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let mut network = Network::new_seeded(vec![64, 32, 10], SIGMOID, 0.5, 1);
+    let input = Matrix::new(64, 1, vec![0.5; 64]);
+    // Warm up so buffers kept between calls are already allocated
+    network.feed_forward(input.clone());
+
+    let input = std::hint::black_box(input);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let output = network.feed_forward(input);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    std::hint::black_box(output);
+
+    println!(
+        "feed_forward 64-32-10: {} allocations per call (budget {})",
+        allocations, MAX_FEED_FORWARD_ALLOCATIONS
+    );
+    assert!(
+        allocations <= MAX_FEED_FORWARD_ALLOCATIONS,
+        "feed_forward made {} allocations, more than the budget of {}",
+        allocations,
+        MAX_FEED_FORWARD_ALLOCATIONS
+    );
+
+    let mut buffers = ForwardBuffers::new(&network);
+    let input = std::hint::black_box(vec![0.5; 64]);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    std::hint::black_box(network.feed_forward_buffered(&input, &mut buffers));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    println!("feed_forward_buffered 64-32-10: {} allocations per call", allocations);
+    assert_eq!(allocations, 0, "feed_forward_buffered allocated {} times", allocations);
+}
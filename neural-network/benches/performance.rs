@@ -0,0 +1,94 @@
+// Criterion benchmarks giving performance work a baseline
+//
+// Each name ends with the rough time per iteration of a release build on a
+// laptop, so a regression (or an improvement) of an order of magnitude
+// stands out in the output without looking anything up.
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use neural_network::activations::SIGMOID;
+use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
+use neural_network::examples;
+use neural_network::matrix::Matrix;
+use neural_network::network::Network;
+use neural_network::training::{TrainingConfig, TrainingController};
+
+fn metadata() -> CheckpointMetadata {
+    CheckpointMetadata {
+        version: "1.0".to_string(),
+        example: "bench".to_string(),
+        epoch: 0,
+        total_epochs: 0,
+        learning_rate: 0.5,
+        timestamp: "2025-10-13T12:00:00Z".to_string(),
+        history: Vec::new(),
+    }
+}
+
+fn feed_forward(c: &mut Criterion) {
+    let mut group = c.benchmark_group("feed_forward");
+    for (name, layers) in [("2-3-1 (~500ns)", vec![2, 3, 1]), ("64-32-10 (~5us)", vec![64, 32, 10])] {
+        let mut network = Network::new_seeded(layers.clone(), SIGMOID, 0.5, 1);
+        let input = Matrix::new(layers[0], 1, vec![0.5; layers[0]]);
+        group.bench_function(name, |b| b.iter(|| network.feed_forward(black_box(input.clone()))));
+    }
+    group.finish();
+}
+
+fn training_epoch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("training_epoch");
+    // pattern3x3 is the largest image-like example
+    for (name, example) in [("xor (~10us)", "xor"), ("pattern3x3 (~100us)", "pattern3x3")] {
+        let example = examples::get_example(example).unwrap();
+        let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 1);
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || {
+                    let config = TrainingConfig {
+                        epochs: 1,
+                        ..Default::default()
+                    };
+                    let controller = TrainingController::new(network.clone(), config);
+                    (controller, example.inputs.clone(), example.targets.clone())
+                },
+                |(mut controller, inputs, targets)| controller.train(inputs, targets).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn checkpoint(c: &mut Criterion) {
+    let mut group = c.benchmark_group("checkpoint");
+    let network = Network::new_seeded(vec![64, 32, 10], SIGMOID, 0.5, 1);
+    for (name, format) in [("json (~150us)", CheckpointFormat::Json), ("bin (~20us)", CheckpointFormat::Bin)] {
+        let mut bytes = Vec::new();
+        network.write_checkpoint(&mut bytes, metadata(), format).unwrap();
+
+        group.bench_function(format!("serialize 64-32-10 {}", name), |b| {
+            b.iter(|| {
+                let mut buffer = Vec::with_capacity(bytes.len());
+                network.write_checkpoint(&mut buffer, metadata(), format).unwrap();
+                buffer
+            })
+        });
+        group.bench_function(format!("deserialize 64-32-10 {}", name), |b| {
+            b.iter(|| Network::load_checkpoint_from_slice(black_box(&bytes)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn matrix_multiply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_multiply");
+    for (size, magnitude) in [(16, "~10us"), (64, "~400us"), (128, "~3ms")] {
+        let a = Matrix::new(size, size, (0..size * size).map(|i| i as f64 / 100.0).collect());
+        let b = Matrix::new(size, size, (0..size * size).map(|i| 1.0 - i as f64 / 100.0).collect());
+        group.bench_function(format!("{0}x{0} ({1})", size, magnitude), |bencher| {
+            bencher.iter(|| black_box(&a).dot_multiply(black_box(&b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, feed_forward, training_epoch, checkpoint, matrix_multiply);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+// Criterion smoke benchmarks for the hot paths
+//
+// Registered with `test = true`, so `cargo test` runs every benchmark once
+// in Criterion's test mode and a broken bench fails CI; `cargo bench` times
+// them as usual. Covers `feed_forward` on each built-in example's
+// recommended architecture and matrix multiplication at several sizes.
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use neural_network::activations::SIGMOID;
+use neural_network::examples;
+use neural_network::matrix::Matrix;
+use neural_network::network::Network;
+
+fn feed_forward_examples(c: &mut Criterion) {
+    let mut group = c.benchmark_group("feed_forward_examples");
+    for name in examples::list_examples() {
+        let example = examples::get_example(name).unwrap();
+        let layers = example.recommended_arch;
+        let mut network = Network::new_seeded(layers.clone(), SIGMOID, 0.5, 1);
+        let input = Matrix::from(example.inputs[0].clone());
+
+        let output = network.feed_forward(input.clone());
+        assert_eq!(output.rows, layers[layers.len() - 1], "{} output size", name);
+
+        let architecture: Vec<String> = layers.iter().map(usize::to_string).collect();
+        group.bench_function(format!("{} {}", name, architecture.join("-")), |b| {
+            b.iter(|| network.feed_forward(black_box(input.clone())))
+        });
+    }
+    group.finish();
+}
+
+fn matrix_multiply_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_multiply_sizes");
+    for (rows, shared, cols) in [(4, 4, 1), (8, 8, 8), (32, 16, 1), (32, 32, 32)] {
+        let a = Matrix::new(rows, shared, (0..rows * shared).map(|i| i as f64 / 100.0).collect());
+        let b = Matrix::new(shared, cols, (0..shared * cols).map(|i| 1.0 - i as f64 / 100.0).collect());
+
+        let product = a.dot_multiply(&b);
+        assert_eq!((product.rows, product.cols), (rows, cols));
+
+        group.bench_function(format!("{}x{} * {}x{}", rows, shared, shared, cols), |bencher| {
+            bencher.iter(|| black_box(&a).dot_multiply(black_box(&b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, feed_forward_examples, matrix_multiply_sizes);
+criterion_main!(benches);
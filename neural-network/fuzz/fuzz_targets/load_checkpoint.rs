@@ -0,0 +1,12 @@
+// Arbitrary bytes must be rejected with an error, never a panic
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use neural_network::network::Network;
+
+fuzz_target!(|bytes: &[u8]| {
+    if let Ok((network, _)) = Network::load_checkpoint_from_slice(bytes) {
+        // Whatever loads must be safe to run
+        let _ = network.predict(&vec![0.5; network.layers[0]]);
+    }
+});
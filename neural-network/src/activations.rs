@@ -1,17 +1,124 @@
 use std::f64::consts::E;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use crate::matrix::Matrix;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Activation {
+    /// Identifier used in checkpoints and API requests
+    pub name: &'static str,
     pub function: fn(&f64) -> f64,
+    /// Derivative expressed in terms of the activation's output
     pub derivative: fn(&f64) -> f64,
 }
 
 pub const SIGMOID: Activation = Activation {
+    name: "sigmoid",
     function: |x| 1.0 / (1.0 + E.powf(-x)),
     derivative: |x| x * (1.0 - x),
 };
 
+pub const TANH: Activation = Activation {
+    name: "tanh",
+    function: |x| x.tanh(),
+    derivative: |x| 1.0 - x * x,
+};
+
+pub const RELU: Activation = Activation {
+    name: "relu",
+    function: |x| x.max(0.0),
+    derivative: |x| if *x > 0.0 { 1.0 } else { 0.0 },
+};
+
+/// Softmax, normalizing a whole layer into probabilities that sum to 1
+///
+/// Softmax depends on every value in the layer, so `function` and
+/// `derivative` alone don't describe it; use `apply` and `delta`, which
+/// handle it specially. It is meant for the output layer.
+pub const SOFTMAX: Activation = Activation {
+    name: "softmax",
+    function: |x| x.exp(),
+    derivative: |_| 1.0,
+};
+
+/// Every built-in activation
+pub const ACTIVATIONS: [Activation; 4] = [SIGMOID, TANH, RELU, SOFTMAX];
+
+impl Activation {
+    /// Look up a built-in activation by name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::activations::{Activation, TANH};
+    ///
+    /// assert_eq!(Activation::from_name("tanh"), Some(TANH));
+    /// assert_eq!(Activation::from_name("swish"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Activation> {
+        ACTIVATIONS.into_iter().find(|activation| activation.name == name)
+    }
+
+    fn is_softmax(&self) -> bool {
+        self.name == SOFTMAX.name
+    }
+
+    /// Apply the activation to a layer's weighted inputs
+    pub fn apply(&self, inputs: &Matrix) -> Matrix {
+        if !self.is_softmax() {
+            return inputs.map(self.function);
+        }
+
+        // Shift by the maximum so large inputs don't overflow
+        let max = inputs.data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = inputs.data.iter().map(|x| (x - max).exp()).collect();
+        let sum: f64 = exps.iter().sum();
+        Matrix::new(inputs.rows, inputs.cols, exps.into_iter().map(|e| e / sum).collect())
+    }
+
+    /// `apply` without allocating, overwriting `values` with the activations
+    pub fn apply_in_place(&self, values: &mut [f64]) {
+        if !self.is_softmax() {
+            for value in values.iter_mut() {
+                *value = (self.function)(value);
+            }
+            return;
+        }
+
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        for value in values.iter_mut() {
+            *value = (*value - max).exp();
+        }
+        let sum: f64 = values.iter().sum();
+        for value in values.iter_mut() {
+            *value /= sum;
+        }
+    }
+
+    /// Error signal at a layer's weighted inputs, given the layer's `outputs`
+    /// and the error signal at those outputs
+    pub fn delta(&self, outputs: &Matrix, errors: &Matrix) -> Matrix {
+        if !self.is_softmax() {
+            return outputs.map(self.derivative).elementwise_multiply(errors);
+        }
+
+        // Softmax Jacobian-vector product: s * (e - s.e)
+        let weighted: f64 = outputs.data.iter().zip(&errors.data).map(|(s, e)| s * e).sum();
+        let data = outputs
+            .data
+            .iter()
+            .zip(&errors.data)
+            .map(|(s, e)| s * (e - weighted))
+            .collect();
+        Matrix::new(outputs.rows, outputs.cols, data)
+    }
+}
+
+impl PartialEq for Activation {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
 // Custom serialization for Activation
 // We serialize it as a string identifier since function pointers can't be serialized
 impl Serialize for Activation {
@@ -19,9 +126,7 @@ impl Serialize for Activation {
     where
         S: Serializer,
     {
-        // For now, we only have SIGMOID
-        // In the future, we could compare function pointers or use a registry
-        serializer.serialize_str("sigmoid")
+        serializer.serialize_str(self.name)
     }
 }
 
@@ -31,12 +136,44 @@ impl<'de> Deserialize<'de> for Activation {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "sigmoid" => Ok(SIGMOID),
-            _ => Err(serde::de::Error::custom(format!(
-                "Unknown activation function: {}",
-                s
-            ))),
+        Activation::from_name(&s).ok_or_else(|| {
+            serde::de::Error::custom(format!("Unknown activation function: {}", s))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_softmax_outputs_sum_to_one() {
+        let outputs = SOFTMAX.apply(&Matrix::from(vec![1.0, 2.0, 3.0, 1000.0]));
+
+        assert!((outputs.data.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(outputs.data.iter().all(|x| x.is_finite()));
+        assert!(outputs.data[3] > 0.99);
+    }
+
+    #[test]
+    fn test_softmax_delta_matches_finite_differences() {
+        let inputs = [0.3, -1.2, 0.8];
+        let errors = Matrix::from(vec![0.5, -0.25, 1.0]);
+        let outputs = SOFTMAX.apply(&Matrix::from(inputs.to_vec()));
+        let delta = SOFTMAX.delta(&outputs, &errors);
+
+        // delta[j] is d(errors . softmax(inputs)) / d(inputs[j])
+        let objective = |inputs: &[f64]| -> f64 {
+            let outputs = SOFTMAX.apply(&Matrix::from(inputs.to_vec()));
+            outputs.data.iter().zip(&errors.data).map(|(s, e)| s * e).sum()
+        };
+        for j in 0..inputs.len() {
+            let mut plus = inputs;
+            let mut minus = inputs;
+            plus[j] += 1e-6;
+            minus[j] -= 1e-6;
+            let numeric = (objective(&plus) - objective(&minus)) / 2e-6;
+            assert!((delta.data[j] - numeric).abs() < 1e-6, "{} vs {}", delta.data[j], numeric);
         }
     }
 }
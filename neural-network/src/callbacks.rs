@@ -0,0 +1,80 @@
+/// Ready-made callbacks for `TrainingController`
+use crate::network::Network;
+use crate::training::TrainingCallback;
+use std::sync::{Arc, Mutex};
+
+/// The lowest-loss network seen so far, with the epoch and loss it had
+#[derive(Debug, Clone)]
+pub struct BestSnapshot {
+    pub epoch: u32,
+    pub loss: f64,
+    pub network: Network,
+}
+
+/// Handle to the snapshot kept by a `best_model_tracker` callback
+///
+/// Clones share the same snapshot, so the handle can be read while or
+/// after the controller that owns the callback trains.
+#[derive(Debug, Clone, Default)]
+pub struct BestModel {
+    best: Arc<Mutex<Option<BestSnapshot>>>,
+}
+
+impl BestModel {
+    /// The best snapshot so far, or `None` before the first finite loss
+    pub fn snapshot(&self) -> Option<BestSnapshot> {
+        self.best.lock().unwrap().clone()
+    }
+
+    /// The lowest epoch loss seen so far
+    pub fn loss(&self) -> Option<f64> {
+        self.best.lock().unwrap().as_ref().map(|best| best.loss)
+    }
+
+    /// A copy of the network as it was after its best epoch
+    pub fn network(&self) -> Option<Network> {
+        self.snapshot().map(|best| best.network)
+    }
+}
+
+/// Keep the lowest-loss network of a training run in memory
+///
+/// Returns a callback for `TrainingController::add_callback` and a handle
+/// to read the best network from, for picking the best model without
+/// writing checkpoints. The network is cloned only on epochs whose loss
+/// beats every earlier one; NaN and infinite losses never count.
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::activations::SIGMOID;
+/// use neural_network::callbacks::best_model_tracker;
+/// use neural_network::examples;
+/// use neural_network::network::Network;
+/// use neural_network::training::{TrainingConfig, TrainingController};
+///
+/// let xor = examples::get_example("xor").unwrap();
+/// let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+/// let config = TrainingConfig { epochs: 100, ..Default::default() };
+/// let mut controller = TrainingController::new(network, config);
+///
+/// let (callback, best) = best_model_tracker();
+/// controller.add_callback(callback);
+/// controller.train(xor.inputs, xor.targets).unwrap();
+/// assert_eq!(best.loss(), controller.best_loss());
+/// ```
+pub fn best_model_tracker() -> (TrainingCallback, BestModel) {
+    let handle = BestModel::default();
+    let best = handle.best.clone();
+    let callback: TrainingCallback = Box::new(move |epoch, loss, network| {
+        let mut best = best.lock().unwrap();
+        if loss.is_finite() && best.as_ref().is_none_or(|best| loss < best.loss) {
+            *best = Some(BestSnapshot {
+                epoch,
+                loss,
+                network: network.clone(),
+            });
+        }
+    });
+    (callback, handle)
+}
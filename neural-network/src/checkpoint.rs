@@ -4,14 +4,114 @@
 /// training sessions. Checkpoints include both the network state (weights, biases)
 /// and metadata about the training session (epoch, timestamp, etc.).
 use crate::network::Network;
-use anyhow::{Context, Result};
+use crate::optimizer::OptimizerState;
+use crate::error::{NnError, Result};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "fs")]
 use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
+use std::str::FromStr;
 
 /// Supported checkpoint format version
 const CHECKPOINT_VERSION: &str = "1.0";
 
+/// Magic bytes at the start of a binary checkpoint
+const BINARY_MAGIC: &[u8] = b"NNCK";
+
+/// Magic bytes at the start of a gzip stream
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// Largest decompressed size accepted for a gzip checkpoint, so a small
+/// crafted file can't expand until memory runs out
+pub const MAX_CHECKPOINT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// On-disk encoding of a checkpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CheckpointFormat {
+    /// Pretty-printed JSON (human readable, the default)
+    #[default]
+    Json,
+
+    /// Compact MessagePack prefixed with `NNCK` magic bytes
+    Bin,
+
+    /// Gzip-compressed JSON
+    JsonGz,
+}
+
+impl CheckpointFormat {
+    /// Infer the format from a file extension
+    ///
+    /// `.bin` selects binary, `.gz` selects gzip-compressed JSON, and anything
+    /// else is treated as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::checkpoint::CheckpointFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(CheckpointFormat::from_path(Path::new("model.bin")), CheckpointFormat::Bin);
+    /// assert_eq!(CheckpointFormat::from_path(Path::new("model.json.gz")), CheckpointFormat::JsonGz);
+    /// assert_eq!(CheckpointFormat::from_path(Path::new("model.json")), CheckpointFormat::Json);
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bin") => CheckpointFormat::Bin,
+            Some("gz") => CheckpointFormat::JsonGz,
+            _ => CheckpointFormat::Json,
+        }
+    }
+}
+
+impl CheckpointFormat {
+    /// Detect the format from the leading bytes of a checkpoint
+    ///
+    /// Returns `None` if the bytes don't look like any known format.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(BINARY_MAGIC) {
+            Some(CheckpointFormat::Bin)
+        } else if bytes.starts_with(GZIP_MAGIC) {
+            Some(CheckpointFormat::JsonGz)
+        } else if bytes.trim_ascii_start().starts_with(b"{") {
+            Some(CheckpointFormat::Json)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for CheckpointFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointFormat::Json => write!(f, "json"),
+            CheckpointFormat::Bin => write!(f, "bin"),
+            CheckpointFormat::JsonGz => write!(f, "json-gz"),
+        }
+    }
+}
+
+impl FromStr for CheckpointFormat {
+    type Err = NnError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(CheckpointFormat::Json),
+            "bin" => Ok(CheckpointFormat::Bin),
+            "json-gz" => Ok(CheckpointFormat::JsonGz),
+            _ => Err(NnError::Format(format!(
+                "Unknown checkpoint format: {}. Expected json, bin or json-gz",
+                s
+            ))),
+        }
+    }
+}
+
 /// Metadata about a training checkpoint
 ///
 /// Contains information about when and where the checkpoint was created,
@@ -35,6 +135,24 @@ pub struct CheckpointMetadata {
 
     /// ISO 8601 timestamp of when checkpoint was created
     pub timestamp: String,
+
+    /// One record per session that resumed training from a checkpoint,
+    /// oldest first; empty for checkpoints that were never resumed
+    #[serde(default)]
+    pub history: Vec<SessionRecord>,
+}
+
+/// A training session that resumed from a checkpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Epochs the session trained
+    pub epochs: u32,
+
+    /// Learning rate at the end of the session
+    pub learning_rate: f64,
+
+    /// ISO 8601 timestamp of the session's latest checkpoint
+    pub timestamp: String,
 }
 
 /// Complete checkpoint containing network state and metadata
@@ -48,6 +166,11 @@ pub struct Checkpoint {
 
     /// The neural network state (weights, biases, architecture)
     pub network: Network,
+
+    /// Optimizer state (velocities or moments) of the training run, absent
+    /// for plain gradient descent and older checkpoints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer: Option<OptimizerState>,
 }
 
 impl Network {
@@ -76,6 +199,7 @@ impl Network {
     ///     total_epochs: 1000,
     ///     learning_rate: 0.5,
     ///     timestamp: chrono::Utc::now().to_rfc3339(),
+    ///     history: Vec::new(),
     /// };
     ///
     /// let checkpoint = network.to_checkpoint(metadata);
@@ -84,6 +208,7 @@ impl Network {
         Checkpoint {
             metadata,
             network: self.clone(),
+            optimizer: None,
         }
     }
 
@@ -117,21 +242,14 @@ impl Network {
     ///     total_epochs: 1000,
     ///     learning_rate: 0.5,
     ///     timestamp: chrono::Utc::now().to_rfc3339(),
+    ///     history: Vec::new(),
     /// };
     ///
     /// let checkpoint = network.to_checkpoint(metadata);
     /// let restored = Network::from_checkpoint(checkpoint).expect("Should restore");
     /// ```
     pub fn from_checkpoint(checkpoint: Checkpoint) -> Result<Self> {
-        // Validate checkpoint version
-        if checkpoint.metadata.version != CHECKPOINT_VERSION {
-            anyhow::bail!(
-                "Unsupported checkpoint version: {}. Expected: {}",
-                checkpoint.metadata.version,
-                CHECKPOINT_VERSION
-            );
-        }
-
+        checkpoint.check_version()?;
         Ok(checkpoint.network)
     }
 
@@ -173,25 +291,27 @@ impl Network {
     ///     total_epochs: 1000,
     ///     learning_rate: 0.5,
     ///     timestamp: chrono::Utc::now().to_rfc3339(),
+    ///     history: Vec::new(),
     /// };
     ///
     /// network.save_checkpoint(Path::new("checkpoint.json"), metadata)
     ///     .expect("Failed to save checkpoint");
     /// ```
+    #[cfg(feature = "fs")]
     pub fn save_checkpoint(&self, path: &Path, metadata: CheckpointMetadata) -> Result<()> {
         let checkpoint = self.to_checkpoint(metadata);
 
         let json = serde_json::to_string_pretty(&checkpoint)
-            .context("Failed to serialize checkpoint")?;
+            .map_err(NnError::serde("Failed to serialize checkpoint"))?;
 
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+                .map_err(NnError::io(format!("Failed to create directory {}", parent.display())))?;
         }
 
         fs::write(path, json)
-            .with_context(|| format!("Failed to write checkpoint to {}", path.display()))?;
+            .map_err(NnError::io(format!("Failed to write checkpoint to {}", path.display())))?;
 
         Ok(())
     }
@@ -229,21 +349,265 @@ impl Network {
     ///
     /// println!("Resumed from epoch {}", metadata.epoch);
     /// ```
+    #[cfg(feature = "fs")]
     pub fn load_checkpoint(path: &Path) -> Result<(Self, CheckpointMetadata)> {
         let contents = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read checkpoint from {}", path.display()))?;
+            .map_err(NnError::io(format!("Failed to read checkpoint from {}", path.display())))?;
 
         let checkpoint: Checkpoint = serde_json::from_str(&contents)
-            .context("Failed to deserialize checkpoint")?;
+            .map_err(NnError::serde("Failed to deserialize checkpoint"))?;
+
+        let metadata = checkpoint.metadata.clone();
+        let network = Self::from_checkpoint(checkpoint)?;
+
+        Ok((network, metadata))
+    }
+}
+
+impl Network {
+    /// Write a checkpoint to any writer in the given format
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
+    ///
+    /// let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// let metadata = CheckpointMetadata {
+    ///     version: "1.0".to_string(),
+    ///     example: "xor".to_string(),
+    ///     epoch: 100,
+    ///     total_epochs: 1000,
+    ///     learning_rate: 0.5,
+    ///     timestamp: chrono::Utc::now().to_rfc3339(),
+    ///     history: Vec::new(),
+    /// };
+    ///
+    /// let mut bytes = Vec::new();
+    /// network.write_checkpoint(&mut bytes, metadata, CheckpointFormat::Bin)
+    ///     .expect("Failed to write checkpoint");
+    /// assert!(bytes.starts_with(b"NNCK"));
+    /// ```
+    pub fn write_checkpoint<W: Write>(
+        &self,
+        writer: W,
+        metadata: CheckpointMetadata,
+        format: CheckpointFormat,
+    ) -> Result<()> {
+        self.to_checkpoint(metadata).write(writer, format)
+    }
+
+    /// Save a checkpoint to a file in the given format
+    ///
+    /// Like `save_checkpoint`, but lets the caller choose the encoding.
+    #[cfg(feature = "fs")]
+    pub fn save_checkpoint_as(
+        &self,
+        path: &Path,
+        metadata: CheckpointMetadata,
+        format: CheckpointFormat,
+    ) -> Result<()> {
+        self.to_checkpoint(metadata).save(path, format)
+    }
+
+    /// Load a checkpoint from a file in the given format
+    #[cfg(feature = "fs")]
+    pub fn load_checkpoint_as(
+        path: &Path,
+        format: CheckpointFormat,
+    ) -> Result<(Self, CheckpointMetadata)> {
+        let bytes = fs::read(path)
+            .map_err(NnError::io(format!("Failed to read checkpoint from {}", path.display())))?;
+
+        Self::read_checkpoint(&bytes, format)
+    }
+
+    /// Load a checkpoint without knowing its format in advance
+    ///
+    /// The format is detected from the file's magic bytes, falling back to
+    /// the file extension when the contents are not recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neural_network::network::Network;
+    /// use std::path::Path;
+    ///
+    /// let (network, metadata) = Network::load_checkpoint_auto(Path::new("model.bin"))
+    ///     .expect("Failed to load checkpoint");
+    /// ```
+    #[cfg(feature = "fs")]
+    pub fn load_checkpoint_auto(path: &Path) -> Result<(Self, CheckpointMetadata)> {
+        let checkpoint = Checkpoint::load(path)?;
+        Ok((checkpoint.network, checkpoint.metadata))
+    }
+
+    /// Decode checkpoint bytes whose format is detected from their magic bytes
+    pub fn load_checkpoint_from_slice(bytes: &[u8]) -> Result<(Self, CheckpointMetadata)> {
+        let format = CheckpointFormat::detect(bytes)
+            .ok_or_else(|| NnError::Format("Unrecognized checkpoint format".to_string()))?;
+        Self::read_checkpoint(bytes, format)
+    }
 
+    /// Decode checkpoint bytes in the given format
+    fn read_checkpoint(bytes: &[u8], format: CheckpointFormat) -> Result<(Self, CheckpointMetadata)> {
+        let checkpoint = Checkpoint::decode(bytes, format)?;
         let metadata = checkpoint.metadata.clone();
         let network = Self::from_checkpoint(checkpoint)?;
 
         Ok((network, metadata))
     }
+
+    /// Load a checkpoint, warning instead of failing on a version mismatch
+    ///
+    /// Useful for experimenting with checkpoints written by newer versions,
+    /// at your own risk. The format is detected as in `load_checkpoint_auto`,
+    /// and files that fail to deserialize are still rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neural_network::network::Network;
+    /// use std::path::Path;
+    ///
+    /// let (network, metadata) = Network::load_checkpoint_lenient(Path::new("future.json"))
+    ///     .expect("Failed to load checkpoint");
+    /// ```
+    #[cfg(feature = "fs")]
+    pub fn load_checkpoint_lenient(path: &Path) -> Result<(Self, CheckpointMetadata)> {
+        let bytes = fs::read(path)
+            .map_err(NnError::io(format!("Failed to read checkpoint from {}", path.display())))?;
+
+        let format =
+            CheckpointFormat::detect(&bytes).unwrap_or_else(|| CheckpointFormat::from_path(path));
+        let checkpoint = Checkpoint::decode(&bytes, format)?;
+
+        if checkpoint.metadata.version != CHECKPOINT_VERSION {
+            eprintln!(
+                "Warning: loading checkpoint version {} (expected {}); results may be incorrect",
+                checkpoint.metadata.version, CHECKPOINT_VERSION
+            );
+        }
+
+        Ok((checkpoint.network, checkpoint.metadata))
+    }
+}
+
+impl Checkpoint {
+    /// Write the checkpoint to any writer in the given format
+    pub fn write<W: Write>(&self, mut writer: W, format: CheckpointFormat) -> Result<()> {
+        match format {
+            CheckpointFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, self)
+                    .map_err(NnError::serde("Failed to serialize checkpoint"))?;
+            }
+            CheckpointFormat::Bin => {
+                let bytes = rmp_serde::to_vec_named(self)
+                    .map_err(NnError::serde("Failed to serialize checkpoint"))?;
+                writer.write_all(BINARY_MAGIC)?;
+                writer.write_all(&bytes)?;
+            }
+            CheckpointFormat::JsonGz => {
+                let mut encoder = GzEncoder::new(&mut writer, Compression::default());
+                serde_json::to_writer(&mut encoder, self)
+                    .map_err(NnError::serde("Failed to serialize checkpoint"))?;
+                encoder.finish()?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Save the checkpoint to a file in the given format
+    #[cfg(feature = "fs")]
+    pub fn save(&self, path: &Path, format: CheckpointFormat) -> Result<()> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes, format)?;
+
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(NnError::io(format!("Failed to create directory {}", parent.display())))?;
+        }
+
+        fs::write(path, bytes)
+            .map_err(NnError::io(format!("Failed to write checkpoint to {}", path.display())))?;
+
+        Ok(())
+    }
+
+    /// Load a whole checkpoint, including any optimizer state
+    ///
+    /// The format is detected as in `Network::load_checkpoint_auto`, and the
+    /// version is validated.
+    #[cfg(feature = "fs")]
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)
+            .map_err(NnError::io(format!("Failed to read checkpoint from {}", path.display())))?;
+
+        let format =
+            CheckpointFormat::detect(&bytes).unwrap_or_else(|| CheckpointFormat::from_path(path));
+        let checkpoint = Self::decode(&bytes, format)?;
+        checkpoint.check_version()?;
+
+        Ok(checkpoint)
+    }
+
+    fn check_version(&self) -> Result<()> {
+        if self.metadata.version != CHECKPOINT_VERSION {
+            return Err(NnError::CheckpointVersion {
+                found: self.metadata.version.clone(),
+                expected: CHECKPOINT_VERSION.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Deserialize checkpoint bytes in the given format without validating the version
+    fn decode(bytes: &[u8], format: CheckpointFormat) -> Result<Self> {
+        let checkpoint: Checkpoint = match format {
+            CheckpointFormat::Json => {
+                serde_json::from_slice(bytes).map_err(NnError::serde("Failed to deserialize checkpoint"))?
+            }
+            CheckpointFormat::Bin => {
+                let body = bytes
+                    .strip_prefix(BINARY_MAGIC)
+                    .ok_or_else(|| {
+                        NnError::Format("Not a binary checkpoint (missing NNCK header)".to_string())
+                    })?;
+                rmp_serde::from_slice(body).map_err(NnError::serde("Failed to deserialize checkpoint"))?
+            }
+            CheckpointFormat::JsonGz => {
+                let json = decompress(bytes, MAX_CHECKPOINT_BYTES)?;
+                serde_json::from_slice(&json).map_err(NnError::serde("Failed to deserialize checkpoint"))?
+            }
+        };
+
+        Ok(checkpoint)
+    }
+}
+
+/// Decompress a gzip stream of at most `limit` bytes
+fn decompress(bytes: &[u8], limit: u64) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    // One byte past the limit tells an oversized stream from one that fits exactly
+    GzDecoder::new(bytes)
+        .take(limit + 1)
+        .read_to_end(&mut data)
+        .map_err(NnError::io("Failed to decompress checkpoint"))?;
+    if data.len() as u64 > limit {
+        return Err(NnError::Format(format!(
+            "Checkpoint decompresses to more than {} bytes",
+            limit
+        )));
+    }
+    Ok(data)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "fs"))]
 mod tests {
     use super::*;
     use crate::activations::SIGMOID;
@@ -253,6 +617,17 @@ mod tests {
         assert_eq!(CHECKPOINT_VERSION, "1.0");
     }
 
+    #[test]
+    fn test_decompression_is_limited() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[b' '; 1000]).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        assert_eq!(decompress(&bytes, 1000).unwrap().len(), 1000);
+        let error = decompress(&bytes, 999).unwrap_err();
+        assert!(matches!(error, NnError::Format(_)), "{}", error);
+    }
+
     #[test]
     fn test_checkpoint_metadata_creation() {
         let metadata = CheckpointMetadata {
@@ -262,6 +637,7 @@ mod tests {
             total_epochs: 100,
             learning_rate: 0.5,
             timestamp: "2025-10-13T12:00:00Z".to_string(),
+            history: Vec::new(),
         };
 
         assert_eq!(metadata.version, "1.0");
@@ -278,6 +654,7 @@ mod tests {
             total_epochs: 1000,
             learning_rate: 0.5,
             timestamp: "2025-10-13T12:00:00Z".to_string(),
+            history: Vec::new(),
         };
 
         let checkpoint = network.to_checkpoint(metadata);
@@ -296,6 +673,7 @@ mod tests {
             total_epochs: 1000,
             learning_rate: 0.5,
             timestamp: "2025-10-13T12:00:00Z".to_string(),
+            history: Vec::new(),
         };
 
         let checkpoint = network.to_checkpoint(metadata);
@@ -314,6 +692,7 @@ mod tests {
             total_epochs: 1000,
             learning_rate: 0.5,
             timestamp: "2025-10-13T12:00:00Z".to_string(),
+            history: Vec::new(),
         };
 
         let checkpoint = network.to_checkpoint(metadata);
@@ -321,4 +700,115 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_checkpoint_format_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+
+        for (format, file) in [
+            (CheckpointFormat::Json, "model.json"),
+            (CheckpointFormat::Bin, "model.bin"),
+            (CheckpointFormat::JsonGz, "model.json.gz"),
+        ] {
+            let path = temp_dir.path().join(file);
+            let metadata = CheckpointMetadata {
+                version: "1.0".to_string(),
+                example: "xor".to_string(),
+                epoch: 100,
+                total_epochs: 1000,
+                learning_rate: 0.5,
+                timestamp: "2025-10-13T12:00:00Z".to_string(),
+                history: Vec::new(),
+            };
+
+            network.save_checkpoint_as(&path, metadata, format).unwrap();
+            let (restored, metadata) =
+                Network::load_checkpoint_as(&path, CheckpointFormat::from_path(&path)).unwrap();
+
+            for (restored, original) in restored.weights.iter().zip(&network.weights) {
+                for (a, b) in restored.data.iter().zip(&original.data) {
+                    assert!((a - b).abs() < 1e-12, "{} weights should match", format);
+                }
+            }
+            assert_eq!(metadata.epoch, 100);
+        }
+    }
+
+    #[test]
+    fn test_load_checkpoint_auto_detects_format() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+
+        // Deliberately misleading extensions: detection must use the magic bytes
+        for (format, file) in [
+            (CheckpointFormat::Json, "json.model"),
+            (CheckpointFormat::Bin, "bin.json"),
+            (CheckpointFormat::JsonGz, "gz.bin"),
+        ] {
+            let path = temp_dir.path().join(file);
+            let metadata = CheckpointMetadata {
+                version: "1.0".to_string(),
+                example: "xor".to_string(),
+                epoch: 7,
+                total_epochs: 10,
+                learning_rate: 0.5,
+                timestamp: "2025-10-13T12:00:00Z".to_string(),
+                history: Vec::new(),
+            };
+            network.save_checkpoint_as(&path, metadata, format).unwrap();
+
+            let (restored, metadata) = Network::load_checkpoint_auto(&path)
+                .unwrap_or_else(|e| panic!("{} checkpoint should load: {}", format, e));
+            assert_eq!(restored.layers, vec![2, 3, 1]);
+            assert_eq!(metadata.epoch, 7);
+        }
+    }
+
+    #[test]
+    fn test_detect_rejects_unknown_bytes() {
+        assert_eq!(CheckpointFormat::detect(b"not a checkpoint"), None);
+        assert!(Network::load_checkpoint_from_slice(b"not a checkpoint").is_err());
+    }
+
+    #[test]
+    fn test_lenient_load_accepts_newer_version() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("future.json");
+
+        let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+        let metadata = CheckpointMetadata {
+            version: "2.0".to_string(),
+            example: "xor".to_string(),
+            epoch: 100,
+            total_epochs: 1000,
+            learning_rate: 0.5,
+            timestamp: "2025-10-13T12:00:00Z".to_string(),
+            history: Vec::new(),
+        };
+        network.save_checkpoint(&path, metadata).unwrap();
+
+        assert!(Network::load_checkpoint(&path).is_err(), "Strict load should reject 2.0");
+
+        let (restored, metadata) =
+            Network::load_checkpoint_lenient(&path).expect("Lenient load should accept 2.0");
+        assert_eq!(restored.layers, vec![2, 3, 1]);
+        assert_eq!(metadata.version, "2.0");
+    }
+
+    #[test]
+    fn test_lenient_load_still_rejects_corrupt_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("corrupt.json");
+        fs::write(&path, "{\"metadata\": {}}").unwrap();
+
+        assert!(Network::load_checkpoint_lenient(&path).is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_format_from_str() {
+        assert_eq!("bin".parse::<CheckpointFormat>().unwrap(), CheckpointFormat::Bin);
+        assert_eq!("json-gz".parse::<CheckpointFormat>().unwrap(), CheckpointFormat::JsonGz);
+        assert!("yaml".parse::<CheckpointFormat>().is_err());
+    }
 }
@@ -0,0 +1,200 @@
+/// Training data sources that are read batch by batch
+///
+/// A `DataSource` hands training data to `TrainingController::train_from_source`
+/// in batches, so datasets larger than memory can be streamed from disk
+/// instead of being loaded into `Vec`s up front. Each epoch starts with
+/// `reset` and reads batches until `next_batch` returns `None`.
+#[cfg(feature = "fs")]
+use anyhow::Context;
+#[cfg(feature = "fs")]
+use std::fs::File;
+#[cfg(feature = "fs")]
+use std::io::{BufRead, BufReader, Lines};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+
+/// A batch of inputs and their targets
+pub type Batch = (Vec<Vec<f64>>, Vec<Vec<f64>>);
+
+/// Source of training batches
+pub trait DataSource {
+    /// The next batch of `(inputs, targets)`, or `None` once this pass is done
+    fn next_batch(&mut self) -> Option<Batch>;
+
+    /// Rewind to the first batch for another pass over the data
+    fn reset(&mut self) -> anyhow::Result<()>;
+
+    /// Error that ended the current pass early, if any
+    ///
+    /// Sources that can fail while reading return `None` from `next_batch`
+    /// and report the cause here. Infallible sources keep the default.
+    fn take_error(&mut self) -> Option<anyhow::Error> {
+        None
+    }
+}
+
+/// CSV file read lazily in fixed-size batches
+///
+/// Each row holds the input columns followed by the target columns. A first
+/// row that isn't numeric is treated as a header and skipped.
+#[cfg(feature = "fs")]
+pub struct CsvDataSource {
+    path: PathBuf,
+    input_count: usize,
+    target_count: usize,
+    batch_size: usize,
+    lines: Lines<BufReader<File>>,
+    line_number: usize,
+    error: Option<anyhow::Error>,
+}
+
+#[cfg(feature = "fs")]
+impl CsvDataSource {
+    /// Open a CSV file with `input_count` input and `target_count` target columns
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neural_network::data_source::CsvDataSource;
+    ///
+    /// let source = CsvDataSource::open("data/large.csv", 2, 1, 256).unwrap();
+    /// ```
+    pub fn open(
+        path: impl AsRef<Path>,
+        input_count: usize,
+        target_count: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            input_count > 0 && target_count > 0,
+            "CSV data needs at least one input and one target column"
+        );
+        anyhow::ensure!(batch_size > 0, "Batch size must be at least 1");
+
+        let path = path.as_ref().to_path_buf();
+        let lines = Self::open_lines(&path)?;
+        Ok(Self {
+            path,
+            input_count,
+            target_count,
+            batch_size,
+            lines,
+            line_number: 0,
+            error: None,
+        })
+    }
+
+    fn open_lines(path: &Path) -> anyhow::Result<Lines<BufReader<File>>> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open data file {}", path.display()))?;
+        Ok(BufReader::new(file).lines())
+    }
+
+    /// Read and parse the next data row, skipping blank lines and a header
+    fn next_row(&mut self) -> anyhow::Result<Option<Vec<f64>>> {
+        while let Some(line) = self.lines.next() {
+            self.line_number += 1;
+            let line = line.with_context(|| format!("Failed to read {}", self.path.display()))?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let values: Result<Vec<f64>, _> =
+                line.split(',').map(|field| field.trim().parse::<f64>()).collect();
+            let values = match values {
+                Ok(values) => values,
+                // A non-numeric first line is a header
+                Err(_) if self.line_number == 1 => continue,
+                Err(e) => anyhow::bail!("{} row {}: {}", self.path.display(), self.line_number, e),
+            };
+
+            let columns = self.input_count + self.target_count;
+            anyhow::ensure!(
+                values.len() == columns,
+                "{} row {}: expected {} columns, got {}",
+                self.path.display(),
+                self.line_number,
+                columns,
+                values.len()
+            );
+            return Ok(Some(values));
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "fs")]
+impl DataSource for CsvDataSource {
+    fn next_batch(&mut self) -> Option<Batch> {
+        let mut inputs = Vec::with_capacity(self.batch_size);
+        let mut targets = Vec::with_capacity(self.batch_size);
+
+        while inputs.len() < self.batch_size {
+            match self.next_row() {
+                Ok(Some(mut values)) => {
+                    targets.push(values.split_off(self.input_count));
+                    inputs.push(values);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            }
+        }
+
+        if inputs.is_empty() {
+            None
+        } else {
+            Some((inputs, targets))
+        }
+    }
+
+    fn reset(&mut self) -> anyhow::Result<()> {
+        self.lines = Self::open_lines(&self.path)?;
+        self.line_number = 0;
+        self.error = None;
+        Ok(())
+    }
+
+    fn take_error(&mut self) -> Option<anyhow::Error> {
+        self.error.take()
+    }
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_batches_and_reset() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("xor.csv");
+        std::fs::write(&path, "x1,x2,y\n0,0,0\n0,1,1\n\n1,0,1\n1,1,0\n0.5,0.5,0.5\n").unwrap();
+
+        let mut source = CsvDataSource::open(&path, 2, 1, 2).unwrap();
+        let sizes: Vec<usize> = std::iter::from_fn(|| source.next_batch())
+            .map(|(inputs, _)| inputs.len())
+            .collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+
+        source.reset().unwrap();
+        let (inputs, targets) = source.next_batch().unwrap();
+        assert_eq!(inputs, vec![vec![0.0, 0.0], vec![0.0, 1.0]]);
+        assert_eq!(targets, vec![vec![0.0], vec![1.0]]);
+    }
+
+    #[test]
+    fn test_csv_reports_bad_rows() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bad.csv");
+        std::fs::write(&path, "0,0,0\n0,1\n").unwrap();
+
+        let mut source = CsvDataSource::open(&path, 2, 1, 10).unwrap();
+        assert!(source.next_batch().is_none());
+        let error = source.take_error().expect("Short row should be an error");
+        assert!(error.to_string().contains("row 2"), "{}", error);
+    }
+}
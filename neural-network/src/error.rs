@@ -0,0 +1,106 @@
+/// Errors returned by the crate's core APIs
+///
+/// Prediction and checkpoint functions return `NnError`, so callers can
+/// match on what went wrong. Higher-level helpers such as training and data
+/// loading return `anyhow::Result`; an `NnError` converts into
+/// `anyhow::Error` with `?`, keeping its source chain.
+use std::error::Error;
+
+/// Result type of the core APIs
+pub type Result<T> = std::result::Result<T, NnError>;
+
+/// Error kinds of the core APIs
+///
+/// `Io` and `Serde` errors describe the failed operation followed by the
+/// underlying error, which is also available as their `source`.
+#[derive(Debug, thiserror::Error)]
+pub enum NnError {
+    /// An input has a different number of values than the network's input layer
+    #[error("Invalid input dimensions: expected {expected}, got {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    /// Input values that are NaN or infinite, with their indices
+    #[error("Input values must be finite, got {}", describe_non_finite(.values))]
+    NonFinite { values: Vec<(usize, f64)> },
+
+    /// `predict_topk` asked for more classes than the network has outputs
+    #[error("Cannot rank the top {k} of {outputs} outputs")]
+    InvalidTopK { k: usize, outputs: usize },
+
+    /// A checkpoint written with an unsupported format version
+    #[error("Unsupported checkpoint version: {found}. Expected: {expected}")]
+    CheckpointVersion { found: String, expected: String },
+
+    /// An unknown checkpoint format name, or bytes in no recognized format
+    #[error("{0}")]
+    Format(String),
+
+    /// Reading or writing a file or stream failed
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Encoding or decoding a checkpoint failed
+    #[error("{context}: {source}")]
+    Serde {
+        context: String,
+        #[source]
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl NnError {
+    /// Wrap an I/O error with a description of what was being done
+    pub(crate) fn io(context: impl Into<String>) -> impl FnOnce(std::io::Error) -> NnError {
+        move |source| NnError::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Wrap an encoding or decoding error with a description of what was being done
+    pub(crate) fn serde<E: Error + Send + Sync + 'static>(
+        context: impl Into<String>,
+    ) -> impl FnOnce(E) -> NnError {
+        move |source| NnError::Serde {
+            context: context.into(),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl From<std::io::Error> for NnError {
+    fn from(source: std::io::Error) -> Self {
+        NnError::io("I/O error")(source)
+    }
+}
+
+impl From<serde_json::Error> for NnError {
+    fn from(source: serde_json::Error) -> Self {
+        NnError::serde("JSON encoding error")(source)
+    }
+}
+
+impl From<rmp_serde::encode::Error> for NnError {
+    fn from(source: rmp_serde::encode::Error) -> Self {
+        NnError::serde("MessagePack encoding error")(source)
+    }
+}
+
+impl From<rmp_serde::decode::Error> for NnError {
+    fn from(source: rmp_serde::decode::Error) -> Self {
+        NnError::serde("MessagePack decoding error")(source)
+    }
+}
+
+/// "NaN at index 0, inf at index 2"
+fn describe_non_finite(values: &[(usize, f64)]) -> String {
+    values
+        .iter()
+        .map(|(i, value)| format!("{} at index {}", value, i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
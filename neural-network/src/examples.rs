@@ -3,14 +3,16 @@
 /// This module provides pre-configured examples of classic machine learning problems:
 /// AND, OR, and XOR logic gates. Each example includes the training data, recommended
 /// architecture, and hyperparameters.
+use serde::Deserialize;
+
 /// Represents a training example with inputs, targets, and recommended configuration
 #[derive(Debug, Clone)]
 pub struct Example {
     /// Name of the example (e.g., "and", "or", "xor")
-    pub name: &'static str,
+    pub name: String,
 
     /// Description of what this example demonstrates
-    pub description: &'static str,
+    pub description: String,
 
     /// Training inputs - each inner vec is one input sample
     pub inputs: Vec<Vec<f64>>,
@@ -49,8 +51,8 @@ pub struct Example {
 pub fn get_example(name: &str) -> Option<Example> {
     match name {
         "and" => Some(Example {
-            name: "and",
-            description: "Logical AND gate - outputs 1 only when both inputs are 1. This is a linearly separable problem.",
+            name: "and".to_string(),
+            description: "Logical AND gate - outputs 1 only when both inputs are 1. This is a linearly separable problem.".to_string(),
             inputs: vec![
                 vec![0.0, 0.0],
                 vec![0.0, 1.0],
@@ -69,8 +71,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "or" => Some(Example {
-            name: "or",
-            description: "Logical OR gate - outputs 1 when at least one input is 1. This is a linearly separable problem.",
+            name: "or".to_string(),
+            description: "Logical OR gate - outputs 1 when at least one input is 1. This is a linearly separable problem.".to_string(),
             inputs: vec![
                 vec![0.0, 0.0],
                 vec![0.0, 1.0],
@@ -89,8 +91,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "xor" => Some(Example {
-            name: "xor",
-            description: "Logical XOR gate - outputs 1 when inputs are different. This is NOT linearly separable and requires a hidden layer.",
+            name: "xor".to_string(),
+            description: "Logical XOR gate - outputs 1 when inputs are different. This is NOT linearly separable and requires a hidden layer.".to_string(),
             inputs: vec![
                 vec![0.0, 0.0],
                 vec![0.0, 1.0],
@@ -109,8 +111,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "parity3" => Some(Example {
-            name: "parity3",
-            description: "3-bit parity - outputs 1 when an odd number of inputs are 1. Extension of XOR to 3 inputs.",
+            name: "parity3".to_string(),
+            description: "3-bit parity - outputs 1 when an odd number of inputs are 1. Extension of XOR to 3 inputs.".to_string(),
             inputs: vec![
                 vec![0.0, 0.0, 0.0],
                 vec![0.0, 0.0, 1.0],
@@ -137,8 +139,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "quadrant" => Some(Example {
-            name: "quadrant",
-            description: "Quadrant classification - classifies 2D points into 4 quadrants. First multi-class output example.",
+            name: "quadrant".to_string(),
+            description: "Quadrant classification - classifies 2D points into 4 quadrants. First multi-class output example.".to_string(),
             inputs: vec![
                 // Quadrant I: x > 0, y > 0 -> [1, 0, 0, 0]
                 vec![1.0, 1.0],
@@ -181,8 +183,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "adder2" => Some(Example {
-            name: "adder2",
-            description: "2-bit binary adder - adds two 2-bit numbers. Demonstrates arithmetic learning with multi-bit outputs.",
+            name: "adder2".to_string(),
+            description: "2-bit binary adder - adds two 2-bit numbers. Demonstrates arithmetic learning with multi-bit outputs.".to_string(),
             inputs: vec![
                 // Format: [A1, A0, B1, B0] where A = A1*2 + A0, B = B1*2 + B0
                 vec![0.0, 0.0, 0.0, 0.0], // 0 + 0 = 0
@@ -227,8 +229,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "iris" => Some(Example {
-            name: "iris",
-            description: "Iris flower classification - classic ML dataset with real botanical measurements. Classifies iris species based on sepal and petal dimensions.",
+            name: "iris".to_string(),
+            description: "Iris flower classification - classic ML dataset with real botanical measurements. Classifies iris species based on sepal and petal dimensions.".to_string(),
             inputs: vec![
                 // Format: [sepal_length, sepal_width, petal_length, petal_width] in cm
                 // Iris Setosa (20 samples)
@@ -289,8 +291,8 @@ pub fn get_example(name: &str) -> Option<Example> {
         }),
 
         "pattern3x3" => Some(Example {
-            name: "pattern3x3",
-            description: "3x3 pattern recognition - recognizes visual patterns (X, O, +, -) in a 3x3 grid. Demonstrates image-like data processing.",
+            name: "pattern3x3".to_string(),
+            description: "3x3 pattern recognition - recognizes visual patterns (X, O, +, -) in a 3x3 grid. Demonstrates image-like data processing.".to_string(),
             inputs: vec![
                 // Format: 9 pixels in row-major order [top-left, top-center, top-right, ..., bottom-right]
                 // X pattern - diagonals (6 variations)
@@ -364,6 +366,187 @@ pub fn list_examples() -> Vec<&'static str> {
     vec!["and", "or", "xor", "parity3", "quadrant", "adder2", "iris", "pattern3x3"]
 }
 
+/// JSON layout of an example dataset file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExampleFile {
+    name: String,
+    #[serde(default)]
+    description: String,
+    inputs: Vec<Vec<f64>>,
+    targets: Vec<Vec<f64>>,
+    architecture: Vec<usize>,
+    #[serde(default = "default_epochs")]
+    epochs: u32,
+    #[serde(default = "default_learning_rate")]
+    learning_rate: f64,
+}
+
+fn default_epochs() -> u32 {
+    10000
+}
+
+fn default_learning_rate() -> f64 {
+    0.5
+}
+
+/// Parse an example from JSON
+///
+/// The JSON holds `name`, `inputs`, `targets` and `architecture`, plus
+/// optional `description`, `epochs` (default 10000) and `learning_rate`
+/// (default 0.5), which become the recommended settings. Every input and
+/// every target must have the same length, matching the first and last
+/// layers of `architecture`.
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::examples::parse_json;
+///
+/// let json = r#"{
+///     "name": "not",
+///     "inputs": [[0.0], [1.0]],
+///     "targets": [[1.0], [0.0]],
+///     "architecture": [1, 2, 1]
+/// }"#;
+/// let example = parse_json(json).unwrap();
+/// assert_eq!(example.recommended_epochs, 10000);
+/// ```
+pub fn parse_json(json: &str) -> anyhow::Result<Example> {
+    let file: ExampleFile = serde_json::from_str(json)?;
+    validate_dimensions(&file.inputs, &file.targets, &file.architecture)?;
+
+    Ok(Example {
+        name: file.name,
+        description: file.description,
+        inputs: file.inputs,
+        targets: file.targets,
+        recommended_arch: file.architecture,
+        recommended_epochs: file.epochs,
+        recommended_lr: file.learning_rate,
+    })
+}
+
+/// Load an example from a JSON dataset file, as described in `parse_json`
+///
+/// Handy for multi-output datasets that are awkward to write as CSV.
+#[cfg(feature = "fs")]
+pub fn load_json(path: &std::path::Path) -> anyhow::Result<Example> {
+    use anyhow::Context;
+
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read example from {}", path.display()))?;
+    parse_json(&json).with_context(|| format!("Invalid example file {}", path.display()))
+}
+
+/// Combine built-in examples into one dataset for a single training run
+///
+/// The samples are concatenated in the order given, so every example must
+/// have the same number of inputs and outputs. The recommended
+/// architecture takes the widest of each layer when the examples have the
+/// same depth, and otherwise the deepest example's architecture; the
+/// recommended epochs are the largest and the learning rate the smallest.
+/// Samples are kept as given, so an input that two examples label
+/// differently (like `[0, 1]` in `and` and `or`) pulls the network towards
+/// the average of its targets.
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::examples::combine;
+///
+/// let gates = combine(&["and", "or"]).unwrap();
+/// assert_eq!(gates.name, "and+or");
+/// assert_eq!(gates.inputs.len(), 8);
+/// assert!(combine(&["and", "iris"]).is_err());
+/// ```
+pub fn combine(names: &[&str]) -> anyhow::Result<Example> {
+    anyhow::ensure!(!names.is_empty(), "No examples to combine");
+    let examples = names
+        .iter()
+        .map(|name| get_example(name).ok_or_else(|| anyhow::anyhow!("Unknown example '{}'", name)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let first = &examples[0];
+    let (input_width, target_width) = (first.inputs[0].len(), first.targets[0].len());
+    for example in &examples[1..] {
+        anyhow::ensure!(
+            example.inputs[0].len() == input_width && example.targets[0].len() == target_width,
+            "Example '{}' has {} inputs and {} outputs, but '{}' has {} and {}",
+            example.name,
+            example.inputs[0].len(),
+            example.targets[0].len(),
+            first.name,
+            input_width,
+            target_width
+        );
+    }
+
+    let deepest = &examples.iter().max_by_key(|example| example.recommended_arch.len()).unwrap_or(first).recommended_arch;
+    let recommended_arch = if examples.iter().all(|example| example.recommended_arch.len() == deepest.len()) {
+        (0..deepest.len())
+            .map(|layer| examples.iter().map(|example| example.recommended_arch[layer]).max().unwrap_or(0))
+            .collect()
+    } else {
+        deepest.clone()
+    };
+
+    Ok(Example {
+        name: names.join("+"),
+        description: examples
+            .iter()
+            .map(|example| example.description.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        inputs: examples.iter().flat_map(|example| example.inputs.clone()).collect(),
+        targets: examples.iter().flat_map(|example| example.targets.clone()).collect(),
+        recommended_arch,
+        recommended_epochs: examples.iter().map(|example| example.recommended_epochs).max().unwrap_or(0),
+        recommended_lr: examples.iter().map(|example| example.recommended_lr).fold(f64::INFINITY, f64::min),
+    })
+}
+
+/// Check that the samples are consistent and fit the architecture
+fn validate_dimensions(
+    inputs: &[Vec<f64>],
+    targets: &[Vec<f64>],
+    architecture: &[usize],
+) -> anyhow::Result<()> {
+    anyhow::ensure!(!inputs.is_empty(), "Example has no samples");
+    anyhow::ensure!(
+        inputs.len() == targets.len(),
+        "Example has {} inputs but {} targets",
+        inputs.len(),
+        targets.len()
+    );
+    anyhow::ensure!(
+        architecture.len() >= 2 && !architecture.contains(&0),
+        "Architecture {:?} needs at least an input and an output layer, none of them empty",
+        architecture
+    );
+
+    let input_width = architecture[0];
+    let target_width = architecture[architecture.len() - 1];
+    for (i, (input, target)) in inputs.iter().zip(targets).enumerate() {
+        anyhow::ensure!(
+            input.len() == input_width && target.len() == target_width,
+            "Sample {} has {} inputs and {} targets, but architecture {:?} expects {} and {}",
+            i,
+            input.len(),
+            target.len(),
+            architecture,
+            input_width,
+            target_width
+        );
+        anyhow::ensure!(
+            input.iter().chain(target).all(|value| value.is_finite()),
+            "Sample {} has a non-finite value",
+            i
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
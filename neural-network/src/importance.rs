@@ -0,0 +1,77 @@
+/// Permutation feature importance
+///
+/// A feature matters to a network if scrambling it makes the predictions
+/// worse. Each input column is shuffled across the samples, breaking its link
+/// to the targets while keeping its distribution, and the rise in mean squared
+/// error is that feature's importance. Shuffles are seeded, so scores are
+/// reproducible.
+use crate::loss::Loss;
+use crate::network::Network;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+/// Number of shuffles averaged per feature, smoothing out lucky permutations
+/// of small datasets
+pub const IMPORTANCE_REPEATS: usize = 10;
+
+/// Seed of the shuffles
+const IMPORTANCE_SEED: u64 = 0;
+
+impl Network {
+    /// Importance of each input feature, as the increase in mean squared error
+    /// when that feature is shuffled across the samples
+    ///
+    /// Returns one score per input. Scores near zero mean the network ignores
+    /// the feature; small negative scores are noise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let network = Network::new_seeded(vec![3, 4, 1], SIGMOID, 0.5, 1);
+    /// let inputs = vec![vec![0.0, 1.0, 0.5], vec![1.0, 0.0, 0.5]];
+    /// let targets = vec![vec![1.0], vec![0.0]];
+    ///
+    /// let importance = network.feature_importance(&inputs, &targets);
+    /// assert_eq!(importance.len(), 3);
+    /// // A constant feature can't be scrambled
+    /// assert_eq!(importance[2], 0.0);
+    /// ```
+    pub fn feature_importance(&self, inputs: &[Vec<f64>], targets: &[Vec<f64>]) -> Vec<f64> {
+        let loss = |inputs: &[Vec<f64>]| -> f64 {
+            let total: f64 = inputs
+                .iter()
+                .zip(targets)
+                .map(|(input, target)| Loss::MeanSquaredError.compute(&self.forward(input), target))
+                .sum();
+            total / inputs.len().max(1) as f64
+        };
+        let baseline = loss(inputs);
+
+        let mut rng = StdRng::seed_from_u64(IMPORTANCE_SEED);
+        let mut shuffled = inputs.to_vec();
+        (0..self.layers[0])
+            .map(|feature| {
+                let mut column: Vec<f64> = inputs.iter().map(|input| input[feature]).collect();
+                let increase: f64 = (0..IMPORTANCE_REPEATS)
+                    .map(|_| {
+                        column.shuffle(&mut rng);
+                        for (input, &value) in shuffled.iter_mut().zip(&column) {
+                            input[feature] = value;
+                        }
+                        loss(&shuffled) - baseline
+                    })
+                    .sum();
+
+                // Restore the column for the next feature
+                for (input, original) in shuffled.iter_mut().zip(inputs) {
+                    input[feature] = original[feature];
+                }
+                increase / IMPORTANCE_REPEATS as f64
+            })
+            .collect()
+    }
+}
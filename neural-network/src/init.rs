@@ -0,0 +1,129 @@
+/// Distributions for a network's initial weights
+///
+/// `Network::new` and `Network::new_seeded` draw every weight and bias
+/// uniformly from [0, 1). `Network::new_with_init` takes a `WeightInit`
+/// instead, either one of the usual heuristics scaled by each layer's size
+/// or an explicit distribution, e.g. to reproduce a paper's setup.
+use crate::activations::Activation;
+use crate::matrix::Matrix;
+use crate::network::Network;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// How to draw a network's initial weights
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightInit {
+    /// Weights and biases uniform in [0, 1), as `Network::new_seeded` draws them
+    #[default]
+    Uniform,
+
+    /// Xavier (Glorot) initialization: weights uniform in `[-a, a]` with
+    /// `a = sqrt(6 / (fan_in + fan_out))`, biases zero. Suits sigmoid and tanh.
+    Xavier,
+
+    /// He initialization: weights normal with standard deviation
+    /// `sqrt(2 / fan_in)`, biases zero. Suits ReLU.
+    He,
+
+    /// Weights and biases normal with mean 0 and standard deviation `std`
+    Normal { std: f64 },
+
+    /// Weights and biases uniform in `[low, high)`
+    UniformRange { low: f64, high: f64 },
+}
+
+impl WeightInit {
+    /// Draw a weight for a layer with `fan_in` inputs and `fan_out` outputs
+    pub fn weight<R: Rng + ?Sized>(&self, fan_in: usize, fan_out: usize, rng: &mut R) -> f64 {
+        match *self {
+            WeightInit::Uniform => rng.gen_range(0.0..1.0),
+            WeightInit::Xavier => {
+                let limit = (6.0 / (fan_in + fan_out) as f64).sqrt();
+                rng.gen_range(-limit..=limit)
+            }
+            WeightInit::He => standard_normal(rng) * (2.0 / fan_in as f64).sqrt(),
+            WeightInit::Normal { std } => standard_normal(rng) * std,
+            WeightInit::UniformRange { low, high } => rng.gen_range(low..high),
+        }
+    }
+
+    /// Draw a bias; the heuristics start biases at zero
+    pub fn bias<R: Rng + ?Sized>(&self, fan_in: usize, fan_out: usize, rng: &mut R) -> f64 {
+        match self {
+            WeightInit::Xavier | WeightInit::He => 0.0,
+            _ => self.weight(fan_in, fan_out, rng),
+        }
+    }
+
+    /// Check the distribution's parameters
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match *self {
+            WeightInit::Normal { std } => anyhow::ensure!(
+                std.is_finite() && std >= 0.0,
+                "Normal init needs a finite, non-negative std, got {}",
+                std
+            ),
+            WeightInit::UniformRange { low, high } => anyhow::ensure!(
+                low.is_finite() && high.is_finite() && low < high,
+                "UniformRange init needs finite bounds with low < high, got {} and {}",
+                low,
+                high
+            ),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// A sample from the standard normal distribution (Box-Muller transform)
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // 1 - u lies in (0, 1], so the logarithm is finite
+    let u: f64 = 1.0 - rng.gen_range(0.0..1.0);
+    let v: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+impl Network {
+    /// Create a network with weights drawn from `init` using a seeded RNG
+    ///
+    /// # Errors
+    ///
+    /// When `init`'s parameters are invalid (see `WeightInit::validate`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::TANH;
+    /// use neural_network::init::WeightInit;
+    ///
+    /// let init = WeightInit::UniformRange { low: -0.1, high: 0.1 };
+    /// let network = Network::new_with_init(vec![2, 3, 1], TANH, 0.5, init, 42).unwrap();
+    /// assert!(network.weights.iter().flat_map(|w| &w.data).all(|w| w.abs() <= 0.1));
+    /// ```
+    pub fn new_with_init(
+        layers: Vec<usize>,
+        activation: Activation,
+        learning_rate: f64,
+        init: WeightInit,
+        seed: u64,
+    ) -> anyhow::Result<Self> {
+        init.validate()?;
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut weights = vec![];
+        let mut biases = vec![];
+        for pair in layers.windows(2) {
+            let (fan_in, fan_out) = (pair[0], pair[1]);
+            let data = (0..fan_in * fan_out).map(|_| init.weight(fan_in, fan_out, &mut rng)).collect();
+            weights.push(Matrix::new(fan_out, fan_in, data));
+            let data = (0..fan_out).map(|_| init.bias(fan_in, fan_out, &mut rng)).collect();
+            biases.push(Matrix::new(fan_out, 1, data));
+        }
+
+        let activations = vec![activation; weights.len()];
+        Ok(Network::from_parameters(layers, weights, biases, activations, learning_rate))
+    }
+}
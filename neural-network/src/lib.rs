@@ -5,6 +5,16 @@ pub mod activations;
 pub mod examples;
 pub mod checkpoint;
 pub mod training;
+pub mod metrics;
+pub mod loss;
+pub mod data_source;
+pub mod preprocessing;
+pub mod quantize;
+pub mod importance;
+pub mod optimizer;
+pub mod callbacks;
+pub mod init;
+pub mod error;
 
 pub mod matrix {
 
@@ -0,0 +1,188 @@
+/// Loss functions used during training
+///
+/// A loss measures how far a network output is from its target and provides
+/// the gradient that backpropagation pushes through the network. Gradients
+/// follow the backpropagation convention of `0.5 * (target - output)^2` for
+/// mean squared error, so the MSE gradient is simply `output - target`.
+/// Reported losses are twice the function those gradients come from, for
+/// every loss, so they share one scale: an error within Huber's `delta`
+/// reports the same loss as under mean squared error.
+use crate::matrix::Matrix;
+use serde::{Deserialize, Serialize};
+
+/// How per-sample losses are normalized into the reported loss
+///
+/// Either way the reported loss is averaged over samples; the modes differ
+/// in whether it is also averaged over each sample's outputs. Gradients are
+/// the same in both modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LossReduction {
+    /// Summed over each sample's outputs, so networks with more outputs
+    /// report proportionally larger losses
+    #[default]
+    PerSample,
+
+    /// Averaged over every output of every sample (`samples * outputs`
+    /// elements), so losses compare across output counts
+    PerElement,
+}
+
+impl LossReduction {
+    /// Normalize the loss of one sample with `outputs` outputs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::loss::LossReduction;
+    ///
+    /// assert_eq!(LossReduction::PerSample.normalize(0.8, 4), 0.8);
+    /// assert_eq!(LossReduction::PerElement.normalize(0.8, 4), 0.2);
+    /// ```
+    pub fn normalize(&self, sample_loss: f64, outputs: usize) -> f64 {
+        match self {
+            LossReduction::PerSample => sample_loss,
+            LossReduction::PerElement => sample_loss / outputs as f64,
+        }
+    }
+}
+
+/// Loss function selection
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Loss {
+    /// Squared error, the classic choice for the logic gate examples
+    #[default]
+    MeanSquaredError,
+
+    /// Huber loss: quadratic for errors up to `delta`, linear beyond it.
+    /// Large errors (outliers) contribute a gradient clipped to `delta`.
+    /// Reported as `e^2` up to `delta` and `2 * delta * (|e| - delta / 2)`
+    /// beyond, twice the textbook form, to match mean squared error.
+    Huber { delta: f64 },
+}
+
+impl Loss {
+    /// Loss for a single sample, summed over outputs
+    ///
+    /// Every loss is on the squared error's scale, so `target_loss`, loss
+    /// averages and histories compare across loss functions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::loss::Loss;
+    ///
+    /// let mse = Loss::MeanSquaredError.compute(&[0.5], &[1.0]);
+    /// assert!((mse - 0.25).abs() < 1e-12);
+    /// assert_eq!(Loss::Huber { delta: 1.0 }.compute(&[0.5], &[1.0]), mse);
+    /// ```
+    pub fn compute(&self, output: &[f64], target: &[f64]) -> f64 {
+        output
+            .iter()
+            .zip(target)
+            .map(|(o, t)| {
+                let error = t - o;
+                match *self {
+                    Loss::MeanSquaredError => error * error,
+                    Loss::Huber { delta } => {
+                        if error.abs() <= delta {
+                            error * error
+                        } else {
+                            2.0 * delta * (error.abs() - 0.5 * delta)
+                        }
+                    }
+                }
+            })
+            .sum()
+    }
+
+    /// Gradient of the loss with respect to each output
+    pub fn gradient(&self, output: &[f64], target: &[f64]) -> Vec<f64> {
+        output
+            .iter()
+            .zip(target)
+            .map(|(o, t)| {
+                let diff = o - t;
+                match *self {
+                    Loss::MeanSquaredError => diff,
+                    Loss::Huber { delta } => diff.clamp(-delta, delta),
+                }
+            })
+            .collect()
+    }
+
+    /// Error signal for backpropagation (the negated gradient)
+    ///
+    /// For mean squared error this is `target - output`, matching
+    /// `Network::back_propogate`.
+    pub fn output_errors(&self, outputs: &Matrix, targets: &Matrix) -> Matrix {
+        let data = self
+            .gradient(&outputs.data, &targets.data)
+            .into_iter()
+            .map(|g| -g)
+            .collect::<Vec<f64>>();
+
+        Matrix {
+            rows: outputs.rows,
+            cols: outputs.cols,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_huber_matches_mse_gradient_for_small_errors() {
+        let huber = Loss::Huber { delta: 1.0 };
+        let output = [0.2, 0.9, -0.3];
+        let target = [0.5, 0.4, 0.0];
+
+        assert_eq!(
+            huber.gradient(&output, &target),
+            Loss::MeanSquaredError.gradient(&output, &target)
+        );
+    }
+
+    #[test]
+    fn test_huber_clips_gradient_for_large_errors() {
+        let huber = Loss::Huber { delta: 1.0 };
+        let output = [10.0, -7.0];
+        let target = [0.0, 0.0];
+
+        let mse_gradient = Loss::MeanSquaredError.gradient(&output, &target);
+        let huber_gradient = huber.gradient(&output, &target);
+
+        assert_eq!(mse_gradient, vec![10.0, -7.0]);
+        assert_eq!(huber_gradient, vec![1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_huber_loss_is_linear_beyond_delta() {
+        let huber = Loss::Huber { delta: 1.0 };
+
+        assert!((huber.compute(&[0.5], &[0.0]) - 0.25).abs() < 1e-12);
+        assert!((huber.compute(&[3.0], &[0.0]) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_huber_reports_mse_loss_for_small_errors() {
+        let huber = Loss::Huber { delta: 1.0 };
+        let output = [0.2, 0.9, -0.3];
+        let target = [0.5, 0.4, 0.0];
+
+        let mse = Loss::MeanSquaredError.compute(&output, &target);
+        assert!((huber.compute(&output, &target) - mse).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_output_errors_are_negated_gradient() {
+        let outputs = Matrix::from(vec![0.25, 0.75]);
+        let targets = Matrix::from(vec![1.0, 0.0]);
+
+        let errors = Loss::MeanSquaredError.output_errors(&outputs, &targets);
+
+        assert_eq!(errors, targets.subtract(&outputs));
+    }
+}
@@ -0,0 +1,343 @@
+/// Evaluation metrics for trained networks
+///
+/// These helpers score a network against a labelled dataset. Single-output
+/// networks are treated as binary classifiers thresholded at 0.5, while
+/// multi-output networks are treated as one-hot classifiers compared by argmax.
+/// Regression networks are scored by `AccuracyMode::Regression` instead.
+use crate::network::Network;
+use serde::{Deserialize, Serialize};
+
+/// Index of the largest value in a slice (first index wins on ties)
+///
+/// NaN values are skipped, so they never win; a slice with no other values
+/// gives 0.
+pub fn argmax(values: &[f64]) -> usize {
+    let mut best = 0;
+    for (i, &value) in values.iter().enumerate() {
+        if !value.is_nan() && (values[best].is_nan() || value > values[best]) {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Class index of an output or target vector
+///
+/// Single values are binary classes split at 0.5; longer vectors are one-hot.
+pub fn class_of(values: &[f64]) -> usize {
+    if values.len() == 1 {
+        (values[0] >= 0.5) as usize
+    } else {
+        argmax(values)
+    }
+}
+
+/// How `Network::accuracy` decides whether a prediction is correct
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AccuracyMode {
+    /// The predicted class matches the target's: single outputs are split
+    /// at 0.5, longer ones compared by argmax
+    #[default]
+    Classification,
+    /// Every output is within `tolerance` of its target
+    Regression { tolerance: f64 },
+}
+
+/// Whether a single prediction matches its target
+///
+/// Predictions containing NaN or infinite values are never correct.
+fn is_correct(output: &[f64], target: &[f64], mode: AccuracyMode) -> bool {
+    if !output.iter().all(|value| value.is_finite()) {
+        return false;
+    }
+    match mode {
+        AccuracyMode::Classification => class_of(output) == class_of(target),
+        AccuracyMode::Regression { tolerance } => output
+            .iter()
+            .zip(target)
+            .all(|(value, target)| (value - target).abs() <= tolerance),
+    }
+}
+
+/// Coefficient of determination of `outputs` against `targets`, averaged
+/// over the output columns
+///
+/// A column whose targets are all equal has no variance to explain, so it
+/// scores 1.0 when predicted exactly and 0.0 otherwise.
+pub fn r2(outputs: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    let Some(columns) = targets.first().map(Vec::len) else {
+        return 0.0;
+    };
+    let samples = targets.len() as f64;
+
+    let total: f64 = (0..columns)
+        .map(|column| {
+            let mean = targets.iter().map(|target| target[column]).sum::<f64>() / samples;
+            let residual: f64 = outputs
+                .iter()
+                .zip(targets)
+                .map(|(output, target)| (target[column] - output[column]).powi(2))
+                .sum();
+            // Compared directly, since rounding in the mean can leave a tiny
+            // variance that would blow up the ratio
+            if targets.iter().all(|target| target[column] == targets[0][column]) {
+                return if residual == 0.0 { 1.0 } else { 0.0 };
+            }
+            let variance: f64 = targets.iter().map(|target| (target[column] - mean).powi(2)).sum();
+            1.0 - residual / variance
+        })
+        .sum();
+    total / columns as f64
+}
+
+/// Precision, recall and F1 score for one class
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassMetrics {
+    /// Class index (output neuron, or 0/1 for single-output networks)
+    pub class: usize,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    /// Number of samples whose target is this class
+    pub support: usize,
+}
+
+/// Confusion matrix and per-class metrics for a classifier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassificationReport {
+    /// `confusion_matrix[actual][predicted]` counts samples
+    pub confusion_matrix: Vec<Vec<usize>>,
+    pub classes: Vec<ClassMetrics>,
+    pub accuracy: f64,
+}
+
+impl ClassificationReport {
+    /// Build a report from `(actual, predicted)` class pairs over `class_count` classes
+    ///
+    /// Precision, recall and F1 are `0.0` where their denominator is zero.
+    pub fn from_pairs(pairs: &[(usize, usize)], class_count: usize) -> Self {
+        let mut confusion_matrix = vec![vec![0; class_count]; class_count];
+        for &(actual, predicted) in pairs {
+            confusion_matrix[actual][predicted] += 1;
+        }
+
+        let ratio = |num: usize, den: usize| if den == 0 { 0.0 } else { num as f64 / den as f64 };
+        let classes = (0..class_count)
+            .map(|class| {
+                let true_positives = confusion_matrix[class][class];
+                let support: usize = confusion_matrix[class].iter().sum();
+                let predicted: usize = confusion_matrix.iter().map(|row| row[class]).sum();
+                let precision = ratio(true_positives, predicted);
+                let recall = ratio(true_positives, support);
+                let f1 = if precision + recall == 0.0 {
+                    0.0
+                } else {
+                    2.0 * precision * recall / (precision + recall)
+                };
+                ClassMetrics {
+                    class,
+                    precision,
+                    recall,
+                    f1,
+                    support,
+                }
+            })
+            .collect();
+
+        let correct = (0..class_count).map(|class| confusion_matrix[class][class]).sum();
+        Self {
+            confusion_matrix,
+            classes,
+            accuracy: ratio(correct, pairs.len()),
+        }
+    }
+}
+
+impl Network {
+    /// Fraction of samples predicted correctly
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Input samples
+    /// * `targets` - Expected outputs for each sample
+    /// * `mode` - Whether to compare classes or values within a tolerance
+    ///
+    /// # Returns
+    ///
+    /// Accuracy in the range `[0.0, 1.0]`, or `0.0` for an empty dataset.
+    /// Samples whose output contains NaN or infinite values count as
+    /// incorrect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::metrics::AccuracyMode;
+    ///
+    /// let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// let accuracy = network.accuracy(&[vec![0.0, 1.0]], &[vec![1.0]], AccuracyMode::Classification);
+    /// assert!((0.0..=1.0).contains(&accuracy));
+    ///
+    /// let within = network.accuracy(&[vec![0.0, 1.0]], &[vec![0.5]], AccuracyMode::Regression { tolerance: 0.5 });
+    /// assert_eq!(within, 1.0);
+    /// ```
+    pub fn accuracy(&self, inputs: &[Vec<f64>], targets: &[Vec<f64>], mode: AccuracyMode) -> f64 {
+        if inputs.is_empty() {
+            return 0.0;
+        }
+
+        let mut correct = 0;
+        for (input, target) in inputs.iter().zip(targets) {
+            if is_correct(&self.forward(input), target, mode) {
+                correct += 1;
+            }
+        }
+
+        correct as f64 / inputs.len() as f64
+    }
+
+    /// Coefficient of determination (R^2) of the network's predictions
+    ///
+    /// 1.0 is a perfect fit and 0.0 is no better than always predicting the
+    /// targets' mean; worse fits go negative. Networks with several outputs
+    /// get the mean of each output's score. See [`r2`] for targets without
+    /// variance.
+    ///
+    /// # Returns
+    ///
+    /// The score, or `0.0` for an empty dataset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::TANH;
+    ///
+    /// let network = Network::new(vec![1, 4, 1], TANH, 0.1);
+    /// let inputs = vec![vec![-1.0], vec![0.0], vec![1.0]];
+    /// let targets: Vec<Vec<f64>> = inputs.iter().map(|x| network.predict(x).unwrap()).collect();
+    /// assert_eq!(network.r2_score(&inputs, &targets), 1.0);
+    /// ```
+    pub fn r2_score(&self, inputs: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+        let outputs: Vec<Vec<f64>> = inputs.iter().map(|input| self.forward(input)).collect();
+        r2(&outputs, targets)
+    }
+
+    /// Confusion matrix and per-class precision, recall and F1
+    ///
+    /// Multi-output networks have one class per output neuron; single-output
+    /// networks have two classes (below and above 0.5).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let network = Network::new(vec![2, 4, 3], SIGMOID, 0.5);
+    /// let report = network.classification_report(&[vec![0.0, 1.0]], &[vec![0.0, 1.0, 0.0]]);
+    /// assert_eq!(report.confusion_matrix.len(), 3);
+    /// assert_eq!(report.classes[1].support, 1);
+    /// ```
+    pub fn classification_report(
+        &self,
+        inputs: &[Vec<f64>],
+        targets: &[Vec<f64>],
+    ) -> ClassificationReport {
+        let outputs = self.layers[self.layers.len() - 1];
+        let class_count = if outputs == 1 { 2 } else { outputs };
+
+        let pairs: Vec<(usize, usize)> = inputs
+            .iter()
+            .zip(targets)
+            .map(|(input, target)| {
+                (class_of(target), class_of(&self.forward(input)))
+            })
+            .collect();
+
+        ClassificationReport::from_pairs(&pairs, class_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASSIFICATION: AccuracyMode = AccuracyMode::Classification;
+
+    #[test]
+    fn test_argmax() {
+        assert_eq!(argmax(&[0.1, 0.7, 0.2]), 1);
+        assert_eq!(argmax(&[0.5, 0.5]), 0);
+        assert_eq!(argmax(&[f64::NAN, 0.1, 0.3]), 2);
+        assert_eq!(argmax(&[0.4, f64::NAN, 0.4]), 0);
+        assert_eq!(argmax(&[f64::NAN, f64::NAN]), 0);
+    }
+
+    #[test]
+    fn test_is_correct_binary_and_one_hot() {
+        assert!(is_correct(&[0.9], &[1.0], CLASSIFICATION));
+        assert!(!is_correct(&[0.2], &[1.0], CLASSIFICATION));
+        assert!(is_correct(&[0.1, 0.8, 0.1], &[0.0, 1.0, 0.0], CLASSIFICATION));
+        assert!(!is_correct(&[0.8, 0.1, 0.1], &[0.0, 1.0, 0.0], CLASSIFICATION));
+    }
+
+    #[test]
+    fn test_is_correct_within_tolerance() {
+        let mode = AccuracyMode::Regression { tolerance: 0.1 };
+
+        assert!(is_correct(&[0.35], &[0.3], mode));
+        assert!(!is_correct(&[0.45], &[0.3], mode));
+        assert!(is_correct(&[-0.55, 0.95], &[-0.5, 1.0], mode));
+        assert!(!is_correct(&[-0.55, 0.5], &[-0.5, 1.0], mode));
+    }
+
+    #[test]
+    fn test_non_finite_outputs_are_incorrect() {
+        // A NaN would otherwise fall on the "below 0.5" side and match a 0 target
+        assert!(!is_correct(&[f64::NAN], &[0.0], CLASSIFICATION));
+        assert!(!is_correct(&[f64::NAN, 1.0], &[1.0, 0.0], CLASSIFICATION));
+        assert!(!is_correct(&[f64::INFINITY], &[1.0], CLASSIFICATION));
+        assert!(!is_correct(&[f64::NAN], &[0.0], AccuracyMode::Regression { tolerance: 1.0 }));
+    }
+
+    #[test]
+    fn test_r2_averages_columns() {
+        let targets = vec![vec![1.0, 0.0], vec![2.0, 1.0], vec![3.0, 2.0]];
+        // First column exact, second always the mean
+        let outputs = vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![3.0, 1.0]];
+
+        assert!((r2(&outputs, &targets) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_r2_of_constant_targets() {
+        let targets = vec![vec![0.5], vec![0.5]];
+
+        assert_eq!(r2(&targets.clone(), &targets), 1.0);
+        assert_eq!(r2(&[vec![0.5], vec![0.6]], &targets), 0.0);
+        assert_eq!(r2(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_report_from_pairs() {
+        // Class 0: 2 right, 1 predicted as class 1; class 1: 1 right; class 2: never seen
+        let report = ClassificationReport::from_pairs(&[(0, 0), (0, 0), (0, 1), (1, 1)], 3);
+
+        assert_eq!(report.confusion_matrix, vec![vec![2, 1, 0], vec![0, 1, 0], vec![0, 0, 0]]);
+        assert_eq!(report.accuracy, 0.75);
+
+        let class0 = &report.classes[0];
+        assert_eq!(class0.support, 3);
+        assert_eq!(class0.precision, 1.0);
+        assert!((class0.recall - 2.0 / 3.0).abs() < 1e-12);
+        assert!((class0.f1 - 0.8).abs() < 1e-12);
+
+        let class1 = &report.classes[1];
+        assert_eq!(class1.precision, 0.5);
+        assert_eq!(class1.recall, 1.0);
+
+        assert_eq!(report.classes[2].f1, 0.0);
+    }
+}
@@ -1,18 +1,249 @@
+use crate::error::NnError;
 use matrix::matrix::Matrix;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::de::{self, Deserializer};
 use serde::{Serialize, Deserialize};
+use std::fmt;
 
-use crate::activations::Activation;
+use crate::activations::{Activation, SOFTMAX};
 
 
 #[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+#[serde(try_from = "SerializedNetwork")]
 pub struct Network {
     pub layers: Vec<usize>, // amount of neurons in each layer, [72,16,10]
     pub weights: Vec<Matrix>,
     pub biases: Vec<Matrix>,
     #[serde(skip)]
     data: Vec<Matrix>,
-    pub activation: Activation,
+    /// Activation of each weight layer: `activations[i]` is applied to the
+    /// output of `weights[i]`
+    pub activations: Vec<Activation>,
     pub learning_rate: f64,
+    /// Target means subtracted before training, added back to every
+    /// prediction (see `TrainingConfig::center_targets`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub output_offset: Option<Vec<f64>>,
+    /// Weights removed by `prune`, one mask per weight layer shaped like its
+    /// `data`; `true` marks a pruned weight, which stays zero in training
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub pruned: Option<Vec<Vec<bool>>>,
+}
+
+/// A network as written by any version of this crate
+///
+/// Older checkpoints store one `activation` name for every layer, optionally
+/// with a separate `output_activation`; newer ones store `activations`, one
+/// per weight layer.
+#[derive(Deserialize)]
+struct SerializedNetwork {
+    layers: Vec<usize>,
+    weights: Vec<Matrix>,
+    biases: Vec<Matrix>,
+    #[serde(alias = "activation")]
+    activations: StoredActivations,
+    #[serde(default)]
+    output_activation: Option<Activation>,
+    learning_rate: f64,
+    #[serde(default)]
+    output_offset: Option<Vec<f64>>,
+    #[serde(default)]
+    pruned: Option<Vec<Vec<bool>>>,
+}
+
+/// One activation shared by every layer, or one per layer
+enum StoredActivations {
+    Shared(Activation),
+    PerLayer(Vec<Activation>),
+}
+
+impl<'de> Deserialize<'de> for StoredActivations {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = StoredActivations;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an activation name or a list of them")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                Activation::from_name(name)
+                    .map(StoredActivations::Shared)
+                    .ok_or_else(|| E::custom(format!("Unknown activation function: {}", name)))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(StoredActivations::PerLayer)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl TryFrom<SerializedNetwork> for Network {
+    type Error = String;
+
+    fn try_from(stored: SerializedNetwork) -> Result<Self, String> {
+        let layer_count = stored.weights.len();
+        let mut activations = match stored.activations {
+            StoredActivations::Shared(activation) => vec![activation; layer_count],
+            StoredActivations::PerLayer(activations) => activations,
+        };
+        if activations.len() != layer_count {
+            return Err(format!(
+                "Expected {} activations, one per weight layer, got {}",
+                layer_count,
+                activations.len()
+            ));
+        }
+        if let (Some(output), Some(last)) = (stored.output_activation, activations.last_mut()) {
+            *last = output;
+        }
+        check_shapes(&stored.layers, &stored.weights, &stored.biases)?;
+        if let Some(offset) = &stored.output_offset
+            && offset.len() != stored.layers[layer_count]
+        {
+            return Err(format!(
+                "Expected an output offset of {} values, got {}",
+                stored.layers[layer_count],
+                offset.len()
+            ));
+        }
+        if let Some(pruned) = &stored.pruned
+            && (pruned.len() != layer_count
+                || pruned.iter().zip(&stored.weights).any(|(mask, weights)| mask.len() != weights.data.len()))
+        {
+            return Err("Expected one pruning mask per weight layer, shaped like its weights".to_string());
+        }
+
+        Ok(Network {
+            layers: stored.layers,
+            weights: stored.weights,
+            biases: stored.biases,
+            data: vec![],
+            activations,
+            learning_rate: stored.learning_rate,
+            output_offset: stored.output_offset,
+            pruned: stored.pruned,
+        })
+    }
+}
+
+/// Check that the parameters fit the layer sizes, so a loaded network can't
+/// panic on its first forward pass
+fn check_shapes(layers: &[usize], weights: &[Matrix], biases: &[Matrix]) -> Result<(), String> {
+    if weights.is_empty() || layers.len() != weights.len() + 1 || biases.len() != weights.len() {
+        return Err(format!(
+            "Expected one weight and bias matrix between each of {} layers, got {} and {}",
+            layers.len(),
+            weights.len(),
+            biases.len()
+        ));
+    }
+    for (i, (weight, bias)) in weights.iter().zip(biases).enumerate() {
+        let (inputs, outputs) = (layers[i], layers[i + 1]);
+        if (weight.rows, weight.cols) != (outputs, inputs) || (bias.rows, bias.cols) != (outputs, 1) {
+            return Err(format!(
+                "Layer {} should have {} x {} weights and {} x 1 biases, got {} x {} and {} x {}",
+                i, outputs, inputs, outputs, weight.rows, weight.cols, bias.rows, bias.cols
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Per-layer parameter gradients from one backpropagation pass
+///
+/// Index `i` holds the gradients for `weights[i]` and `biases[i]`. Values are
+/// negated loss gradients, i.e. the direction of the update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gradients {
+    pub weights: Vec<Matrix>,
+    pub biases: Vec<Matrix>,
+}
+
+impl Gradients {
+    /// Global L2 norm over all weight and bias gradients
+    pub fn norm(&self) -> f64 {
+        self.weights
+            .iter()
+            .chain(&self.biases)
+            .flat_map(|m| m.data.iter())
+            .map(|x| x * x)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Multiply every weight and bias gradient by `factor`
+    pub fn scale(&mut self, factor: f64) {
+        for value in self.weights.iter_mut().chain(&mut self.biases).flat_map(|m| m.data.iter_mut()) {
+            *value *= factor;
+        }
+    }
+
+    /// Zero the gradients of the given layers so applying them leaves those
+    /// layers unchanged
+    pub fn freeze(&mut self, layers: &[usize]) {
+        for &layer in layers {
+            for value in self.weights[layer].data.iter_mut().chain(self.biases[layer].data.iter_mut()) {
+                *value = 0.0;
+            }
+        }
+    }
+}
+
+/// Layer buffers reused across `Network::feed_forward_buffered` calls
+///
+/// Allocate once per network shape and pass to every call, so a tight
+/// evaluation loop makes no allocations per input.
+#[derive(Debug, Clone)]
+pub struct ForwardBuffers {
+    /// One column vector per layer, input layer first
+    layers: Vec<Matrix>,
+}
+
+impl ForwardBuffers {
+    /// Buffers sized for `network`'s layers
+    pub fn new(network: &Network) -> Self {
+        ForwardBuffers {
+            layers: network.layers.iter().map(|&size| Matrix::zeros(size, 1)).collect(),
+        }
+    }
+
+    /// Whether these buffers fit `network`'s layer sizes
+    pub fn fits(&self, network: &Network) -> bool {
+        self.layers.len() == network.layers.len()
+            && self.layers.iter().zip(&network.layers).all(|(buffer, &size)| buffer.rows == size)
+    }
+}
+
+/// Check that a user-supplied input has `expected` values, all finite
+pub(crate) fn check_input(expected: usize, input: &[f64]) -> Result<(), NnError> {
+    if input.len() != expected {
+        return Err(NnError::DimensionMismatch {
+            expected,
+            actual: input.len(),
+        });
+    }
+    let invalid: Vec<(usize, f64)> = input
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, value)| !value.is_finite())
+        .collect();
+    if !invalid.is_empty() {
+        return Err(NnError::NonFinite { values: invalid });
+    }
+    Ok(())
 }
 
 impl Network {
@@ -30,15 +261,132 @@ impl Network {
 
 
         Network { 
+            activations: vec![activation; layers.len() - 1],
             layers, 
             weights, 
             biases, 
             data: vec![],
-            activation,
-            learning_rate
+            learning_rate,
+            output_offset: None,
+            pruned: None,
+        }
+
+
+    }
+
+    /// Create a network with weights drawn from a seeded RNG
+    ///
+    /// Two networks built with the same seed and architecture start from
+    /// identical weights, which makes training runs reproducible.
+    pub fn new_seeded(layers: Vec<usize>, activation: Activation, learning_rate: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut weights = vec![];
+        let mut biases = vec![];
+
+        for i in 0..layers.len() - 1 {
+            weights.push(Matrix::random_with_rng(layers[i + 1], layers[i], &mut rng));
+            biases.push(Matrix::random_with_rng(layers[i + 1], 1, &mut rng));
+        }
+
+        Network {
+            activations: vec![activation; layers.len() - 1],
+            layers,
+            weights,
+            biases,
+            data: vec![],
+            learning_rate,
+            output_offset: None,
+            pruned: None,
+        }
+    }
+
+    /// Assemble a network from existing parameters
+    pub(crate) fn from_parameters(
+        layers: Vec<usize>,
+        weights: Vec<Matrix>,
+        biases: Vec<Matrix>,
+        activations: Vec<Activation>,
+        learning_rate: f64,
+    ) -> Self {
+        Network {
+            layers,
+            weights,
+            biases,
+            data: vec![],
+            activations,
+            learning_rate,
+            output_offset: None,
+            pruned: None,
         }
+    }
 
+    /// Use `hidden` for the hidden layers and `output` for the output layer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::{RELU, SIGMOID, SOFTMAX};
+    ///
+    /// let network = Network::new(vec![2, 8, 4], SIGMOID, 0.1).with_activations(RELU, SOFTMAX);
+    /// let output = network.predict(&[0.5, -0.5]).unwrap();
+    /// assert!((output.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn with_activations(mut self, hidden: Activation, output: Activation) -> Self {
+        let layer_count = self.weights.len();
+        self.activations = vec![hidden; layer_count];
+        self.activations[layer_count - 1] = output;
+        self
+    }
 
+    /// Use a separate activation for every weight layer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::{RELU, SIGMOID, TANH};
+    ///
+    /// let network = Network::new(vec![2, 4, 4, 1], SIGMOID, 0.1)
+    ///     .with_layer_activations(vec![RELU, TANH, SIGMOID]);
+    /// assert_eq!(network.layer_activation(1), TANH);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there is exactly one activation per weight layer.
+    pub fn with_layer_activations(mut self, activations: Vec<Activation>) -> Self {
+        assert_eq!(
+            activations.len(),
+            self.weights.len(),
+            "Expected one activation per weight layer"
+        );
+        self.activations = activations;
+        self
+    }
+
+    /// Activation applied by weight layer `layer` (0 = input to first hidden)
+    pub fn layer_activation(&self, layer: usize) -> Activation {
+        self.activations[layer]
+    }
+
+    /// `(rows, cols)` of each weight matrix, input side first
+    ///
+    /// Rows are the layer's output size and columns its input size, so the
+    /// last entry's rows are the network's outputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// assert_eq!(network.layer_shapes(), vec![(3, 2), (1, 3)]);
+    /// ```
+    pub fn layer_shapes(&self) -> Vec<(usize, usize)> {
+        self.weights.iter().map(|weights| (weights.rows, weights.cols)).collect()
     }
 
     pub fn feed_forward(&mut self, inputs: Matrix) -> Matrix {
@@ -53,9 +401,8 @@ impl Network {
 
 
       for i in 0..self.layers.len() -1 {
-            current = self.weights[i]
-            .dot_multiply(&current)
-            .add(&self.biases[i]).map(self.activation.function);
+            current = self.layer_activation(i)
+            .apply(&self.weights[i].dot_multiply(&current).add(&self.biases[i]));
             
             self.data.push(current.clone());
       }
@@ -65,33 +412,412 @@ impl Network {
 
     }
 
+    /// Forward pass with hidden units dropped by an explicit mask
+    ///
+    /// `masks` holds one mask per hidden layer, each as long as its layer;
+    /// units whose entry is `false` have their activation set to 0, and the
+    /// remaining units are not rescaled. Activations are recorded for
+    /// backpropagation like `feed_forward`, so dropout can be tested
+    /// without depending on a random number generator.
+    ///
+    /// # Panics
+    ///
+    /// On a wrong number of inputs, masks or mask entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::matrix::Matrix;
+    ///
+    /// let mut network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// let input = Matrix::from(vec![1.0, 0.0]);
+    /// let output = network.feed_forward_with_mask(input.clone(), &[vec![true; 3]]);
+    /// assert_eq!(output, network.feed_forward(input));
+    /// ```
+    pub fn feed_forward_with_mask(&mut self, inputs: Matrix, masks: &[Vec<bool>]) -> Matrix {
+        let hidden_layers = &self.layers[1..self.layers.len() - 1];
+        assert!(
+            masks.len() == hidden_layers.len(),
+            "Expected {} dropout masks, got {}",
+            hidden_layers.len(),
+            masks.len()
+        );
+        for (layer, (mask, &size)) in masks.iter().zip(hidden_layers).enumerate() {
+            assert!(
+                mask.len() == size,
+                "Dropout mask {} has {} entries, but the layer has {} units",
+                layer,
+                mask.len(),
+                size
+            );
+        }
+        assert!(self.layers[0] == inputs.data.len(), "Invalid Number of Inputs");
+
+        let mut current = inputs;
+        self.data = vec![current.clone()];
+        for i in 0..self.layers.len() - 1 {
+            current = self
+                .layer_activation(i)
+                .apply(&self.weights[i].dot_multiply(&current).add(&self.biases[i]));
+            if let Some(mask) = masks.get(i) {
+                for (value, &keep) in current.data.iter_mut().zip(mask) {
+                    if !keep {
+                        *value = 0.0;
+                    }
+                }
+            }
+            self.data.push(current.clone());
+        }
+        current
+    }
+
+    /// Run a forward pass without recording activations for backpropagation
+    ///
+    /// Unlike `feed_forward` this only borrows the network, so a shared model
+    /// can serve predictions. An `output_offset` is added to the result.
+    /// Inputs come from users, so a wrong number of values or a NaN or
+    /// infinite value is reported as an `NnError::DimensionMismatch` or
+    /// `NnError::NonFinite` rather than producing garbage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::matrix::Matrix;
+    ///
+    /// let mut network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// let output = network.predict(&[1.0, 0.0]).unwrap();
+    /// assert_eq!(output, network.feed_forward(Matrix::from(vec![1.0, 0.0])).data);
+    /// assert!(network.predict(&[f64::NAN, 0.0]).is_err());
+    /// ```
+    pub fn predict(&self, input: &[f64]) -> Result<Vec<f64>, NnError> {
+        check_input(self.layers[0], input)?;
+        Ok(self.forward(input))
+    }
+
+    /// The `k` highest-scoring output indices with their scores, best first
+    ///
+    /// Scores are softmax probabilities: a softmax output layer's outputs
+    /// as they are, and any other layer's outputs passed through softmax,
+    /// which keeps their order. As with `argmax`, ties keep the lower index
+    /// first and NaN outputs rank last, with a NaN score. Inputs are checked
+    /// like `predict`, and a `k` larger than the number of outputs is an
+    /// `NnError::InvalidTopK`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let network = Network::new(vec![2, 3, 4], SIGMOID, 0.5);
+    /// let top = network.predict_topk(&[1.0, 0.0], 2).unwrap();
+    /// assert_eq!(top.len(), 2);
+    /// assert!(top[0].1 >= top[1].1);
+    /// let all = network.predict_topk(&[1.0, 0.0], 4).unwrap();
+    /// assert!((all.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-12);
+    /// assert!(network.predict_topk(&[1.0, 0.0], 5).is_err());
+    /// ```
+    pub fn predict_topk(&self, input: &[f64], k: usize) -> Result<Vec<(usize, f64)>, NnError> {
+        let outputs = self.layers[self.layers.len() - 1];
+        if k > outputs {
+            return Err(NnError::InvalidTopK { k, outputs });
+        }
+
+        let mut scores = self.predict(input)?;
+        if self.activations.last() != Some(&SOFTMAX) {
+            // Softmax over the outputs that aren't NaN, shifted by the
+            // maximum so large outputs don't overflow
+            let max = scores.iter().copied().filter(|s| !s.is_nan()).fold(f64::NEG_INFINITY, f64::max);
+            let mut sum = 0.0;
+            for score in scores.iter_mut().filter(|s| !s.is_nan()) {
+                *score = (*score - max).exp();
+                sum += *score;
+            }
+            scores.iter_mut().for_each(|score| *score /= sum);
+        }
+
+        let mut ranked: Vec<(usize, f64)> = scores.into_iter().enumerate().collect();
+        // A stable sort, so equal scores keep the lower index first
+        ranked.sort_by(|a, b| a.1.is_nan().cmp(&b.1.is_nan()).then(b.1.total_cmp(&a.1)));
+        ranked.truncate(k);
+        Ok(ranked)
+    }
+
+    /// `forward` into preallocated buffers, returning the output layer
+    ///
+    /// The result matches `predict` for valid inputs, including any
+    /// `output_offset`, but reuses `buffers` instead of allocating a matrix
+    /// per layer. Buffers that don't fit the network, for example after a
+    /// resize, are reallocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::{ForwardBuffers, Network};
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    /// let mut buffers = ForwardBuffers::new(&network);
+    /// let output = network.feed_forward_buffered(&[1.0, 0.0], &mut buffers);
+    /// assert_eq!(output.data, network.predict(&[1.0, 0.0]).unwrap());
+    /// ```
+    pub fn feed_forward_buffered<'a>(&self, input: &[f64], buffers: &'a mut ForwardBuffers) -> &'a Matrix {
+        assert!(self.layers[0] == input.len(), "Invalid Number of Inputs");
+        if !buffers.fits(self) {
+            *buffers = ForwardBuffers::new(self);
+        }
+
+        buffers.layers[0].data.copy_from_slice(input);
+        for (i, (weights, biases)) in self.weights.iter().zip(&self.biases).enumerate() {
+            let (done, rest) = buffers.layers.split_at_mut(i + 1);
+            let (current, next) = (&done[i], &mut rest[0]);
+            // Same summation order as `dot_multiply` followed by `add`
+            for (row, (value, bias)) in next.data.iter_mut().zip(&biases.data).enumerate() {
+                let row_weights = &weights.data[row * weights.cols..(row + 1) * weights.cols];
+                let mut sum = 0.0;
+                for (weight, x) in row_weights.iter().zip(&current.data) {
+                    sum += weight * x;
+                }
+                *value = sum + bias;
+            }
+            self.layer_activation(i).apply_in_place(&mut next.data);
+        }
+
+        let output = buffers.layers.last_mut().unwrap();
+        if let Some(offset) = &self.output_offset {
+            for (value, mean) in output.data.iter_mut().zip(offset) {
+                *value += mean;
+            }
+        }
+        output
+    }
+
+    /// `predict` for inputs already known to be valid, such as training data
+    pub(crate) fn forward(&self, input: &[f64]) -> Vec<f64> {
+        assert!(self.layers[0] == input.len(), "Invalid Number of Inputs");
+
+        let mut current = Matrix::from(input.to_vec());
+        for (i, (weights, biases)) in self.weights.iter().zip(&self.biases).enumerate() {
+            current = self
+                .layer_activation(i)
+                .apply(&weights.dot_multiply(&current).add(biases));
+        }
+
+        match &self.output_offset {
+            Some(offset) => current.data.iter().zip(offset).map(|(x, mean)| x + mean).collect(),
+            None => current.data,
+        }
+    }
+
     pub fn back_propogate(&mut self, inputs:Matrix, targets:Matrix) {
 
-        let mut errors = targets.subtract(&inputs);
+        let errors = targets.subtract(&inputs);
 
-        let mut gradients = inputs.clone().map(self.activation.derivative);
+        self.back_propogate_errors(inputs, errors);
+    }
 
+    /// Backpropagate an explicit output error signal
+    ///
+    /// `errors` is the negated loss gradient with respect to the outputs
+    /// (`targets - outputs` for mean squared error).
+    pub fn back_propogate_errors(&mut self, outputs: Matrix, errors: Matrix) {
+        let gradients = self.compute_gradients(&outputs, errors);
+        self.apply_gradients(&gradients);
+    }
 
-      
+    /// Compute parameter gradients for the sample last passed to `feed_forward`
+    ///
+    /// `errors` is the negated loss gradient with respect to the outputs, so
+    /// the result points in the direction that reduces the loss.
+    pub fn compute_gradients(&self, outputs: &Matrix, errors: Matrix) -> Gradients {
+        let layer_count = self.layers.len() - 1;
+        let mut weights = Vec::with_capacity(layer_count);
+        let mut biases = Vec::with_capacity(layer_count);
+
+        let mut errors = errors;
+        let mut layer_outputs = outputs;
+
+        for i in (0..layer_count).rev() {
+            let delta = self.layer_activation(i).delta(layer_outputs, &errors);
+
+            weights.push(delta.dot_multiply(&self.data[i].transpose()));
+            errors = self.weights[i].transpose().dot_multiply(&delta);
+            layer_outputs = &self.data[i];
+            biases.push(delta);
+        }
 
-        for i in (0..self.layers.len() -1).rev(){
-           
-            gradients = gradients.elementwise_multiply(&errors).map(|x| x * self.learning_rate);
-           
-           
-            
-           
-            self.weights[i] = self.weights[i].add(&gradients.dot_multiply(&self.data[i].transpose()));
+        weights.reverse();
+        biases.reverse();
 
-            
-            
-        
-            self.biases[i] = self.biases[i].add(&gradients);
+        Gradients { weights, biases }
+    }
+
+    /// Estimate the loss gradient for every weight and bias by finite differences
+    ///
+    /// Each parameter is nudged by `+epsilon` and `-epsilon` in turn and the
+    /// change in the half squared error `0.5 * sum((target - output)^2)` is
+    /// measured (central differences). Returns `(weights, biases)` shaped like
+    /// `self.weights` and `self.biases`. Parameters are restored afterwards.
+    ///
+    /// This is the plain loss gradient, so it approximates the negation of
+    /// what `compute_gradients` returns for a mean squared error signal. It
+    /// costs two forward passes per parameter, so it is meant for checking
+    /// and comparing gradients on small networks, not for training.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 42);
+    /// let (weights, biases) = network.numeric_gradient(&[1.0, 0.0], &[1.0], 1e-5);
+    /// assert_eq!(weights[0].rows, 3);
+    /// assert_eq!(weights[0].cols, 2);
+    /// assert_eq!(biases[1].rows, 1);
+    /// ```
+    pub fn numeric_gradient(&mut self, input: &[f64], target: &[f64], epsilon: f64) -> (Vec<Matrix>, Vec<Matrix>) {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+
+        let loss = |network: &Network| -> f64 {
+            let output = network.forward(input);
+            0.5 * output.iter().zip(target).map(|(o, t)| (t - o) * (t - o)).sum::<f64>()
+        };
+        let estimate = |network: &mut Network, param: fn(&mut Network) -> &mut Vec<Matrix>| -> Vec<Matrix> {
+            (0..param(network).len())
+                .map(|layer| {
+                    let shape = &param(network)[layer];
+                    let (rows, cols, count) = (shape.rows, shape.cols, shape.data.len());
+                    let data = (0..count)
+                        .map(|k| {
+                            let original = param(network)[layer].data[k];
+                            param(network)[layer].data[k] = original + epsilon;
+                            let plus = loss(network);
+                            param(network)[layer].data[k] = original - epsilon;
+                            let minus = loss(network);
+                            param(network)[layer].data[k] = original;
+                            (plus - minus) / (2.0 * epsilon)
+                        })
+                        .collect();
+                    Matrix::new(rows, cols, data)
+                })
+                .collect()
+        };
+
+        let weights = estimate(self, |network| &mut network.weights);
+        let biases = estimate(self, |network| &mut network.biases);
+        (weights, biases)
+    }
+
+    /// Partial derivatives of every output with respect to every input
+    ///
+    /// Row `j` of the result holds `d output[j] / d input[i]` for each input
+    /// `i`. It runs `feed_forward` once, then backpropagates a unit error
+    /// from each output in turn, chaining `Activation::delta` and the
+    /// transposed weights down to the inputs, so softmax layers are handled
+    /// too. An `output_offset` is constant and does not change the result.
+    ///
+    /// # Panics
+    ///
+    /// On a wrong number of inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 42);
+    /// let jacobian = network.jacobian(&[1.0, 0.0]);
+    /// assert_eq!(jacobian.len(), 1);
+    /// assert_eq!(jacobian[0].len(), 2);
+    /// ```
+    pub fn jacobian(&mut self, input: &[f64]) -> Vec<Vec<f64>> {
+        let outputs = self.feed_forward(Matrix::from(input.to_vec()));
+        let layer_count = self.layers.len() - 1;
+
+        (0..outputs.data.len())
+            .map(|j| {
+                let mut unit = vec![0.0; outputs.data.len()];
+                unit[j] = 1.0;
+                let mut errors = Matrix::from(unit);
+                let mut layer_outputs = &outputs;
+                for i in (0..layer_count).rev() {
+                    let delta = self.layer_activation(i).delta(layer_outputs, &errors);
+                    errors = self.weights[i].transpose().dot_multiply(&delta);
+                    layer_outputs = &self.data[i];
+                }
+                errors.data
+            })
+            .collect()
+    }
 
-            errors = self.weights[i].transpose().dot_multiply(&errors);
-            gradients = self.data[i].map(self.activation.derivative);
+    /// Take a gradient step of size `learning_rate`
+    ///
+    /// Weights removed by `prune` are kept at zero.
+    pub fn apply_gradients(&mut self, gradients: &Gradients) {
+        let learning_rate = self.learning_rate;
 
-        }      
+        for i in 0..self.weights.len() {
+            self.weights[i] = self.weights[i].add(&gradients.weights[i].map(|x| x * learning_rate));
+            self.biases[i] = self.biases[i].add(&gradients.biases[i].map(|x| x * learning_rate));
+        }
+        self.zero_pruned();
+    }
+
+    /// Magnitude pruning: zero every weight whose absolute value is below
+    /// `threshold` and keep it at zero in later training
+    ///
+    /// Returns how many weights were newly pruned. Biases are left alone.
+    /// Pruning again adds to the existing mask, which is saved with the
+    /// network so the sparse structure survives checkpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    ///
+    /// let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 42);
+    /// let pruned = network.prune(0.5);
+    /// let zeros = network.weights.iter().flat_map(|w| &w.data).filter(|&&w| w == 0.0).count();
+    /// assert_eq!(zeros, pruned);
+    /// assert_eq!(network.prune(0.5), 0);
+    /// ```
+    pub fn prune(&mut self, threshold: f64) -> usize {
+        let weights = &self.weights;
+        let pruned = self
+            .pruned
+            .get_or_insert_with(|| weights.iter().map(|w| vec![false; w.data.len()]).collect());
+
+        let mut count = 0;
+        for (weights, mask) in self.weights.iter_mut().zip(pruned.iter_mut()) {
+            for (weight, removed) in weights.data.iter_mut().zip(mask.iter_mut()) {
+                if !*removed && weight.abs() < threshold {
+                    *removed = true;
+                    *weight = 0.0;
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Reset every pruned weight to zero
+    fn zero_pruned(&mut self) {
+        if let Some(pruned) = &self.pruned {
+            for (weights, mask) in self.weights.iter_mut().zip(pruned) {
+                for (weight, &removed) in weights.data.iter_mut().zip(mask) {
+                    if removed {
+                        *weight = 0.0;
+                    }
+                }
+            }
+        }
     }
 
     pub fn train(&mut self, inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>, epochs: u32) {
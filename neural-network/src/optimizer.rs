@@ -0,0 +1,220 @@
+/// Optimizers turning gradients into parameter updates
+///
+/// Plain gradient descent steps straight along each sample's gradient.
+/// Momentum and Adam keep running averages of past gradients between steps;
+/// that state is part of the training run, so checkpoints can carry it and a
+/// resumed run picks up exactly where it stopped.
+use crate::matrix::Matrix;
+use crate::network::{Gradients, Network};
+use serde::{Deserialize, Serialize};
+
+/// Update rule applied to each sample's gradients
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Optimizer {
+    /// Plain stochastic gradient descent
+    #[default]
+    Sgd,
+
+    /// Gradient descent with a velocity that decays by `beta` each step
+    ///
+    /// With `nesterov` the gradient is taken at the look-ahead position the
+    /// velocity is about to carry the parameters to, which damps overshoot.
+    Momentum {
+        beta: f64,
+        #[serde(default)]
+        nesterov: bool,
+    },
+
+    /// Adam: steps scaled by running estimates of the gradient's first and
+    /// second moments
+    Adam { beta1: f64, beta2: f64, epsilon: f64 },
+}
+
+impl Optimizer {
+    /// Adam with the usual defaults (0.9, 0.999, 1e-8)
+    pub fn adam() -> Self {
+        Optimizer::Adam {
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+/// Running state of an optimizer over a training run
+///
+/// Moments start out empty and are sized to the network on the first step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizerState {
+    /// Optimizer the state belongs to
+    pub optimizer: Optimizer,
+
+    /// Number of updates applied so far
+    pub step: u64,
+
+    /// Momentum velocity, or Adam's first moment estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_moment: Option<Gradients>,
+
+    /// Adam's second moment estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub second_moment: Option<Gradients>,
+}
+
+impl OptimizerState {
+    /// Fresh state for `optimizer`
+    pub fn new(optimizer: Optimizer) -> Self {
+        Self {
+            optimizer,
+            step: 0,
+            first_moment: None,
+            second_moment: None,
+        }
+    }
+
+    /// Update `network` with one sample's gradients
+    ///
+    /// Steps are scaled by the network's learning rate.
+    pub fn apply(&mut self, network: &mut Network, gradients: Gradients) {
+        self.apply_frozen(network, gradients, &[]);
+    }
+
+    /// Update `network` like `apply`, leaving the weight layers in `frozen`
+    /// untouched
+    ///
+    /// Their gradients, stored moments and final update are all zeroed, so
+    /// momentum carried over from a checkpoint can't move a frozen layer.
+    pub fn apply_frozen(&mut self, network: &mut Network, mut gradients: Gradients, frozen: &[usize]) {
+        gradients.freeze(frozen);
+        for moment in [&mut self.first_moment, &mut self.second_moment].into_iter().flatten() {
+            moment.freeze(frozen);
+        }
+        self.step += 1;
+        let mut update = match self.optimizer {
+            Optimizer::Sgd => gradients,
+            Optimizer::Momentum { beta, nesterov } => {
+                let velocity = match self.first_moment.take() {
+                    Some(velocity) => blend(&velocity, &gradients, |v, g| beta * v + g),
+                    None => gradients.clone(),
+                };
+                // Gradients are computed at the current parameters rather
+                // than the look-ahead point, so Nesterov steps by
+                // `g + beta * v`, the usual reformulation that stores the
+                // parameters already moved to the next look-ahead
+                let update = if nesterov {
+                    blend(&gradients, &velocity, |g, v| g + beta * v)
+                } else {
+                    velocity.clone()
+                };
+                self.first_moment = Some(velocity);
+                update
+            }
+            Optimizer::Adam { beta1, beta2, epsilon } => {
+                let zeros = || gradients.zeros_like();
+                let first = self.first_moment.take().unwrap_or_else(zeros);
+                let second = self.second_moment.take().unwrap_or_else(zeros);
+                let first = blend(&first, &gradients, |m, g| beta1 * m + (1.0 - beta1) * g);
+                let second = blend(&second, &gradients, |v, g| beta2 * v + (1.0 - beta2) * g * g);
+
+                // Correct the bias of moments that started at zero
+                let first_correction = 1.0 - beta1.powf(self.step as f64);
+                let second_correction = 1.0 - beta2.powf(self.step as f64);
+                let update = blend(&first, &second, |m, v| {
+                    (m / first_correction) / ((v / second_correction).sqrt() + epsilon)
+                });
+                self.first_moment = Some(first);
+                self.second_moment = Some(second);
+                update
+            }
+        };
+        update.freeze(frozen);
+        network.apply_gradients(&update);
+    }
+}
+
+/// Combine two gradient sets element by element
+fn blend(a: &Gradients, b: &Gradients, f: impl Fn(f64, f64) -> f64) -> Gradients {
+    let zip = |a: &[Matrix], b: &[Matrix]| -> Vec<Matrix> {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| {
+                let data = a.data.iter().zip(&b.data).map(|(&x, &y)| f(x, y)).collect();
+                Matrix::new(a.rows, a.cols, data)
+            })
+            .collect()
+    };
+    Gradients {
+        weights: zip(&a.weights, &b.weights),
+        biases: zip(&a.biases, &b.biases),
+    }
+}
+
+impl Gradients {
+    /// All-zero gradients of the same shape
+    fn zeros_like(&self) -> Gradients {
+        let zeros = |matrices: &[Matrix]| -> Vec<Matrix> { matrices.iter().map(|m| Matrix::zeros(m.rows, m.cols)).collect() };
+        Gradients {
+            weights: zeros(&self.weights),
+            biases: zeros(&self.biases),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::activations::SIGMOID;
+
+    fn gradients(value: f64) -> Gradients {
+        Gradients {
+            weights: vec![Matrix::new(1, 2, vec![value; 2])],
+            biases: vec![Matrix::new(1, 1, vec![value])],
+        }
+    }
+
+    #[test]
+    fn test_momentum_accumulates_velocity() {
+        let mut network = Network::new_seeded(vec![2, 1], SIGMOID, 1.0, 0);
+        let before = network.weights[0].data[0];
+        let mut state = OptimizerState::new(Optimizer::Momentum {
+            beta: 0.5,
+            nesterov: false,
+        });
+
+        state.apply(&mut network, gradients(1.0));
+        state.apply(&mut network, gradients(1.0));
+
+        // Steps of 1.0 then 1.5
+        assert!((network.weights[0].data[0] - before - 2.5).abs() < 1e-12);
+        assert_eq!(state.step, 2);
+    }
+
+    #[test]
+    fn test_nesterov_steps_past_the_velocity() {
+        let mut network = Network::new_seeded(vec![2, 1], SIGMOID, 1.0, 0);
+        let before = network.weights[0].data[0];
+        let mut state = OptimizerState::new(Optimizer::Momentum {
+            beta: 0.5,
+            nesterov: true,
+        });
+
+        state.apply(&mut network, gradients(1.0));
+        state.apply(&mut network, gradients(1.0));
+
+        // Velocities of 1.0 then 1.5, giving steps of 1.5 then 1.75
+        assert!((network.weights[0].data[0] - before - 3.25).abs() < 1e-12);
+        assert_eq!(state.first_moment.unwrap().weights[0].data[0], 1.5);
+    }
+
+    #[test]
+    fn test_adam_first_step_has_learning_rate_size() {
+        let mut network = Network::new_seeded(vec![2, 1], SIGMOID, 0.1, 0);
+        let before = network.weights[0].data[0];
+        let mut state = OptimizerState::new(Optimizer::adam());
+
+        state.apply(&mut network, gradients(-20.0));
+
+        assert!((network.weights[0].data[0] - before + 0.1).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,218 @@
+/// Preparing training data: splitting off a validation set and centering
+/// targets
+///
+/// Splits are deterministic for a given seed, so a training run can be
+/// reproduced exactly. Categorical data is split per class so a rare class
+/// can't end up entirely on one side.
+use crate::data_source::Batch;
+use crate::metrics::class_of;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::collections::BTreeMap;
+
+/// Whether targets look like class labels rather than regression values
+///
+/// Single-output targets must all be 0 or 1; wider targets must all be
+/// one-hot.
+pub fn is_categorical(targets: &[Vec<f64>]) -> bool {
+    let is_binary = |value: &f64| *value == 0.0 || *value == 1.0;
+    !targets.is_empty()
+        && targets.iter().all(|target| {
+            target.iter().all(is_binary)
+                && (target.len() == 1 || target.iter().filter(|v| **v == 1.0).count() == 1)
+        })
+}
+
+/// Shuffle the samples and hold out `ratio` of them for validation
+///
+/// Returns `(train, validation)`.
+pub fn random_split(inputs: &[Vec<f64>], targets: &[Vec<f64>], ratio: f64, seed: u64) -> (Batch, Batch) {
+    let (train, validation) = random_indices(inputs.len(), ratio, seed);
+    (gather(inputs, targets, &train), gather(inputs, targets, &validation))
+}
+
+fn random_indices(len: usize, ratio: f64, seed: u64) -> (Vec<usize>, Vec<usize>) {
+    let mut indices: Vec<usize> = (0..len).collect();
+    indices.shuffle(&mut StdRng::seed_from_u64(seed));
+
+    let validation_count = (len as f64 * ratio).round() as usize;
+    let train = indices.split_off(validation_count.min(len));
+    (train, indices)
+}
+
+/// Hold out `ratio` of the samples of each class for validation
+///
+/// The class of a sample is the argmax of its one-hot target (or 0/1 for
+/// single targets). Every class with at least two samples keeps one on each
+/// side, so both splits contain it. Returns `(train, validation)`.
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::preprocessing::stratified_split;
+///
+/// let inputs: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64]).collect();
+/// let targets: Vec<Vec<f64>> = (0..10).map(|i| vec![(i < 2) as u8 as f64]).collect();
+///
+/// let ((_, train_targets), (_, val_targets)) = stratified_split(&inputs, &targets, 0.2, 1);
+/// assert!(train_targets.contains(&vec![1.0]) && val_targets.contains(&vec![1.0]));
+/// ```
+pub fn stratified_split(
+    inputs: &[Vec<f64>],
+    targets: &[Vec<f64>],
+    ratio: f64,
+    seed: u64,
+) -> (Batch, Batch) {
+    let (train, validation) = stratified_indices(targets, ratio, seed);
+    (gather(inputs, targets, &train), gather(inputs, targets, &validation))
+}
+
+fn stratified_indices(targets: &[Vec<f64>], ratio: f64, seed: u64) -> (Vec<usize>, Vec<usize>) {
+    let mut classes: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, target) in targets.iter().enumerate() {
+        classes.entry(class_of(target)).or_default().push(i);
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut train = Vec::new();
+    let mut validation = Vec::new();
+    for indices in classes.values_mut() {
+        indices.shuffle(&mut rng);
+        let mut count = (indices.len() as f64 * ratio).round() as usize;
+        if indices.len() >= 2 {
+            count = count.clamp(1, indices.len() - 1);
+        }
+        validation.extend_from_slice(&indices[..count]);
+        train.extend_from_slice(&indices[count..]);
+    }
+
+    // Keep the original sample order within each split
+    train.sort_unstable();
+    validation.sort_unstable();
+    (train, validation)
+}
+
+/// Split off a validation set, stratifying when the targets are categorical
+pub fn validation_split(
+    inputs: &[Vec<f64>],
+    targets: &[Vec<f64>],
+    ratio: f64,
+    seed: u64,
+) -> (Batch, Batch) {
+    let (train, validation) = validation_split_indices(targets, ratio, seed);
+    (gather(inputs, targets, &train), gather(inputs, targets, &validation))
+}
+
+/// Indices of the samples `validation_split` puts on each side
+///
+/// Returns `(train, validation)`, for splitting data that travels alongside
+/// the samples, such as per-sample weights.
+pub fn validation_split_indices(targets: &[Vec<f64>], ratio: f64, seed: u64) -> (Vec<usize>, Vec<usize>) {
+    if is_categorical(targets) {
+        stratified_indices(targets, ratio, seed)
+    } else {
+        random_indices(targets.len(), ratio, seed)
+    }
+}
+
+/// Mean of each target column
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::preprocessing::{center, target_means};
+///
+/// let targets = vec![vec![1.0, 10.0], vec![3.0, 20.0]];
+/// let means = target_means(&targets);
+/// assert_eq!(means, vec![2.0, 15.0]);
+/// assert_eq!(center(&targets, &means), vec![vec![-1.0, -5.0], vec![1.0, 5.0]]);
+/// ```
+pub fn target_means(targets: &[Vec<f64>]) -> Vec<f64> {
+    let width = targets.first().map_or(0, Vec::len);
+    (0..width)
+        .map(|column| targets.iter().map(|target| target[column]).sum::<f64>() / targets.len() as f64)
+        .collect()
+}
+
+/// Subtract `means` from every target
+pub fn center(targets: &[Vec<f64>], means: &[f64]) -> Vec<Vec<f64>> {
+    targets
+        .iter()
+        .map(|target| target.iter().zip(means).map(|(value, mean)| value - mean).collect())
+        .collect()
+}
+
+fn gather(inputs: &[Vec<f64>], targets: &[Vec<f64>], indices: &[usize]) -> Batch {
+    indices
+        .iter()
+        .map(|&i| (inputs[i].clone(), targets[i].clone()))
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20 samples: 15 of class 0, 3 of class 1 and 2 of class 2
+    fn imbalanced() -> Batch {
+        (0..20)
+            .map(|i| {
+                let class = match i {
+                    0..15 => 0,
+                    15..18 => 1,
+                    _ => 2,
+                };
+                let mut target = vec![0.0; 3];
+                target[class] = 1.0;
+                (vec![i as f64], target)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_stratified_split_keeps_every_class_on_both_sides() {
+        let (inputs, targets) = imbalanced();
+
+        for seed in 0..20 {
+            let ((train_inputs, train_targets), (val_inputs, val_targets)) =
+                stratified_split(&inputs, &targets, 0.2, seed);
+
+            assert_eq!(train_inputs.len() + val_inputs.len(), 20);
+            for class in 0..3 {
+                let has_class = |targets: &[Vec<f64>]| targets.iter().any(|t| class_of(t) == class);
+                assert!(has_class(&train_targets), "seed {} train lacks class {}", seed, class);
+                assert!(has_class(&val_targets), "seed {} validation lacks class {}", seed, class);
+            }
+            // 20% of the majority class goes to validation
+            assert_eq!(val_targets.iter().filter(|t| class_of(t) == 0).count(), 3);
+        }
+    }
+
+    #[test]
+    fn test_splits_are_deterministic_per_seed() {
+        let (inputs, targets) = imbalanced();
+
+        assert_eq!(
+            stratified_split(&inputs, &targets, 0.25, 7),
+            stratified_split(&inputs, &targets, 0.25, 7)
+        );
+        assert_eq!(
+            random_split(&inputs, &targets, 0.25, 7),
+            random_split(&inputs, &targets, 0.25, 7)
+        );
+        assert_ne!(
+            random_split(&inputs, &targets, 0.25, 7).1,
+            random_split(&inputs, &targets, 0.25, 8).1
+        );
+    }
+
+    #[test]
+    fn test_is_categorical() {
+        assert!(is_categorical(&[vec![0.0], vec![1.0]]));
+        assert!(is_categorical(&[vec![0.0, 1.0], vec![1.0, 0.0]]));
+        assert!(!is_categorical(&[vec![0.5]]));
+        assert!(!is_categorical(&[vec![1.0, 1.0]]));
+        assert!(!is_categorical(&[]));
+    }
+}
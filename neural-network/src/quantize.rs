@@ -0,0 +1,229 @@
+/// Reduced-precision copies of a network for deployment
+///
+/// A `QuantizedNetwork` stores its weights and biases as `f32`, which halves
+/// the size of the parameters in compact encodings such as MessagePack. It
+/// still computes in `f64`, so the only difference from the original network
+/// is the rounding of each parameter. An `I8Network` goes further, storing
+/// its weights as 8-bit integers and computing with them.
+use crate::activations::Activation;
+use crate::matrix::Matrix;
+use crate::network::Network;
+use serde::{Deserialize, Serialize};
+
+/// Expected bound on the absolute difference between the predictions of a
+/// network and its `f32` quantization, for sigmoid networks trained on the
+/// built-in examples (inputs in [0, 1], weights of moderate magnitude)
+pub const F32_PREDICTION_TOLERANCE: f64 = 1e-5;
+
+/// Expected bound on the absolute difference between the predictions of a
+/// network and its `I8Network`, under the same conditions as
+/// `F32_PREDICTION_TOLERANCE`. Each weight and activation is rounded to one
+/// of 255 levels, so outputs near 0.5 may land on either side of a
+/// classification threshold; confident outputs keep their class.
+pub const I8_PREDICTION_TOLERANCE: f64 = 0.05;
+
+/// Largest magnitude of a symmetric `i8` level
+const I8_LEVELS: f64 = 127.0;
+
+/// Quantize `values` to `i8` with a single scale, so `value ~= scale * level`
+///
+/// The scale maps the largest magnitude to 127; all-zero values get a scale
+/// of 1.
+fn to_i8_levels(values: impl Iterator<Item = f64> + Clone) -> (f64, Vec<i8>) {
+    let max = values.clone().fold(0.0, |max: f64, value| max.max(value.abs()));
+    let scale = if max > 0.0 { max / I8_LEVELS } else { 1.0 };
+    let levels = values
+        .map(|value| (value / scale).round().clamp(-I8_LEVELS, I8_LEVELS) as i8)
+        .collect();
+    (scale, levels)
+}
+
+/// A matrix with `f32` entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedMatrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<f32>,
+}
+
+impl QuantizedMatrix {
+    fn quantize(matrix: &Matrix) -> Self {
+        QuantizedMatrix {
+            rows: matrix.rows,
+            cols: matrix.cols,
+            data: matrix.data.iter().map(|&x| x as f32).collect(),
+        }
+    }
+
+    fn dequantize(&self) -> Matrix {
+        Matrix::new(self.rows, self.cols, self.data.iter().map(|&x| x as f64).collect())
+    }
+}
+
+/// A network whose parameters are stored as `f32`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedNetwork {
+    pub layers: Vec<usize>,
+    pub weights: Vec<QuantizedMatrix>,
+    pub biases: Vec<QuantizedMatrix>,
+    pub activations: Vec<Activation>,
+    pub learning_rate: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_offset: Option<Vec<f64>>,
+}
+
+impl Network {
+    /// Round every weight and bias to `f32`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::quantize::F32_PREDICTION_TOLERANCE;
+    ///
+    /// let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+    /// let quantized = network.quantize_f32();
+    ///
+    /// let original = network.predict(&[1.0, 0.0]).unwrap()[0];
+    /// let approximate = quantized.predict(&[1.0, 0.0]).unwrap()[0];
+    /// assert!((original - approximate).abs() < F32_PREDICTION_TOLERANCE);
+    /// ```
+    pub fn quantize_f32(&self) -> QuantizedNetwork {
+        QuantizedNetwork {
+            layers: self.layers.clone(),
+            weights: self.weights.iter().map(QuantizedMatrix::quantize).collect(),
+            biases: self.biases.iter().map(QuantizedMatrix::quantize).collect(),
+            activations: self.activations.clone(),
+            learning_rate: self.learning_rate,
+            output_offset: self.output_offset.clone(),
+        }
+    }
+
+    /// Quantize every weight matrix to `i8` levels for integer inference
+    ///
+    /// The levels and scales are computed once here, so predictions only
+    /// quantize the activations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use neural_network::network::Network;
+    /// use neural_network::activations::SIGMOID;
+    /// use neural_network::quantize::I8_PREDICTION_TOLERANCE;
+    ///
+    /// let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+    /// let quantized = network.quantize_i8();
+    ///
+    /// let original = network.predict(&[1.0, 0.0]).unwrap()[0];
+    /// let approximate = quantized.predict(&[1.0, 0.0]).unwrap()[0];
+    /// assert!((original - approximate).abs() < I8_PREDICTION_TOLERANCE);
+    /// ```
+    pub fn quantize_i8(&self) -> I8Network {
+        I8Network {
+            layers: self.layers.clone(),
+            weights: self.weights.iter().map(I8Matrix::quantize).collect(),
+            biases: self.biases.iter().map(QuantizedMatrix::quantize).collect(),
+            activations: self.activations.clone(),
+            output_offset: self.output_offset.clone(),
+        }
+    }
+}
+
+/// A matrix of `i8` levels sharing one scale, so entry `i` is
+/// approximately `scale * data[i]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct I8Matrix {
+    pub rows: usize,
+    pub cols: usize,
+    pub scale: f64,
+    pub data: Vec<i8>,
+}
+
+impl I8Matrix {
+    fn quantize(matrix: &Matrix) -> Self {
+        let (scale, data) = to_i8_levels(matrix.data.iter().copied());
+        I8Matrix {
+            rows: matrix.rows,
+            cols: matrix.cols,
+            scale,
+            data,
+        }
+    }
+}
+
+/// A network whose weights are stored as `i8` levels with one scale per
+/// weight matrix, for integer inference
+///
+/// Biases are kept as `f32`; they are few and added after the integer sums.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct I8Network {
+    pub layers: Vec<usize>,
+    pub weights: Vec<I8Matrix>,
+    pub biases: Vec<QuantizedMatrix>,
+    pub activations: Vec<Activation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_offset: Option<Vec<f64>>,
+}
+
+impl QuantizedNetwork {
+    /// Expand back to a full-precision network, e.g. to continue training
+    ///
+    /// The rounding is not undone: parameters keep their `f32` values.
+    pub fn dequantize(&self) -> Network {
+        let mut network = Network::from_parameters(
+            self.layers.clone(),
+            self.weights.iter().map(QuantizedMatrix::dequantize).collect(),
+            self.biases.iter().map(QuantizedMatrix::dequantize).collect(),
+            self.activations.clone(),
+            self.learning_rate,
+        );
+        network.output_offset = self.output_offset.clone();
+        network
+    }
+
+    /// Run a forward pass, computing in `f64` from the stored parameters
+    ///
+    /// Inputs are checked like `Network::predict`.
+    pub fn predict(&self, input: &[f64]) -> crate::error::Result<Vec<f64>> {
+        self.dequantize().predict(input)
+    }
+}
+
+impl I8Network {
+    /// Run a forward pass with 8-bit integer matrix multiplication
+    ///
+    /// Each layer's incoming activations are quantized to `i8` with one
+    /// scale, multiplied with the stored weight levels and summed in `i32`,
+    /// and scaled back before the bias and activation are applied in `f64`.
+    /// Predictions stay within `I8_PREDICTION_TOLERANCE` of the original
+    /// network's. Inputs are checked like `Network::predict`.
+    pub fn predict(&self, input: &[f64]) -> crate::error::Result<Vec<f64>> {
+        crate::network::check_input(self.layers[0], input)?;
+
+        let mut current = input.to_vec();
+        for ((weights, biases), activation) in self.weights.iter().zip(&self.biases).zip(&self.activations) {
+            let (input_scale, input_levels) = to_i8_levels(current.iter().copied());
+            let scale = weights.scale * input_scale;
+
+            let mut sums: Vec<f64> = weights
+                .data
+                .chunks(weights.cols)
+                .zip(&biases.data)
+                .map(|(row, &bias)| {
+                    let dot: i32 = row.iter().zip(&input_levels).map(|(&w, &x)| w as i32 * x as i32).sum();
+                    dot as f64 * scale + bias as f64
+                })
+                .collect();
+            activation.apply_in_place(&mut sums);
+            current = sums;
+        }
+
+        if let Some(offset) = &self.output_offset {
+            for (value, mean) in current.iter_mut().zip(offset) {
+                *value += mean;
+            }
+        }
+        Ok(current)
+    }
+}
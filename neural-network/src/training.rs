@@ -1,26 +1,210 @@
 /// Training controller for managing neural network training with callbacks and checkpointing
-use crate::checkpoint::CheckpointMetadata;
+#[cfg(feature = "fs")]
+use crate::checkpoint::{Checkpoint, CheckpointMetadata, SessionRecord};
+use crate::checkpoint::CheckpointFormat;
+use crate::data_source::DataSource;
+use crate::loss::{Loss, LossReduction};
 use crate::network::Network;
+use crate::optimizer::{Optimizer, OptimizerState};
+use crate::preprocessing;
 use crate::matrix::Matrix;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use web_time::Instant;
 
 /// Configuration for training a neural network
+///
+/// Serializable, so a whole training setup can be saved as JSON and
+/// reproduced later. Fields missing from the JSON take their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TrainingConfig {
     pub epochs: u32,
+    /// Save a checkpoint to `checkpoint_path` every this many epochs and
+    /// after the last one. Must be set together with `checkpoint_path`.
     pub checkpoint_interval: Option<u32>,
     pub checkpoint_path: Option<PathBuf>,
+    /// Instead of saving every `checkpoint_interval` epochs, save to
+    /// `checkpoint_path` only after epochs whose training loss is the lowest
+    /// so far, so the file holds the best model rather than the last
+    pub checkpoint_on_best: bool,
     pub verbose: bool,
     pub example_name: Option<String>,
+    /// Loss function used for gradients and reported loss
+    pub loss: Loss,
+    /// Whether the reported loss is per sample (the default) or per output
+    /// element. Affects progress, history, `target_loss` and
+    /// `checkpoint_on_best`, but not gradients.
+    pub loss_reduction: LossReduction,
+    /// Encoding used when writing checkpoints
+    pub checkpoint_format: CheckpointFormat,
+    /// Weight layer indices (0 = input to first hidden) held fixed during
+    /// training. Errors still propagate through them to earlier layers.
+    pub frozen_layers: Vec<usize>,
+    /// Reservoir training, as in an extreme learning machine: every weight
+    /// layer except the output layer is frozen at its random initialization,
+    /// so the hidden layers act as fixed random features and only the linear
+    /// readout learns. The readout converges quickly, so it trains at the
+    /// network's learning rate times `reservoir_lr_scale`. Needs at least one
+    /// hidden layer.
+    pub reservoir: bool,
+    /// Multiplier applied to the learning rate of the readout in reservoir
+    /// training. Reported learning rates stay the network's own.
+    pub reservoir_lr_scale: f64,
+    /// Fraction of the samples held out to measure validation loss each
+    /// epoch. Categorical data is split per class.
+    pub validation_split: Option<f64>,
+    /// Seed for choosing the validation samples
+    pub validation_seed: u64,
+    /// Smoothing factor in (0, 1] for an exponential moving average of the
+    /// loss, reported as `TrainingProgress::loss_ema`. Smaller values give a
+    /// smoother, slower-moving curve.
+    pub loss_ema: Option<f64>,
+    /// Update rule applied to each sample's gradients. Momentum and Adam
+    /// state is saved with checkpoints and restored on resume.
+    pub optimizer: Optimizer,
+    /// Subtract each target's mean before training and store the means as
+    /// the network's `output_offset`, which `predict` adds back. Helps
+    /// regression targets far from zero; the output activation must reach
+    /// negative values, e.g. tanh.
+    pub center_targets: bool,
+    /// Stop as soon as an epoch's training loss drops below this value.
+    /// `epochs` remains the cap when the target is never reached.
+    pub target_loss: Option<f64>,
+    /// Decay in [0, 1) of an exponential moving average of the weights and
+    /// biases, updated after every sample. Values close to 1 average over
+    /// more updates. `TrainingController::apply_ema_weights` swaps the
+    /// average into the network; it is not saved with checkpoints.
+    pub weight_ema_decay: Option<f64>,
+    /// Append each epoch's `TrainingProgress` to this file as one JSON line
+    /// as soon as the epoch completes, so the log survives a crash mid-run.
+    /// An existing file is appended to, not replaced.
+    pub metrics_log_path: Option<PathBuf>,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            epochs: 1000,
+            checkpoint_interval: None,
+            checkpoint_path: None,
+            checkpoint_on_best: false,
+            verbose: false,
+            example_name: None,
+            loss: Loss::default(),
+            loss_reduction: LossReduction::default(),
+            checkpoint_format: CheckpointFormat::default(),
+            frozen_layers: Vec::new(),
+            reservoir: false,
+            reservoir_lr_scale: 5.0,
+            validation_split: None,
+            validation_seed: 0,
+            loss_ema: None,
+            optimizer: Optimizer::default(),
+            center_targets: false,
+            target_loss: None,
+            weight_ema_decay: None,
+            metrics_log_path: None,
+        }
+    }
+}
+
+/// Summary of a completed training run
+#[derive(Debug, Clone)]
+pub struct TrainingReport {
+    /// Loss after the last completed epoch
+    pub final_loss: f64,
+    /// Number of epochs actually run
+    pub epochs_run: u32,
+    /// Wall-clock training time in milliseconds
+    pub duration_ms: u64,
+    /// Progress recorded after each completed epoch
+    pub history: Vec<TrainingProgress>,
+    /// Whether training stopped because the loss dropped below `target_loss`
+    pub target_reached: bool,
+}
+
+/// Statistics for a single completed epoch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrainingProgress {
+    pub epoch: u32,
+    /// Loss over the training set after the epoch
+    pub loss: f64,
+    /// Mean over the epoch's samples of the global L2 gradient norm
+    pub gradient_norm: f64,
+    /// Loss over the held-out validation samples, when `validation_split` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub val_loss: Option<f64>,
+    /// Exponential moving average of `loss`, when `loss_ema` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loss_ema: Option<f64>,
+    /// Learning rate the network had at the end of the epoch
+    pub learning_rate: f64,
 }
 
 /// Callback function type for training progress
 pub type TrainingCallback = Box<dyn FnMut(u32, f64, &Network)>;
 
+/// Callback receiving the full per-epoch statistics
+pub type ProgressCallback = Box<dyn FnMut(&TrainingProgress, &Network)>;
+
+/// Callback run before each epoch with the epoch number and mutable access
+/// to the network
+pub type PreEpochCallback = Box<dyn FnMut(u32, &mut Network)>;
+
 /// Controller for training neural networks with advanced features
 pub struct TrainingController {
     network: Network,
     config: TrainingConfig,
+    optimizer: OptimizerState,
     callbacks: Vec<TrainingCallback>,
+    progress_callbacks: Vec<ProgressCallback>,
+    pre_epoch_callbacks: Vec<PreEpochCallback>,
+    stop_flag: Option<Arc<AtomicBool>>,
+    /// Lowest epoch loss seen so far
+    best_loss: Option<f64>,
+    /// Progress of every epoch this controller has trained
+    history: Vec<TrainingProgress>,
+    /// Moving average of the parameters, once `weight_ema_decay` has seen
+    /// an update
+    weight_ema: Option<WeightAverage>,
+    /// Session history of the checkpoint this controller resumed from, to
+    /// which saved checkpoints append the current session; `None` for a
+    /// fresh run
+    #[cfg(feature = "fs")]
+    resumed_sessions: Option<Vec<SessionRecord>>,
+    /// File behind `metrics_log_path`, opened at the first logged epoch
+    #[cfg(feature = "fs")]
+    metrics_log: Option<std::fs::File>,
+}
+
+/// Exponential moving average of a network's weights and biases
+#[derive(Debug, Clone)]
+struct WeightAverage {
+    weights: Vec<Matrix>,
+    biases: Vec<Matrix>,
+}
+
+impl WeightAverage {
+    fn new(network: &Network) -> Self {
+        Self {
+            weights: network.weights.clone(),
+            biases: network.biases.clone(),
+        }
+    }
+
+    /// Move the average towards the network's current parameters
+    fn update(&mut self, network: &Network, decay: f64) {
+        let averaged = self.weights.iter_mut().chain(&mut self.biases);
+        let current = network.weights.iter().chain(&network.biases);
+        for (average, current) in averaged.zip(current) {
+            for (a, &x) in average.data.iter_mut().zip(&current.data) {
+                *a = decay * *a + (1.0 - decay) * x;
+            }
+        }
+    }
 }
 
 impl TrainingController {
@@ -28,8 +212,19 @@ impl TrainingController {
     pub fn new(network: Network, config: TrainingConfig) -> Self {
         Self {
             network,
+            optimizer: OptimizerState::new(config.optimizer),
             config,
             callbacks: Vec::new(),
+            progress_callbacks: Vec::new(),
+            pre_epoch_callbacks: Vec::new(),
+            stop_flag: None,
+            best_loss: None,
+            history: Vec::new(),
+            weight_ema: None,
+            #[cfg(feature = "fs")]
+            resumed_sessions: None,
+            #[cfg(feature = "fs")]
+            metrics_log: None,
         }
     }
 
@@ -38,87 +233,709 @@ impl TrainingController {
         self.callbacks.push(callback);
     }
 
-    /// Calculate mean squared error loss
+    /// Add a callback receiving `TrainingProgress` (including the gradient
+    /// norm) after each epoch
+    pub fn add_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress_callbacks.push(callback);
+    }
+
+    /// Add a callback to be called at the start of each epoch
+    ///
+    /// The callback can change the network before the epoch trains on it,
+    /// e.g. setting `learning_rate` for a custom schedule; the change applies
+    /// to that epoch's updates.
+    pub fn add_pre_epoch_callback(&mut self, callback: PreEpochCallback) {
+        self.pre_epoch_callbacks.push(callback);
+    }
+
+    /// Stop training early once `flag` is set
+    ///
+    /// The flag is checked before each epoch, so training ends after the
+    /// epoch in progress completes. The report reflects the epochs actually run.
+    pub fn set_stop_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.stop_flag = Some(flag);
+    }
+
+    fn stop_requested(&self) -> bool {
+        self.stop_flag
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Calculate the configured loss averaged over samples
     fn calculate_loss(&mut self, inputs: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
         let mut total_loss = 0.0;
         for i in 0..inputs.len() {
             let output = self.network.feed_forward(Matrix::from(inputs[i].clone()));
-            let target = Matrix::from(targets[i].clone());
-
-            // Calculate MSE
-            for j in 0..output.data.len() {
-                let error = target.data[j] - output.data[j];
-                total_loss += error * error;
-            }
+            total_loss += self.sample_loss(&output.data, &targets[i]);
         }
         total_loss / (inputs.len() as f64)
     }
 
     /// Train the network with the configured settings
+    ///
+    /// Returns a `TrainingReport` with the final loss, the number of epochs
+    /// run, the elapsed time and the per-epoch history. With
+    /// `validation_split` set, the held-out samples are not trained on and
+    /// the final loss covers only the training samples.
     pub fn train(
         &mut self,
         inputs: Vec<Vec<f64>>,
         targets: Vec<Vec<f64>>,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<TrainingReport> {
+        self.train_weighted(inputs, targets, None)
+    }
+
+    /// Train like `train`, scaling each sample's gradient by its weight
+    ///
+    /// `sample_weights` holds one non-negative weight per sample, so a
+    /// curriculum can fade samples in: a weight of 0 leaves a sample out of
+    /// the updates entirely, including Momentum and Adam state and the weight
+    /// average, and 1 trains on it normally. Losses stay unweighted. `None`
+    /// weights every sample by 1.
+    pub fn train_weighted(
+        &mut self,
+        inputs: Vec<Vec<f64>>,
+        targets: Vec<Vec<f64>>,
+        sample_weights: Option<Vec<f64>>,
+    ) -> anyhow::Result<TrainingReport> {
+        self.validate_config()?;
+        if let Some(weights) = &sample_weights {
+            anyhow::ensure!(
+                weights.len() == inputs.len(),
+                "Got {} sample weights for {} samples",
+                weights.len(),
+                inputs.len()
+            );
+            anyhow::ensure!(
+                weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+                "Sample weights must be finite and non-negative"
+            );
+        }
+        let targets = self.center_targets(targets);
+
+        let (inputs, targets, sample_weights, validation) = match self.config.validation_split {
+            Some(ratio) => {
+                anyhow::ensure!(
+                    ratio > 0.0 && ratio < 1.0,
+                    "validation_split must be between 0 and 1, got {}",
+                    ratio
+                );
+                let (train, validation) =
+                    preprocessing::validation_split_indices(&targets, ratio, self.config.validation_seed);
+                anyhow::ensure!(
+                    !train.is_empty() && !validation.is_empty(),
+                    "validation_split {} leaves no training or validation samples",
+                    ratio
+                );
+                let pick = |rows: &[Vec<f64>], indices: &[usize]| -> Vec<Vec<f64>> {
+                    indices.iter().map(|&i| rows[i].clone()).collect()
+                };
+                let sample_weights = sample_weights
+                    .map(|weights| train.iter().map(|&i| weights[i]).collect());
+                (
+                    pick(&inputs, &train),
+                    pick(&targets, &train),
+                    sample_weights,
+                    Some((pick(&inputs, &validation), pick(&targets, &validation))),
+                )
+            }
+            None => (inputs, targets, sample_weights, None),
+        };
+
+        let start = Instant::now();
+        let mut final_loss = None;
+        let mut epochs_run = 0;
+        let first_entry = self.history.len();
+        let mut loss_ema = None;
+        let mut target_reached = false;
+
         for epoch in 1..=self.config.epochs {
+            if self.stop_requested() {
+                break;
+            }
+            self.start_epoch(epoch);
+
             // Train one epoch
-            for j in 0..inputs.len() {
-                let outputs = self.network.feed_forward(Matrix::from(inputs[j].clone()));
-                self.network.back_propogate(outputs, Matrix::from(targets[j].clone()));
+            let mut gradient_norm_sum = 0.0;
+            for (i, (input, target)) in inputs.iter().zip(&targets).enumerate() {
+                let weight = sample_weights.as_ref().map_or(1.0, |weights| weights[i]);
+                let (_, gradient_norm) = self.train_sample(input, target, weight);
+                gradient_norm_sum += gradient_norm;
             }
 
             // Calculate loss for callbacks
             let loss = self.calculate_loss(&inputs, &targets);
+            final_loss = Some(loss);
+            epochs_run = epoch;
+            loss_ema = self.smooth_loss(loss_ema, loss);
+
+            let progress = TrainingProgress {
+                epoch,
+                loss,
+                gradient_norm: if inputs.is_empty() {
+                    0.0
+                } else {
+                    gradient_norm_sum / inputs.len() as f64
+                },
+                val_loss: validation
+                    .as_ref()
+                    .map(|(inputs, targets)| self.calculate_loss(inputs, targets)),
+                loss_ema,
+                learning_rate: self.network.learning_rate,
+            };
+            self.finish_epoch(&progress)?;
+            if self.reached_target(loss) {
+                target_reached = true;
+                break;
+            }
+        }
+
+        let final_loss = match final_loss {
+            Some(loss) => loss,
+            None => self.calculate_loss(&inputs, &targets),
+        };
+
+        Ok(TrainingReport {
+            final_loss,
+            epochs_run,
+            duration_ms: start.elapsed().as_millis() as u64,
+            history: self.history[first_entry..].to_vec(),
+            target_reached,
+        })
+    }
+
+    /// Train on batches read from a `DataSource`
+    ///
+    /// Every epoch rewinds the source and trains on each of its batches in
+    /// turn, so only one batch needs to be in memory at a time. Since a
+    /// second pass over the data would double the reading, the reported loss
+    /// for an epoch is the mean loss of each sample just before the network
+    /// was updated on it.
+    pub fn train_from_source(
+        &mut self,
+        source: &mut impl DataSource,
+    ) -> anyhow::Result<TrainingReport> {
+        self.validate_config()?;
+        anyhow::ensure!(
+            self.config.validation_split.is_none(),
+            "validation_split is not supported when training from a data source"
+        );
+        anyhow::ensure!(
+            !self.config.center_targets || self.network.output_offset.is_some(),
+            "center_targets is not supported when training from a data source"
+        );
+
+        let start = Instant::now();
+        let mut final_loss = None;
+        let mut epochs_run = 0;
+        let first_entry = self.history.len();
+        let mut loss_ema = None;
+        let mut target_reached = false;
+
+        for epoch in 1..=self.config.epochs {
+            if self.stop_requested() {
+                break;
+            }
+            self.start_epoch(epoch);
 
-            // Verbose output
-            if self.config.verbose
-                && (self.config.epochs < 100 || epoch % (self.config.epochs / 100) == 0) {
-                    println!("Epoch {} of {}: loss = {:.6}", epoch, self.config.epochs, loss);
+            source.reset()?;
+            let mut samples = 0;
+            let mut loss_sum = 0.0;
+            let mut gradient_norm_sum = 0.0;
+            while let Some((inputs, targets)) = source.next_batch() {
+                anyhow::ensure!(
+                    inputs.len() == targets.len(),
+                    "Batch has {} inputs but {} targets",
+                    inputs.len(),
+                    targets.len()
+                );
+                let targets = match &self.network.output_offset {
+                    Some(offset) => preprocessing::center(&targets, offset),
+                    None => targets,
+                };
+                for (input, target) in inputs.iter().zip(&targets) {
+                    let (loss, gradient_norm) = self.train_sample(input, target, 1.0);
+                    loss_sum += loss;
+                    gradient_norm_sum += gradient_norm;
                 }
+                samples += inputs.len();
+            }
+            if let Some(e) = source.take_error() {
+                return Err(e.context(format!("Failed to read training data in epoch {}", epoch)));
+            }
+            anyhow::ensure!(samples > 0, "Data source produced no samples");
+
+            let loss = loss_sum / samples as f64;
+            final_loss = Some(loss);
+            epochs_run = epoch;
+            loss_ema = self.smooth_loss(loss_ema, loss);
+
+            let progress = TrainingProgress {
+                epoch,
+                loss,
+                gradient_norm: gradient_norm_sum / samples as f64,
+                val_loss: None,
+                loss_ema,
+                learning_rate: self.network.learning_rate,
+            };
+            self.finish_epoch(&progress)?;
+            if self.reached_target(loss) {
+                target_reached = true;
+                break;
+            }
+        }
+
+        let final_loss = match final_loss {
+            Some(loss) => loss,
+            None => self.source_loss(source)?,
+        };
+
+        Ok(TrainingReport {
+            final_loss,
+            epochs_run,
+            duration_ms: start.elapsed().as_millis() as u64,
+            history: self.history[first_entry..].to_vec(),
+            target_reached,
+        })
+    }
+
+    fn validate_config(&self) -> anyhow::Result<()> {
+        // One without the other would silently skip checkpointing
+        let config = &self.config;
+        match (config.checkpoint_interval, &config.checkpoint_path, config.checkpoint_on_best) {
+            (Some(0), _, _) => anyhow::bail!("checkpoint_interval must be at least 1"),
+            (Some(_), _, true) => {
+                anyhow::bail!("checkpoint_interval and checkpoint_on_best can't be combined")
+            }
+            (Some(interval), None, false) => anyhow::bail!(
+                "checkpoint_interval {} is set without a checkpoint_path to write to",
+                interval
+            ),
+            (None, None, true) => {
+                anyhow::bail!("checkpoint_on_best is set without a checkpoint_path to write to")
+            }
+            (None, Some(path), false) => anyhow::bail!(
+                "checkpoint_path {} is set without a checkpoint_interval or checkpoint_on_best",
+                path.display()
+            ),
+            _ => {}
+        }
+        #[cfg(not(feature = "fs"))]
+        anyhow::ensure!(
+            self.config.checkpoint_path.is_none(),
+            "Writing checkpoints to a file needs the fs feature"
+        );
+        #[cfg(not(feature = "fs"))]
+        anyhow::ensure!(
+            self.config.metrics_log_path.is_none(),
+            "Writing a metrics log needs the fs feature"
+        );
+
+        if let Some(alpha) = self.config.loss_ema {
+            anyhow::ensure!(
+                alpha > 0.0 && alpha <= 1.0,
+                "loss_ema must be in (0, 1], got {}",
+                alpha
+            );
+        }
+        if let Some(decay) = self.config.weight_ema_decay {
+            anyhow::ensure!(
+                (0.0..1.0).contains(&decay),
+                "weight_ema_decay must be in [0, 1), got {}",
+                decay
+            );
+        }
+        if let Some(target) = self.config.target_loss {
+            anyhow::ensure!(
+                target > 0.0 && target.is_finite(),
+                "target_loss must be positive, got {}",
+                target
+            );
+        }
+        if let Loss::Huber { delta } = self.config.loss {
+            anyhow::ensure!(
+                delta > 0.0 && delta.is_finite(),
+                "Huber delta must be positive and finite, got {}",
+                delta
+            );
+        }
+
+        let weight_layers = self.network.weights.len();
+        if let Some(&layer) = self.config.frozen_layers.iter().find(|&&l| l >= weight_layers) {
+            anyhow::bail!(
+                "Cannot freeze layer {}: network only has weight layers 0..{}",
+                layer,
+                weight_layers
+            );
+        }
+        anyhow::ensure!(
+            !self.config.reservoir || weight_layers > 1,
+            "Reservoir training needs a hidden layer to freeze"
+        );
+        anyhow::ensure!(
+            self.config.reservoir_lr_scale > 0.0 && self.config.reservoir_lr_scale.is_finite(),
+            "reservoir_lr_scale must be positive and finite, got {}",
+            self.config.reservoir_lr_scale
+        );
+        Ok(())
+    }
 
-            // Call callbacks
-            for callback in &mut self.callbacks {
-                callback(epoch, loss, &self.network);
+    /// Express targets relative to the network's output offset, first
+    /// setting the offset to the target means when `center_targets` is on
+    ///
+    /// A network resumed with an offset keeps it, centering or not.
+    fn center_targets(&mut self, targets: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        if self.config.center_targets && self.network.output_offset.is_none() && !targets.is_empty() {
+            self.network.output_offset = Some(preprocessing::target_means(&targets));
+        }
+        match &self.network.output_offset {
+            Some(offset) => preprocessing::center(&targets, offset),
+            None => targets,
+        }
+    }
+
+    /// Whether an epoch with this training loss meets `target_loss`
+    fn reached_target(&self, loss: f64) -> bool {
+        self.config.target_loss.is_some_and(|target| loss < target)
+    }
+
+    /// Fold this epoch's loss into the moving average, starting from the
+    /// first epoch's loss
+    fn smooth_loss(&self, previous: Option<f64>, loss: f64) -> Option<f64> {
+        let alpha = self.config.loss_ema?;
+        Some(match previous {
+            Some(previous) => alpha * loss + (1.0 - alpha) * previous,
+            None => loss,
+        })
+    }
+
+    /// Loss of one sample, normalized by `loss_reduction`
+    fn sample_loss(&self, output: &[f64], target: &[f64]) -> f64 {
+        let loss = self.config.loss.compute(output, target);
+        self.config.loss_reduction.normalize(loss, target.len())
+    }
+
+    /// Update the network on one sample with its gradient scaled by
+    /// `weight`, returning its loss before the update and the gradient norm
+    fn train_sample(&mut self, input: &[f64], target: &[f64], weight: f64) -> (f64, f64) {
+        let outputs = self.network.feed_forward(Matrix::from(input.to_vec()));
+        let loss = self.sample_loss(&outputs.data, target);
+        let errors = self.config.loss.output_errors(&outputs, &Matrix::from(target.to_vec()));
+        let mut gradients = self.network.compute_gradients(&outputs, errors);
+        gradients.scale(weight);
+        let gradient_norm = gradients.norm();
+        if weight == 0.0 {
+            // An optimizer step would still move the parameters along the
+            // stored momentum, so skip it altogether
+            return (loss, gradient_norm);
+        }
+        let mut frozen = self.config.frozen_layers.clone();
+        let learning_rate = self.network.learning_rate;
+        if self.config.reservoir {
+            frozen.extend(0..self.network.weights.len() - 1);
+            // Only the readout moves, so scaling the whole step scales its rate
+            self.network.learning_rate *= self.config.reservoir_lr_scale;
+        }
+        self.optimizer.apply_frozen(&mut self.network, gradients, &frozen);
+        self.network.learning_rate = learning_rate;
+        if let Some(decay) = self.config.weight_ema_decay {
+            match &mut self.weight_ema {
+                Some(average) => average.update(&self.network, decay),
+                None => self.weight_ema = Some(WeightAverage::new(&self.network)),
             }
+        }
+        (loss, gradient_norm)
+    }
+
+    /// Mean loss over one pass of a data source, without training
+    fn source_loss(&mut self, source: &mut impl DataSource) -> anyhow::Result<f64> {
+        source.reset()?;
+        let mut samples = 0;
+        let mut total_loss = 0.0;
+        while let Some((inputs, targets)) = source.next_batch() {
+            total_loss += self.calculate_loss(&inputs, &targets) * inputs.len() as f64;
+            samples += inputs.len();
+        }
+        if let Some(e) = source.take_error() {
+            return Err(e);
+        }
+        anyhow::ensure!(samples > 0, "Data source produced no samples");
+        Ok(total_loss / samples as f64)
+    }
+
+    /// Run the pre-epoch callbacks for `epoch`
+    fn start_epoch(&mut self, epoch: u32) {
+        for callback in &mut self.pre_epoch_callbacks {
+            callback(epoch, &mut self.network);
+        }
+    }
+
+    /// Report a completed epoch: verbose output, callbacks and checkpoints
+    fn finish_epoch(&mut self, progress: &TrainingProgress) -> anyhow::Result<()> {
+        let epoch = progress.epoch;
+        let loss = progress.loss;
 
-            // Save checkpoint if needed
-            if let (Some(interval), Some(path)) = (self.config.checkpoint_interval, &self.config.checkpoint_path)
-                && epoch % interval == 0 {
-                    let metadata = CheckpointMetadata {
-                        version: "1.0".to_string(),
-                        example: self.config.example_name.clone().unwrap_or_else(|| "training".to_string()),
-                        epoch,
-                        total_epochs: self.config.epochs,
-                        learning_rate: self.network.learning_rate,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                    };
-                    self.network.save_checkpoint(path, metadata)?;
+        // Verbose output
+        if self.config.verbose
+            && (self.config.epochs < 100 || epoch.is_multiple_of(self.config.epochs / 100)) {
+                match progress.loss_ema {
+                    Some(ema) => println!(
+                        "Epoch {} of {}: loss = {:.6} (smoothed {:.6})",
+                        epoch, self.config.epochs, loss, ema
+                    ),
+                    None => println!("Epoch {} of {}: loss = {:.6}", epoch, self.config.epochs, loss),
                 }
+            }
+
+        // Call callbacks
+        for callback in &mut self.callbacks {
+            callback(epoch, loss, &self.network);
+        }
+        for callback in &mut self.progress_callbacks {
+            callback(progress, &self.network);
         }
 
+        self.history.push(*progress);
+        #[cfg(feature = "fs")]
+        self.log_metrics(progress)?;
+
+        let improved = loss.is_finite() && self.best_loss.is_none_or(|best| loss < best);
+        if improved {
+            self.best_loss = Some(loss);
+        }
+
+        // The last epoch is always checkpointed, including one that reaches the target loss
+        #[cfg(feature = "fs")]
+        self.save_checkpoint(epoch, epoch == self.config.epochs || self.reached_target(loss), improved)?;
+
+        Ok(())
+    }
+
+    /// Append `progress` to the metrics log as one JSON line, if one is configured
+    ///
+    /// Each line goes to the file in a single unbuffered write, so every
+    /// completed epoch is on disk even if the process dies later.
+    #[cfg(feature = "fs")]
+    fn log_metrics(&mut self, progress: &TrainingProgress) -> anyhow::Result<()> {
+        use anyhow::Context;
+        use std::io::Write;
+
+        let Some(path) = &self.config.metrics_log_path else {
+            return Ok(());
+        };
+        let file = match &mut self.metrics_log {
+            Some(file) => file,
+            None => {
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open metrics log {}", path.display()))?;
+                self.metrics_log.insert(file)
+            }
+        };
+        let mut line = serde_json::to_string(progress).context("Failed to serialize metrics")?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write metrics log {}", path.display()))
+    }
+
+    /// Save a checkpoint if one is due after `epoch`
+    ///
+    /// With `checkpoint_on_best` one is due when the epoch `improved` on the
+    /// best loss; otherwise every `checkpoint_interval` epochs and after the
+    /// `last` one.
+    #[cfg(feature = "fs")]
+    fn save_checkpoint(&self, epoch: u32, last: bool, improved: bool) -> anyhow::Result<()> {
+        let due = match self.config.checkpoint_interval {
+            _ if self.config.checkpoint_on_best => improved,
+            Some(interval) => epoch.is_multiple_of(interval) || last,
+            None => false,
+        };
+        if let Some(path) = &self.config.checkpoint_path
+            && due {
+                let timestamp = chrono::Utc::now().to_rfc3339();
+                let history = match &self.resumed_sessions {
+                    Some(sessions) => {
+                        let mut history = sessions.clone();
+                        history.push(SessionRecord {
+                            epochs: epoch,
+                            learning_rate: self.network.learning_rate,
+                            timestamp: timestamp.clone(),
+                        });
+                        history
+                    }
+                    None => Vec::new(),
+                };
+                let metadata = CheckpointMetadata {
+                    version: "1.0".to_string(),
+                    example: self.config.example_name.clone().unwrap_or_else(|| "training".to_string()),
+                    epoch,
+                    total_epochs: self.config.epochs,
+                    learning_rate: self.network.learning_rate,
+                    timestamp,
+                    history,
+                };
+                let mut checkpoint = self.network.to_checkpoint(metadata);
+                if self.config.optimizer != Optimizer::Sgd {
+                    checkpoint.optimizer = Some(self.optimizer.clone());
+                }
+                checkpoint.save(path, self.config.checkpoint_format)?;
+            }
+
         Ok(())
     }
 
+    /// Lowest epoch loss seen by this controller, if it has trained
+    pub fn best_loss(&self) -> Option<f64> {
+        self.best_loss
+    }
+
+    /// Progress of every epoch this controller has trained, across all
+    /// `train` calls
+    pub fn history(&self) -> &[TrainingProgress] {
+        &self.history
+    }
+
+    /// Write `history` to a file as a JSON array, one object per epoch
+    #[cfg(feature = "fs")]
+    pub fn save_history(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let json = serde_json::to_string_pretty(&self.history).context("Failed to serialize history")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write history to {}", path.display()))
+    }
+
     /// Get a reference to the trained network
     pub fn network(&self) -> &Network {
         &self.network
     }
 
     /// Create a training controller from a checkpoint file
+    ///
+    /// Optimizer state saved in the checkpoint is restored when it belongs to
+    /// the configured optimizer; otherwise the optimizer starts fresh.
+    /// Checkpoints this controller saves carry the loaded session history
+    /// plus a `SessionRecord` for this run.
+    #[cfg(feature = "fs")]
     pub fn from_checkpoint(
         checkpoint_path: &std::path::Path,
         config: TrainingConfig,
     ) -> anyhow::Result<Self> {
-        let (network, _metadata) = Network::load_checkpoint(checkpoint_path)?;
+        let checkpoint = Checkpoint::load(checkpoint_path)?;
+        let optimizer = checkpoint
+            .optimizer
+            .filter(|state| state.optimizer == config.optimizer)
+            .unwrap_or_else(|| OptimizerState::new(config.optimizer));
         Ok(Self {
-            network,
+            network: checkpoint.network,
             config,
+            optimizer,
             callbacks: Vec::new(),
+            progress_callbacks: Vec::new(),
+            pre_epoch_callbacks: Vec::new(),
+            stop_flag: None,
+            best_loss: None,
+            history: Vec::new(),
+            weight_ema: None,
+            resumed_sessions: Some(checkpoint.metadata.history),
+            metrics_log: None,
         })
     }
 
+    /// Swap the moving average of the weights and biases into the network
+    ///
+    /// The average is often a more stable final model than the last update.
+    /// The replaced parameters take the average's place, so calling this
+    /// again swaps them back. Fails unless `weight_ema_decay` is set and
+    /// training has updated the network.
+    pub fn apply_ema_weights(&mut self) -> anyhow::Result<()> {
+        let average = self.weight_ema.as_mut().ok_or_else(|| {
+            anyhow::anyhow!("No weight average: set weight_ema_decay and train first")
+        })?;
+        std::mem::swap(&mut self.network.weights, &mut average.weights);
+        std::mem::swap(&mut self.network.biases, &mut average.biases);
+        Ok(())
+    }
+
     /// Consume the controller and return the network
     pub fn into_network(self) -> Network {
         self.network
     }
 }
+
+/// Estimate how well a setup generalizes with k-fold cross-validation
+///
+/// Sample `i` goes to fold `i % k`, so data ordered by class is spread over
+/// the folds. For each fold a fresh network from `network_factory` is
+/// trained with `config` on the other folds and scored on the held-out one
+/// with the configured loss. Returns the `k` validation losses in fold order.
+///
+/// The folds would all write to the same file, so a config with a
+/// `checkpoint_path` or `metrics_log_path` is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use neural_network::activations::SIGMOID;
+/// use neural_network::network::Network;
+/// use neural_network::training::{TrainingConfig, cross_validate};
+///
+/// let inputs = vec![vec![0.0], vec![1.0], vec![0.0], vec![1.0]];
+/// let targets = vec![vec![0.0], vec![1.0], vec![0.0], vec![1.0]];
+/// let config = TrainingConfig { epochs: 10, ..Default::default() };
+/// let factory = || Network::new_seeded(vec![1, 2, 1], SIGMOID, 0.5, 1);
+/// let losses = cross_validate(factory, &inputs, &targets, 2, &config).unwrap();
+/// assert_eq!(losses.len(), 2);
+/// ```
+pub fn cross_validate(
+    mut network_factory: impl FnMut() -> Network,
+    inputs: &[Vec<f64>],
+    targets: &[Vec<f64>],
+    k: usize,
+    config: &TrainingConfig,
+) -> anyhow::Result<Vec<f64>> {
+    anyhow::ensure!(
+        inputs.len() == targets.len(),
+        "Got {} inputs but {} targets",
+        inputs.len(),
+        targets.len()
+    );
+    anyhow::ensure!(
+        k >= 2 && k <= inputs.len(),
+        "Cross-validation needs between 2 and {} folds, got {}",
+        inputs.len(),
+        k
+    );
+    anyhow::ensure!(
+        config.checkpoint_path.is_none() && config.metrics_log_path.is_none(),
+        "Cross-validation can't use a checkpoint_path or metrics_log_path, which every fold would write to"
+    );
+
+    (0..k)
+        .map(|fold| {
+            let pick = |rows: &[Vec<f64>], held_out: bool| -> Vec<Vec<f64>> {
+                rows.iter()
+                    .enumerate()
+                    .filter(|(i, _)| (i % k == fold) == held_out)
+                    .map(|(_, row)| row.clone())
+                    .collect()
+            };
+
+            let mut controller = TrainingController::new(network_factory(), config.clone());
+            controller.train(pick(inputs, false), pick(targets, false))?;
+
+            // `predict` adds back any target centering, so compare with raw targets
+            let (fold_inputs, fold_targets) = (pick(inputs, true), pick(targets, true));
+            let loss = fold_inputs
+                .iter()
+                .zip(&fold_targets)
+                .map(|(input, target)| controller.sample_loss(&controller.network.forward(input), target))
+                .sum::<f64>()
+                / fold_inputs.len() as f64;
+            Ok(loss)
+        })
+        .collect()
+}
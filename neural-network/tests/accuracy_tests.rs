@@ -0,0 +1,48 @@
+// Integration tests for Network::accuracy in its classification and regression modes
+use neural_network::activations::{SIGMOID, TANH};
+use neural_network::metrics::AccuracyMode;
+use neural_network::network::Network;
+
+#[test]
+fn test_regression_accuracy_counts_outputs_within_tolerance() {
+    let network = Network::new_seeded(vec![1, 4, 1], TANH, 0.1, 11);
+    let inputs: Vec<Vec<f64>> = [-1.0, -0.5, 0.0, 0.5].iter().map(|&x| vec![x]).collect();
+    let outputs: Vec<f64> = inputs.iter().map(|input| network.predict(input).unwrap()[0]).collect();
+
+    // Two targets close to the output, two far from it
+    let targets: Vec<Vec<f64>> = outputs
+        .iter()
+        .zip([0.05, -0.05, 0.3, -0.3])
+        .map(|(output, offset)| vec![output + offset])
+        .collect();
+
+    let mode = AccuracyMode::Regression { tolerance: 0.1 };
+    assert_eq!(network.accuracy(&inputs, &targets, mode), 0.5);
+    let loose = AccuracyMode::Regression { tolerance: 0.5 };
+    assert_eq!(network.accuracy(&inputs, &targets, loose), 1.0);
+}
+
+#[test]
+fn test_classification_accuracy_thresholds_single_outputs() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 2);
+    let input = vec![vec![0.0, 1.0]];
+    let class = (network.predict(&input[0]).unwrap()[0] >= 0.5) as u8 as f64;
+
+    let mode = AccuracyMode::Classification;
+    assert_eq!(network.accuracy(&input, &[vec![class]], mode), 1.0);
+    assert_eq!(network.accuracy(&input, &[vec![1.0 - class]], mode), 0.0);
+}
+
+#[test]
+fn test_nan_outputs_count_as_incorrect() {
+    let mut network = Network::new_seeded(vec![2, 3, 2], SIGMOID, 0.5, 2);
+    network.weights[1].data[0] = f64::NAN;
+    let inputs = vec![vec![1.0, 1.0], vec![0.0, 1.0]];
+    // Skipping the NaN, argmax would pick the target class
+    let targets = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+    assert!(network.predict(&inputs[0]).unwrap()[0].is_nan());
+
+    assert_eq!(network.accuracy(&inputs, &targets, AccuracyMode::Classification), 0.0);
+    let mode = AccuracyMode::Regression { tolerance: 1.0 };
+    assert_eq!(network.accuracy(&inputs, &targets, mode), 0.0);
+}
@@ -1,7 +1,9 @@
 // Integration tests for checkpoint functionality
+#![cfg(feature = "fs")]
+
 use neural_network::network::Network;
-use neural_network::activations::SIGMOID;
-use neural_network::checkpoint::CheckpointMetadata;
+use neural_network::activations::{RELU, SIGMOID, TANH};
+use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
 use std::fs;
 use tempfile::TempDir;
 
@@ -21,6 +23,7 @@ fn test_create_checkpoint() {
         total_epochs: 10000,
         learning_rate: 0.5,
         timestamp: "2025-10-13T12:00:00Z".to_string(),
+        history: Vec::new(),
     };
 
     let checkpoint = network.to_checkpoint(metadata.clone());
@@ -46,6 +49,7 @@ fn test_checkpoint_from_network() {
         total_epochs: 100,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     let checkpoint = network.to_checkpoint(metadata);
@@ -74,6 +78,7 @@ fn test_save_and_load_checkpoint() {
         total_epochs: 1000,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     // Save checkpoint
@@ -111,6 +116,7 @@ fn test_checkpoint_preserves_predictions() {
         total_epochs: 1000,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     network.save_checkpoint(&checkpoint_path, metadata).unwrap();
@@ -140,6 +146,7 @@ fn test_checkpoint_file_format() {
         total_epochs: 1000,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     network.save_checkpoint(&checkpoint_path, metadata).unwrap();
@@ -173,9 +180,9 @@ fn test_invalid_checkpoint_version() {
             "timestamp": "2025-01-01T00:00:00Z"
         },
         "network": {
-            "layers": [2, 3, 1],
-            "weights": [],
-            "biases": [],
+            "layers": [2, 1],
+            "weights": [{"rows": 1, "cols": 2, "data": [0.1, 0.2]}],
+            "biases": [{"rows": 1, "cols": 1, "data": [0.0]}],
             "activation": "sigmoid",
             "learning_rate": 0.5
         }
@@ -222,6 +229,7 @@ fn test_checkpoint_metadata_fields() {
         total_epochs: 10000,
         learning_rate: 0.5,
         timestamp: "2025-10-13T12:34:56Z".to_string(),
+        history: Vec::new(),
     };
 
     // All fields should be accessible
@@ -258,6 +266,7 @@ fn test_resume_training_from_checkpoint() {
         total_epochs: 500,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     network.save_checkpoint(&checkpoint_path, metadata).unwrap();
@@ -290,6 +299,7 @@ fn test_checkpoint_serialization_is_deterministic() {
         total_epochs: 1000,
         learning_rate: 0.5,
         timestamp: "2025-10-13T12:00:00Z".to_string(), // Fixed timestamp for determinism
+        history: Vec::new(),
     };
 
     network.save_checkpoint(&path1, metadata.clone()).unwrap();
@@ -303,3 +313,95 @@ fn test_checkpoint_serialization_is_deterministic() {
 
     // TempDir automatically cleans up when dropped
 }
+
+/// A checkpoint for a [2, 2, 1] network as written before per-layer
+/// activations, with the given extra network fields
+fn single_activation_checkpoint(activation_fields: &str) -> String {
+    format!(
+        r#"{{
+        "metadata": {{
+            "version": "1.0",
+            "example": "xor",
+            "epoch": 10,
+            "total_epochs": 10,
+            "learning_rate": 0.5,
+            "timestamp": "2025-01-01T00:00:00Z"
+        }},
+        "network": {{
+            "layers": [2, 2, 1],
+            "weights": [
+                {{"rows": 2, "cols": 2, "data": [0.5, -0.5, 0.25, 1.0]}},
+                {{"rows": 1, "cols": 2, "data": [1.5, -2.0]}}
+            ],
+            "biases": [
+                {{"rows": 2, "cols": 1, "data": [0.1, -0.1]}},
+                {{"rows": 1, "cols": 1, "data": [0.2]}}
+            ],
+            {},
+            "learning_rate": 0.5
+        }}
+    }}"#,
+        activation_fields
+    )
+}
+
+#[test]
+fn test_load_single_activation_checkpoint() {
+    let temp_dir = create_temp_dir();
+    let path = temp_dir.path().join("old.json");
+
+    // One activation name applies to every layer
+    fs::write(&path, single_activation_checkpoint(r#""activation": "tanh""#)).unwrap();
+    let (network, _) = Network::load_checkpoint(&path).unwrap();
+    assert_eq!(network.activations, vec![TANH, TANH]);
+    let output = network.predict(&[1.0, 0.0]).unwrap();
+    let hidden = [(0.5f64 + 0.1).tanh(), (0.25f64 - 0.1).tanh()];
+    let expected = (1.5 * hidden[0] - 2.0 * hidden[1] + 0.2).tanh();
+    assert!((output[0] - expected).abs() < 1e-12);
+
+    // A separate output activation overrides the last layer
+    let fields = r#""activation": "tanh", "output_activation": "sigmoid""#;
+    fs::write(&path, single_activation_checkpoint(fields)).unwrap();
+    let (network, _) = Network::load_checkpoint(&path).unwrap();
+    assert_eq!(network.activations, vec![TANH, SIGMOID]);
+
+    fs::write(&path, single_activation_checkpoint(r#""activation": "swish""#)).unwrap();
+    let error = Network::load_checkpoint(&path).unwrap_err();
+    assert!(format!("{:#}", error).contains("Unknown activation function: swish"));
+}
+
+#[test]
+fn test_multi_activation_checkpoint_round_trips() {
+    let temp_dir = create_temp_dir();
+    let network = Network::new_seeded(vec![2, 4, 3, 1], SIGMOID, 0.5, 5)
+        .with_layer_activations(vec![RELU, TANH, SIGMOID]);
+    let metadata = CheckpointMetadata {
+        version: "1.0".to_string(),
+        example: "xor".to_string(),
+        epoch: 0,
+        total_epochs: 100,
+        learning_rate: 0.5,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
+    };
+
+    for (name, format) in [("multi.json", CheckpointFormat::Json), ("multi.bin", CheckpointFormat::Bin)] {
+        let path = temp_dir.path().join(name);
+        network.save_checkpoint_as(&path, metadata.clone(), format).unwrap();
+
+        let (restored, _) = Network::load_checkpoint_as(&path, format).unwrap();
+        assert_eq!(restored.activations, vec![RELU, TANH, SIGMOID]);
+        assert_eq!(restored.predict(&[0.3, -0.7]).unwrap(), network.predict(&[0.3, -0.7]).unwrap());
+    }
+
+    let path = temp_dir.path().join("multi.json");
+    let mut json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json["network"]["activations"], serde_json::json!(["relu", "tanh", "sigmoid"]));
+
+    // The list must cover every weight layer
+    json["network"]["activations"] = serde_json::json!(["relu", "tanh"]);
+    fs::write(&path, json.to_string()).unwrap();
+    let error = Network::load_checkpoint(&path).unwrap_err();
+    assert!(format!("{:#}", error).contains("Expected 3 activations"), "{:#}", error);
+}
+
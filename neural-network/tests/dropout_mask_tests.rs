@@ -0,0 +1,45 @@
+// Integration tests for forward passes with explicit dropout masks
+use neural_network::activations::SIGMOID;
+use neural_network::matrix::Matrix;
+use neural_network::network::Network;
+
+#[test]
+fn test_mask_matches_manually_zeroed_activation() {
+    let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 9);
+    let input = Matrix::from(vec![0.7, -0.2]);
+
+    let masked = network.feed_forward_with_mask(input.clone(), &[vec![true, false, true]]);
+
+    // The same pass by hand, with hidden unit 1 zeroed
+    let mut hidden = network
+        .layer_activation(0)
+        .apply(&network.weights[0].dot_multiply(&input).add(&network.biases[0]));
+    hidden.data[1] = 0.0;
+    let expected = network
+        .layer_activation(1)
+        .apply(&network.weights[1].dot_multiply(&hidden).add(&network.biases[1]));
+
+    assert_eq!(masked, expected);
+    assert_ne!(masked, network.feed_forward(input));
+}
+
+#[test]
+fn test_masks_apply_per_hidden_layer() {
+    let mut network = Network::new_seeded(vec![2, 3, 2, 1], SIGMOID, 0.5, 9);
+    let input = Matrix::from(vec![1.0, 0.0]);
+
+    let all_kept = network.feed_forward_with_mask(input.clone(), &[vec![true; 3], vec![true; 2]]);
+    assert_eq!(all_kept, network.feed_forward(input.clone()));
+
+    // Dropping every unit of the last hidden layer leaves only the output bias
+    let output = network.feed_forward_with_mask(input, &[vec![true; 3], vec![false; 2]]);
+    let bias = network.biases[2].data[0];
+    assert_eq!(output.data, vec![1.0 / (1.0 + (-bias).exp())]);
+}
+
+#[test]
+#[should_panic(expected = "Expected 1 dropout masks, got 0")]
+fn test_missing_mask_panics() {
+    let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 9);
+    network.feed_forward_with_mask(Matrix::from(vec![1.0, 0.0]), &[]);
+}
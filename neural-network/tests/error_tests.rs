@@ -0,0 +1,57 @@
+// Integration tests for the crate's error type
+use neural_network::activations::SIGMOID;
+use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
+use neural_network::error::NnError;
+use neural_network::network::Network;
+
+#[test]
+fn test_bad_shaped_predict_is_dimension_mismatch() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+
+    let error = network.predict(&[1.0, 0.0, 1.0]).unwrap_err();
+
+    assert!(matches!(error, NnError::DimensionMismatch { expected: 2, actual: 3 }));
+    assert_eq!(error.to_string(), "Invalid input dimensions: expected 2, got 3");
+}
+
+#[test]
+fn test_non_finite_input_lists_offending_values() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+
+    let error = network.predict(&[f64::NAN, f64::INFINITY]).unwrap_err();
+
+    assert!(matches!(&error, NnError::NonFinite { values } if values.len() == 2));
+    assert_eq!(error.to_string(), "Input values must be finite, got NaN at index 0, inf at index 1");
+}
+
+#[test]
+fn test_future_checkpoint_is_version_error() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 1);
+    let metadata = CheckpointMetadata {
+        version: "99.0".to_string(),
+        example: "xor".to_string(),
+        epoch: 1,
+        total_epochs: 1,
+        learning_rate: 0.5,
+        timestamp: "2024-01-01T00:00:00Z".to_string(),
+        history: Vec::new(),
+    };
+    let mut bytes = Vec::new();
+    network.write_checkpoint(&mut bytes, metadata, CheckpointFormat::Json).unwrap();
+
+    let error = Network::load_checkpoint_from_slice(&bytes).unwrap_err();
+
+    assert!(matches!(error, NnError::CheckpointVersion { ref found, .. } if found == "99.0"));
+}
+
+#[test]
+fn test_errors_convert_into_anyhow() {
+    fn load(bytes: &[u8]) -> anyhow::Result<Network> {
+        Ok(Network::load_checkpoint_from_slice(bytes)?.0)
+    }
+
+    let error = load(b"NNCK not msgpack").unwrap_err();
+
+    assert!(matches!(error.downcast_ref::<NnError>(), Some(NnError::Serde { .. })));
+    assert!(error.to_string().starts_with("Failed to deserialize checkpoint: "));
+}
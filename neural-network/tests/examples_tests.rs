@@ -1,5 +1,5 @@
 // Integration tests for examples module
-use neural_network::examples::{get_example, list_examples};
+use neural_network::examples::{get_example, list_examples, load_json};
 
 #[test]
 fn test_get_and_example() {
@@ -27,7 +27,7 @@ fn test_get_xor_example() {
 #[test]
 fn test_list_examples() {
     let examples = list_examples();
-    assert_eq!(examples.len(), 3);
+    assert_eq!(examples.len(), 8);
     assert!(examples.contains(&"and"));
     assert!(examples.contains(&"or"));
     assert!(examples.contains(&"xor"));
@@ -146,3 +146,91 @@ fn test_recommended_learning_rate() {
     assert!(ex.recommended_lr > 0.0);
     assert!(ex.recommended_lr <= 1.0);
 }
+
+#[test]
+fn test_load_json_example() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("half_adder.json");
+    std::fs::write(
+        &path,
+        r#"{
+            "name": "half_adder",
+            "description": "Sum and carry of two bits",
+            "inputs": [[0, 0], [0, 1], [1, 0], [1, 1]],
+            "targets": [[0, 0], [1, 0], [1, 0], [0, 1]],
+            "architecture": [2, 4, 2],
+            "learning_rate": 0.3
+        }"#,
+    )
+    .unwrap();
+
+    let ex = load_json(&path).expect("Valid example file should load");
+    assert_eq!(ex.name, "half_adder");
+    assert_eq!(ex.description, "Sum and carry of two bits");
+    assert_eq!(ex.inputs.len(), 4);
+    assert_eq!(ex.targets[3], vec![0.0, 1.0]);
+    assert_eq!(ex.recommended_arch, vec![2, 4, 2]);
+    assert_eq!(ex.recommended_epochs, 10000);
+    assert_eq!(ex.recommended_lr, 0.3);
+}
+
+#[test]
+fn test_load_json_rejects_mismatched_architecture() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bad.json");
+    std::fs::write(
+        &path,
+        r#"{"name": "bad", "inputs": [[0, 1]], "targets": [[1]], "architecture": [2, 3, 2]}"#,
+    )
+    .unwrap();
+
+    let error = load_json(&path).unwrap_err();
+    assert!(
+        format!("{:#}", error).contains("Sample 0 has 2 inputs and 1 targets"),
+        "{:#}",
+        error
+    );
+    assert!(load_json(&dir.path().join("missing.json")).is_err());
+}
+
+#[test]
+fn test_combine_and_or_trains_on_both_gates() {
+    use neural_network::activations::SIGMOID;
+    use neural_network::examples::combine;
+    use neural_network::network::Network;
+    use neural_network::training::{TrainingConfig, TrainingController};
+
+    let gates = combine(&["and", "or"]).unwrap();
+    assert_eq!(gates.inputs.len(), 8);
+    assert_eq!(gates.recommended_arch, vec![2, 2, 1]);
+    assert_eq!(&gates.targets[..4], &get_example("and").unwrap().targets[..]);
+    assert_eq!(&gates.targets[4..], &get_example("or").unwrap().targets[..]);
+
+    let network = Network::new_seeded(gates.recommended_arch.clone(), SIGMOID, gates.recommended_lr, 1);
+    let config = TrainingConfig {
+        epochs: gates.recommended_epochs,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    controller.train(gates.inputs.clone(), gates.targets.clone()).unwrap();
+    let network = controller.into_network();
+
+    // Both gates agree on [0, 0] and [1, 1]; on the other inputs they
+    // disagree, so the best single answer is halfway between them
+    assert!(network.predict(&[0.0, 0.0]).unwrap()[0] < 0.2);
+    assert!(network.predict(&[1.0, 1.0]).unwrap()[0] > 0.8);
+    for input in [[0.0, 1.0], [1.0, 0.0]] {
+        let output = network.predict(&input).unwrap()[0];
+        assert!((output - 0.5).abs() < 0.1, "{:?} -> {}", input, output);
+    }
+}
+
+#[test]
+fn test_combine_rejects_mismatched_dimensions() {
+    use neural_network::examples::combine;
+
+    let error = combine(&["xor", "adder2"]).unwrap_err();
+    assert!(error.to_string().contains("'adder2' has"), "{}", error);
+    assert!(combine(&["and", "nand"]).unwrap_err().to_string().contains("Unknown example 'nand'"));
+    assert!(combine(&[]).is_err());
+}
@@ -0,0 +1,46 @@
+use neural_network::activations::SIGMOID;
+use neural_network::examples;
+use neural_network::metrics::AccuracyMode;
+use neural_network::network::Network;
+use neural_network::training::{TrainingConfig, TrainingController};
+
+#[test]
+fn test_padded_feature_is_unimportant_for_and() {
+    // Every `and` row twice, with a third input that doesn't affect the target
+    let and = examples::get_example("and").unwrap();
+    let mut inputs = Vec::new();
+    let mut targets = Vec::new();
+    for (input, target) in and.inputs.iter().zip(&and.targets) {
+        for padding in [0.0, 1.0] {
+            inputs.push(vec![input[0], input[1], padding]);
+            targets.push(target.clone());
+        }
+    }
+
+    let network = Network::new_seeded(vec![3, 3, 1], SIGMOID, 0.5, 3);
+    let config = TrainingConfig {
+        epochs: 3000,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    controller.train(inputs.clone(), targets.clone()).unwrap();
+    let network = controller.into_network();
+    assert_eq!(network.accuracy(&inputs, &targets, AccuracyMode::Classification), 1.0);
+
+    let importance = network.feature_importance(&inputs, &targets);
+    assert_eq!(importance.len(), 3);
+    assert!(importance[0] > 0.05, "first input should matter: {:?}", importance);
+    assert!(importance[1] > 0.05, "second input should matter: {:?}", importance);
+    assert!(importance[2].abs() < 0.01, "padding should not matter: {:?}", importance);
+}
+
+#[test]
+fn test_feature_importance_is_deterministic() {
+    let quadrant = examples::get_example("quadrant").unwrap();
+    let network = Network::new_seeded(quadrant.recommended_arch.clone(), SIGMOID, 0.5, 1);
+
+    assert_eq!(
+        network.feature_importance(&quadrant.inputs, &quadrant.targets),
+        network.feature_importance(&quadrant.inputs, &quadrant.targets)
+    );
+}
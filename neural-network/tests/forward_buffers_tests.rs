@@ -0,0 +1,46 @@
+// Integration tests for forward passes into preallocated buffers
+use neural_network::activations::{RELU, SIGMOID, SOFTMAX, TANH};
+use neural_network::matrix::Matrix;
+use neural_network::network::{ForwardBuffers, Network};
+
+#[test]
+fn test_buffered_matches_unbuffered_over_many_iterations() {
+    let mut network = Network::new_seeded(vec![3, 5, 4, 2], SIGMOID, 0.5, 11)
+        .with_layer_activations(vec![RELU, TANH, SOFTMAX]);
+    let mut buffers = ForwardBuffers::new(&network);
+
+    for i in 0..500 {
+        let x = i as f64 / 50.0;
+        let input = vec![x.sin(), x.cos() * 2.0, 1.0 - x];
+
+        let buffered = network.feed_forward_buffered(&input, &mut buffers).clone();
+        assert_eq!(buffered, network.feed_forward(Matrix::from(input.clone())));
+        assert_eq!(buffered.data, network.predict(&input).unwrap());
+    }
+}
+
+#[test]
+fn test_buffered_adds_output_offset() {
+    let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 4);
+    network.output_offset = Some(vec![10.0]);
+    let mut buffers = ForwardBuffers::new(&network);
+
+    for input in [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]] {
+        let output = network.feed_forward_buffered(&input, &mut buffers);
+        assert_eq!(output.data, network.predict(&input).unwrap());
+        assert!(output.data[0] > 10.0);
+    }
+}
+
+#[test]
+fn test_mismatched_buffers_are_reallocated() {
+    let small = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 4);
+    let large = Network::new_seeded(vec![4, 6, 2], SIGMOID, 0.5, 4);
+    let mut buffers = ForwardBuffers::new(&small);
+    assert!(!buffers.fits(&large));
+
+    let output = large.feed_forward_buffered(&[0.1, 0.2, 0.3, 0.4], &mut buffers).clone();
+
+    assert_eq!(output.data, large.predict(&[0.1, 0.2, 0.3, 0.4]).unwrap());
+    assert!(buffers.fits(&large));
+}
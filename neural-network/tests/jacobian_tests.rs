@@ -0,0 +1,65 @@
+use neural_network::activations::{SIGMOID, SOFTMAX, TANH};
+use neural_network::network::Network;
+
+/// Central-difference estimate of the Jacobian, one row per output
+fn numeric_jacobian(network: &mut Network, input: &[f64], epsilon: f64) -> Vec<Vec<f64>> {
+    let outputs = network.predict(input).unwrap().len();
+    let mut jacobian = vec![vec![0.0; input.len()]; outputs];
+    for i in 0..input.len() {
+        let mut plus = input.to_vec();
+        plus[i] += epsilon;
+        let mut minus = input.to_vec();
+        minus[i] -= epsilon;
+        let (plus, minus) = (network.predict(&plus).unwrap(), network.predict(&minus).unwrap());
+        for j in 0..outputs {
+            jacobian[j][i] = (plus[j] - minus[j]) / (2.0 * epsilon);
+        }
+    }
+    jacobian
+}
+
+fn max_difference(network: &mut Network, input: &[f64]) -> f64 {
+    let analytic = network.jacobian(input);
+    let numeric = numeric_jacobian(network, input, 1e-6);
+    assert_eq!(analytic.len(), numeric.len());
+
+    analytic
+        .iter()
+        .zip(&numeric)
+        .flat_map(|(analytic, numeric)| {
+            assert_eq!(analytic.len(), numeric.len());
+            analytic.iter().zip(numeric)
+        })
+        .map(|(analytic, numeric)| (analytic - numeric).abs())
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn test_jacobian_has_a_row_per_output_and_a_column_per_input() {
+    let mut network = Network::new_seeded(vec![3, 5, 2], SIGMOID, 0.1, 7);
+    let jacobian = network.jacobian(&[0.1, 0.2, 0.3]);
+
+    assert_eq!(jacobian.len(), 2);
+    assert!(jacobian.iter().all(|row| row.len() == 3));
+}
+
+#[test]
+fn test_jacobian_matches_finite_differences() {
+    let mut network = Network::new_seeded(vec![2, 4, 3, 2], SIGMOID, 0.1, 11);
+    assert!(max_difference(&mut network, &[0.7, -0.3]) < 1e-6);
+
+    let mut network = Network::new_seeded(vec![3, 4, 3], TANH, 0.1, 5).with_activations(TANH, SOFTMAX);
+    assert!(max_difference(&mut network, &[0.4, 0.9, -0.2]) < 1e-6);
+}
+
+#[test]
+fn test_softmax_jacobian_columns_sum_to_zero() {
+    // Softmax outputs always sum to 1, so no input can change their total
+    let mut network = Network::new_seeded(vec![2, 4, 3], TANH, 0.1, 9).with_activations(TANH, SOFTMAX);
+    let jacobian = network.jacobian(&[0.5, -0.5]);
+
+    for i in 0..2 {
+        let total: f64 = jacobian.iter().map(|row| row[i]).sum();
+        assert!(total.abs() < 1e-12, "column {} sums to {}", i, total);
+    }
+}
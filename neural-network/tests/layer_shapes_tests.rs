@@ -0,0 +1,22 @@
+// Integration tests for Network::layer_shapes
+use neural_network::activations::SIGMOID;
+use neural_network::network::Network;
+
+#[test]
+fn test_layer_shapes_of_hidden_layer_network() {
+    let network = Network::new(vec![4, 8, 3], SIGMOID, 0.5);
+
+    assert_eq!(network.layer_shapes(), vec![(8, 4), (3, 8)]);
+}
+
+#[test]
+fn test_layer_shapes_match_biases_and_layers() {
+    let network = Network::new(vec![5, 7, 2, 6], SIGMOID, 0.5);
+    let shapes = network.layer_shapes();
+
+    assert_eq!(shapes.len(), network.layers.len() - 1);
+    for (i, &(rows, cols)) in shapes.iter().enumerate() {
+        assert_eq!((rows, cols), (network.layers[i + 1], network.layers[i]));
+        assert_eq!(network.biases[i].rows, rows);
+    }
+}
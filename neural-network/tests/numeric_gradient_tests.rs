@@ -0,0 +1,53 @@
+use neural_network::activations::{SIGMOID, SOFTMAX, TANH};
+use neural_network::matrix::Matrix;
+use neural_network::network::Network;
+
+/// Largest absolute difference between the numeric gradient and the negated
+/// analytic gradient, over every weight and bias
+fn max_difference(network: &mut Network, input: &[f64], target: &[f64]) -> f64 {
+    let (numeric_weights, numeric_biases) = network.numeric_gradient(input, target, 1e-6);
+
+    let outputs = network.feed_forward(Matrix::from(input.to_vec()));
+    let errors = Matrix::from(target.to_vec()).subtract(&outputs);
+    let analytic = network.compute_gradients(&outputs, errors);
+
+    numeric_weights
+        .iter()
+        .chain(&numeric_biases)
+        .zip(analytic.weights.iter().chain(&analytic.biases))
+        .flat_map(|(numeric, analytic)| numeric.data.iter().zip(&analytic.data))
+        .map(|(numeric, analytic)| (numeric + analytic).abs())
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn test_numeric_gradient_matches_parameter_shapes() {
+    let mut network = Network::new_seeded(vec![3, 5, 4, 2], SIGMOID, 0.1, 7);
+    let (weights, biases) = network.numeric_gradient(&[0.1, 0.2, 0.3], &[1.0, 0.0], 1e-5);
+
+    assert_eq!(weights.len(), network.weights.len());
+    assert_eq!(biases.len(), network.biases.len());
+    for (gradient, parameter) in weights.iter().zip(&network.weights).chain(biases.iter().zip(&network.biases)) {
+        assert_eq!((gradient.rows, gradient.cols), (parameter.rows, parameter.cols));
+    }
+}
+
+#[test]
+fn test_numeric_gradient_restores_parameters() {
+    let mut network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.1, 3);
+    let before = network.clone();
+
+    network.numeric_gradient(&[1.0, 0.0], &[1.0], 1e-4);
+
+    assert_eq!(network.weights, before.weights);
+    assert_eq!(network.biases, before.biases);
+}
+
+#[test]
+fn test_numeric_gradient_approximates_backpropagation() {
+    let mut network = Network::new_seeded(vec![2, 4, 3, 1], SIGMOID, 0.1, 11);
+    assert!(max_difference(&mut network, &[0.7, -0.3], &[1.0]) < 1e-6);
+
+    let mut network = Network::new_seeded(vec![2, 4, 3], TANH, 0.1, 5).with_activations(TANH, SOFTMAX);
+    assert!(max_difference(&mut network, &[0.4, 0.9], &[0.0, 1.0, 0.0]) < 1e-6);
+}
@@ -0,0 +1,109 @@
+// Integration tests for input validation in Network::predict
+use neural_network::activations::SIGMOID;
+use neural_network::network::Network;
+
+fn network() -> Network {
+    Network::new_seeded(vec![3, 4, 1], SIGMOID, 0.5, 1)
+}
+
+#[test]
+fn test_predict_accepts_finite_input() {
+    let output = network().predict(&[0.0, -2.5, 1e6]).unwrap();
+
+    assert_eq!(output.len(), 1);
+    assert!(output[0].is_finite());
+}
+
+#[test]
+fn test_predict_rejects_nan_input() {
+    let error = network().predict(&[0.0, f64::NAN, 1.0]).unwrap_err();
+
+    assert_eq!(error.to_string(), "Input values must be finite, got NaN at index 1");
+}
+
+#[test]
+fn test_predict_rejects_infinite_input() {
+    let error = network()
+        .predict(&[f64::INFINITY, 0.0, f64::NEG_INFINITY])
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Input values must be finite, got inf at index 0, -inf at index 2"
+    );
+}
+
+#[test]
+fn test_predict_rejects_wrong_input_count() {
+    let error = network().predict(&[0.0, 1.0]).unwrap_err();
+
+    assert_eq!(error.to_string(), "Invalid input dimensions: expected 3, got 2");
+}
+
+#[test]
+fn test_quantized_predict_validates_input() {
+    let quantized = network().quantize_f32();
+
+    assert!(quantized.predict(&[0.0, f64::NAN, 1.0]).is_err());
+    assert!(quantized.predict(&[0.0, 0.5, 1.0]).is_ok());
+}
+
+#[test]
+fn test_predict_topk_ranks_quadrant_classes() {
+    use neural_network::activations::{SOFTMAX, TANH};
+    use neural_network::examples;
+    use neural_network::metrics::argmax;
+    use neural_network::training::{TrainingConfig, TrainingController};
+
+    let example = examples::get_example("quadrant").unwrap();
+    let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.1, 1)
+        .with_activations(TANH, SOFTMAX);
+    let config = TrainingConfig {
+        epochs: 200,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    controller.train(example.inputs.clone(), example.targets.clone()).unwrap();
+    let network = controller.into_network();
+
+    for input in &example.inputs {
+        let top = network.predict_topk(input, 4).unwrap();
+        assert_eq!(top[0].0, argmax(&network.predict(input).unwrap()));
+        assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1), "{:?}", top);
+        let classes: std::collections::HashSet<usize> = top.iter().map(|(class, _)| *class).collect();
+        assert_eq!(classes.len(), 4);
+        assert!((top.iter().map(|(_, score)| score).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let top1 = network.predict_topk(input, 1).unwrap();
+        assert_eq!(top1, top[..1]);
+    }
+
+    let error = network.predict_topk(&example.inputs[0], 5).unwrap_err();
+    assert!(matches!(error, neural_network::error::NnError::InvalidTopK { k: 5, outputs: 4 }));
+}
+
+#[test]
+fn test_predict_topk_scores_sigmoid_outputs_with_softmax() {
+    use neural_network::metrics::argmax;
+
+    let mut network = Network::new_seeded(vec![2, 3, 4], SIGMOID, 0.5, 2);
+    let input = [0.3, 0.9];
+    let outputs = network.predict(&input).unwrap();
+
+    let top = network.predict_topk(&input, 4).unwrap();
+    assert_eq!(top[0].0, argmax(&outputs));
+    assert!((top.iter().map(|(_, score)| score).sum::<f64>() - 1.0).abs() < 1e-12);
+    let exps: f64 = outputs.iter().map(|o| o.exp()).sum();
+    for (class, score) in &top {
+        assert!((score - outputs[*class].exp() / exps).abs() < 1e-12);
+    }
+
+    // A NaN output ranks last rather than first
+    network.weights[1].data[..3].fill(f64::NAN);
+    let top = network.predict_topk(&input, 4).unwrap();
+    assert_eq!(top[3].0, 0);
+    assert!(top[3].1.is_nan());
+    assert!(top[..3].iter().all(|(_, score)| score.is_finite()));
+    assert!((top[..3].iter().map(|(_, score)| score).sum::<f64>() - 1.0).abs() < 1e-12);
+    assert_eq!(top[0].0, argmax(&network.predict(&input).unwrap()));
+}
@@ -0,0 +1,105 @@
+// Property-based tests for network checkpoints
+//
+// Loading untrusted bytes must fail with an error, never a panic; the
+// `load_checkpoint` fuzz target in `fuzz/` explores the same property for
+// longer than the test suite can.
+use neural_network::activations::ACTIVATIONS;
+use neural_network::checkpoint::{CheckpointFormat, CheckpointMetadata};
+use neural_network::network::Network;
+use proptest::prelude::*;
+
+const FORMATS: [CheckpointFormat; 3] = [CheckpointFormat::Json, CheckpointFormat::Bin, CheckpointFormat::JsonGz];
+
+/// Magic bytes steering arbitrary input towards each decoder
+const PREFIXES: [&[u8]; 4] = [b"", b"NNCK", b"\x1f\x8b", b"{"];
+
+fn metadata(epoch: u32) -> CheckpointMetadata {
+    CheckpointMetadata {
+        version: "1.0".to_string(),
+        example: "proptest".to_string(),
+        epoch,
+        total_epochs: epoch,
+        learning_rate: 0.5,
+        timestamp: "2025-10-13T12:00:00Z".to_string(),
+        history: Vec::new(),
+    }
+}
+
+fn finite() -> impl Strategy<Value = f64> + Clone {
+    any::<f64>().prop_filter("finite", |x| x.is_finite())
+}
+
+/// Networks of 2 to 4 layers of up to 6 neurons with parameters from `values`
+fn network_with(values: impl Strategy<Value = f64> + Clone) -> impl Strategy<Value = Network> {
+    (prop::collection::vec(1usize..=6, 2..=4), 0..ACTIVATIONS.len())
+        .prop_flat_map(move |(layers, activation)| {
+            let parameters: usize = layers.windows(2).map(|pair| pair[1] * (pair[0] + 1)).sum();
+            (Just(layers), Just(activation), prop::collection::vec(values.clone(), parameters))
+        })
+        .prop_map(|(layers, activation, values)| {
+            let mut network = Network::new_seeded(layers, ACTIVATIONS[activation], 0.5, 0);
+            let mut values = values.into_iter();
+            for matrix in network.weights.iter_mut().chain(network.biases.iter_mut()) {
+                matrix.data.iter_mut().for_each(|x| *x = values.next().unwrap());
+            }
+            network
+        })
+}
+
+fn encode(network: &Network, format: CheckpointFormat) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    network.write_checkpoint(&mut bytes, metadata(7), format).unwrap();
+    bytes
+}
+
+/// A loaded network must also be usable without panicking
+fn load_and_predict(bytes: &[u8]) {
+    if let Ok((network, _)) = Network::load_checkpoint_from_slice(bytes) {
+        let _ = network.predict(&vec![0.5; network.layers[0]]);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn checkpoints_round_trip_exactly(network in network_with(finite()), format in prop::sample::select(FORMATS.to_vec())) {
+        let (loaded, loaded_metadata) = Network::load_checkpoint_from_slice(&encode(&network, format)).unwrap();
+
+        prop_assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&network).unwrap());
+        prop_assert_eq!(loaded_metadata.epoch, 7);
+    }
+
+    #[test]
+    fn non_finite_weights_never_panic(network in network_with(any::<f64>()), format in prop::sample::select(FORMATS.to_vec())) {
+        load_and_predict(&encode(&network, format));
+    }
+
+    #[test]
+    fn corrupted_checkpoints_never_panic(
+        network in network_with(finite()),
+        format in prop::sample::select(FORMATS.to_vec()),
+        edits in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+        truncate in any::<prop::sample::Index>(),
+    ) {
+        let mut bytes = encode(&network, format);
+        for (index, byte) in edits {
+            let i = index.index(bytes.len());
+            bytes[i] = byte;
+        }
+        load_and_predict(&bytes);
+
+        bytes.truncate(truncate.index(bytes.len()));
+        load_and_predict(&bytes);
+    }
+
+    #[test]
+    fn arbitrary_bytes_never_panic(
+        prefix in prop::sample::select(PREFIXES.to_vec()),
+        tail in prop::collection::vec(any::<u8>(), 0..256),
+    ) {
+        let bytes = [prefix, &tail[..]].concat();
+
+        prop_assert!(Network::load_checkpoint_from_slice(&bytes).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use neural_network::activations::SIGMOID;
+use neural_network::examples;
+use neural_network::network::Network;
+use neural_network::optimizer::Optimizer;
+use neural_network::training::{TrainingConfig, TrainingController};
+
+fn nonzero_weights(network: &Network) -> usize {
+    network.weights.iter().flat_map(|w| &w.data).filter(|&&w| w != 0.0).count()
+}
+
+fn train(network: Network, epochs: u32, optimizer: Optimizer) -> Network {
+    let xor = examples::get_example("xor").unwrap();
+    let config = TrainingConfig {
+        epochs,
+        optimizer,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    controller.train(xor.inputs, xor.targets).unwrap();
+    controller.into_network()
+}
+
+#[test]
+fn test_pruned_weights_stay_zero_during_training() {
+    let mut network = train(Network::new_seeded(vec![2, 6, 1], SIGMOID, 0.5, 4), 500, Optimizer::Sgd);
+    let before = nonzero_weights(&network);
+
+    let threshold = 1.0;
+    let pruned = network.prune(threshold);
+    assert!(pruned > 0, "Some weights should be below {}", threshold);
+    assert_eq!(nonzero_weights(&network), before - pruned);
+    let mask = network.pruned.clone().unwrap();
+
+    for optimizer in [Optimizer::Sgd, Optimizer::Momentum { beta: 0.9, nesterov: false }, Optimizer::adam()] {
+        let trained = train(network.clone(), 200, optimizer);
+        assert_ne!(trained.weights, network.weights, "Training should still update the other weights");
+        for (weights, mask) in trained.weights.iter().zip(&mask) {
+            for (weight, &removed) in weights.data.iter().zip(mask) {
+                if removed {
+                    assert_eq!(*weight, 0.0);
+                }
+            }
+        }
+        assert_eq!(trained.pruned.as_ref(), Some(&mask));
+    }
+}
+
+#[test]
+fn test_pruning_mask_survives_serialization() {
+    let mut network = Network::new_seeded(vec![3, 5, 2], SIGMOID, 0.5, 9);
+    assert!(network.prune(0.4) > 0);
+
+    let json = serde_json::to_string(&network).unwrap();
+    let mut restored: Network = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.pruned, network.pruned);
+
+    // Pruning more adds to the loaded mask
+    let more = restored.prune(0.8);
+    let removed = restored.pruned.as_ref().unwrap().iter().flatten().filter(|&&removed| removed).count();
+    assert_eq!(removed, network.pruned.as_ref().unwrap().iter().flatten().filter(|&&r| r).count() + more);
+
+    // Unpruned networks don't write a mask
+    let plain = serde_json::to_value(Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1)).unwrap();
+    assert!(plain.get("pruned").is_none());
+}
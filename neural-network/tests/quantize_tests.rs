@@ -0,0 +1,106 @@
+use neural_network::activations::{SIGMOID, SOFTMAX};
+use neural_network::examples;
+use neural_network::network::Network;
+use neural_network::quantize::{F32_PREDICTION_TOLERANCE, I8_PREDICTION_TOLERANCE, I8Network, QuantizedNetwork};
+use neural_network::training::{TrainingConfig, TrainingController};
+
+fn trained_and() -> (Network, examples::Example) {
+    let and = examples::get_example("and").unwrap();
+    let network = Network::new_seeded(and.recommended_arch.clone(), SIGMOID, 0.5, 3);
+    let config = TrainingConfig {
+        epochs: 2000,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    controller.train(and.inputs.clone(), and.targets.clone()).unwrap();
+    (controller.into_network(), and)
+}
+
+#[test]
+fn test_f32_quantization_keeps_predictions_within_tolerance() {
+    let (network, and) = trained_and();
+    let quantized = network.quantize_f32();
+
+    for (input, target) in and.inputs.iter().zip(&and.targets) {
+        let original = network.predict(input).unwrap();
+        let approximate = quantized.predict(input).unwrap();
+        for (o, a) in original.iter().zip(&approximate) {
+            assert!((o - a).abs() < F32_PREDICTION_TOLERANCE, "{} vs {}", o, a);
+        }
+        // Still classifies every row correctly
+        assert_eq!(approximate[0].round(), target[0]);
+    }
+
+    let restored = quantized.dequantize();
+    assert_eq!(restored.layers, network.layers);
+    assert_eq!(restored.predict(&[1.0, 1.0]).unwrap(), quantized.predict(&[1.0, 1.0]).unwrap());
+}
+
+#[test]
+fn test_quantized_network_is_smaller_and_round_trips() {
+    // Large enough that the parameters dominate the encoding
+    let network = Network::new_seeded(vec![16, 32, 4], SIGMOID, 0.5, 1);
+    let quantized = network.quantize_f32();
+
+    let full = rmp_serde::to_vec_named(&network).unwrap();
+    let compact = rmp_serde::to_vec_named(&quantized).unwrap();
+    assert!(
+        (compact.len() as f64) < full.len() as f64 * 0.7,
+        "Quantized encoding should be much smaller: {} vs {} bytes",
+        compact.len(),
+        full.len()
+    );
+
+    let decoded: QuantizedNetwork = rmp_serde::from_slice(&compact).unwrap();
+    assert_eq!(decoded, quantized);
+}
+
+#[test]
+fn test_i8_inference_classifies_and_within_tolerance() {
+    let (network, and) = trained_and();
+    let quantized = network.quantize_i8();
+
+    for (input, target) in and.inputs.iter().zip(&and.targets) {
+        let original = network.predict(input).unwrap();
+        let approximate = quantized.predict(input).unwrap();
+        assert!((original[0] - approximate[0]).abs() < I8_PREDICTION_TOLERANCE, "{:?} vs {:?}", original, approximate);
+        assert_eq!(if approximate[0] > 0.5 { 1.0 } else { 0.0 }, target[0], "{:?}", input);
+    }
+}
+
+#[test]
+fn test_i8_inference_stays_close_on_wider_networks() {
+    // Random inputs in [0, 1] through a softmax network with two hidden layers
+    let network = Network::new_seeded(vec![8, 16, 12, 4], SIGMOID, 0.5, 5).with_activations(SIGMOID, SOFTMAX);
+    let quantized = network.quantize_i8();
+
+    for i in 0..20 {
+        let input: Vec<f64> = (0..8).map(|j| ((i * 8 + j) as f64 * 0.37).fract()).collect();
+        let original = network.predict(&input).unwrap();
+        let approximate = quantized.predict(&input).unwrap();
+        for (o, a) in original.iter().zip(&approximate) {
+            assert!((o - a).abs() < I8_PREDICTION_TOLERANCE, "{} vs {}", o, a);
+        }
+    }
+    assert!(quantized.predict(&[0.0; 3]).is_err());
+}
+
+#[test]
+fn test_i8_network_is_smaller_than_f32_and_round_trips() {
+    let network = Network::new_seeded(vec![16, 32, 4], SIGMOID, 0.5, 1);
+    let quantized = network.quantize_i8();
+    assert!(quantized.weights.iter().all(|w| w.data.len() == w.rows * w.cols));
+
+    let f32_encoding = rmp_serde::to_vec_named(&network.quantize_f32()).unwrap();
+    let i8_encoding = rmp_serde::to_vec_named(&quantized).unwrap();
+    assert!(
+        (i8_encoding.len() as f64) < f32_encoding.len() as f64 * 0.5,
+        "i8 encoding should be much smaller: {} vs {} bytes",
+        i8_encoding.len(),
+        f32_encoding.len()
+    );
+
+    let decoded: I8Network = rmp_serde::from_slice(&i8_encoding).unwrap();
+    assert_eq!(decoded, quantized);
+    assert_eq!(decoded.predict(&[0.5; 16]).unwrap(), quantized.predict(&[0.5; 16]).unwrap());
+}
@@ -0,0 +1,38 @@
+// Integration tests for Network::r2_score
+use neural_network::activations::{SIGMOID, TANH};
+use neural_network::network::Network;
+
+/// Inputs of a synthetic one-dimensional regression set
+fn inputs() -> Vec<Vec<f64>> {
+    (0..8).map(|i| vec![i as f64 / 4.0 - 1.0]).collect()
+}
+
+#[test]
+fn test_perfect_predictor_scores_one() {
+    let network = Network::new_seeded(vec![1, 5, 2], TANH, 0.1, 9);
+    let inputs = inputs();
+    let targets: Vec<Vec<f64>> = inputs.iter().map(|x| network.predict(x).unwrap()).collect();
+
+    assert!((network.r2_score(&inputs, &targets) - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_mean_predictor_scores_zero() {
+    // With every parameter zero the network outputs sigmoid(0) = 0.5 for any input
+    let mut network = Network::new_seeded(vec![1, 3, 1], SIGMOID, 0.1, 9);
+    for matrix in network.weights.iter_mut().chain(&mut network.biases) {
+        matrix.data.iter_mut().for_each(|value| *value = 0.0);
+    }
+    let inputs = inputs();
+    // Targets with mean 0.5
+    let targets: Vec<Vec<f64>> = [0.1, 0.9, 0.3, 0.7, 0.2, 0.8, 0.4, 0.6]
+        .iter()
+        .map(|&y| vec![y])
+        .collect();
+
+    assert!(network.r2_score(&inputs, &targets).abs() < 1e-12);
+
+    // Targets that don't vary are only explained by predicting them exactly
+    assert_eq!(network.r2_score(&inputs, &vec![vec![0.5]; 8]), 1.0);
+    assert_eq!(network.r2_score(&inputs, &vec![vec![0.2]; 8]), 0.0);
+}
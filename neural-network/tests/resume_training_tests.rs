@@ -1,8 +1,11 @@
 // Integration tests for resuming training from checkpoints
+#![cfg(feature = "fs")]
+
 use neural_network::activations::SIGMOID;
 use neural_network::checkpoint::CheckpointMetadata;
 use neural_network::network::Network;
-use neural_network::training::{TrainingConfig, TrainingController};
+use neural_network::optimizer::Optimizer;
+use neural_network::training::{TrainingConfig, TrainingController, TrainingReport};
 use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 
@@ -23,6 +26,7 @@ fn test_resume_from_checkpoint_basic() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = TrainingController::new(network, config);
@@ -41,6 +45,7 @@ fn test_resume_from_checkpoint_basic() {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .expect("Should load from checkpoint");
@@ -69,6 +74,7 @@ fn test_resume_preserves_network_state() {
         total_epochs: 200,
         learning_rate: 0.5,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        history: Vec::new(),
     };
 
     network.save_checkpoint(&checkpoint_path, metadata).unwrap();
@@ -86,6 +92,7 @@ fn test_resume_preserves_network_state() {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -112,6 +119,7 @@ fn test_resume_with_continued_training() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = TrainingController::new(network, config);
@@ -134,6 +142,7 @@ fn test_resume_with_continued_training() {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -161,6 +170,7 @@ fn test_resume_with_callbacks() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = TrainingController::new(network, config);
@@ -175,6 +185,7 @@ fn test_resume_with_callbacks() {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -202,6 +213,7 @@ fn test_resume_nonexistent_checkpoint() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let result = TrainingController::from_checkpoint(&checkpoint_path, config);
@@ -222,6 +234,7 @@ fn test_resume_with_new_checkpoint_path() {
         checkpoint_path: Some(old_checkpoint.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = TrainingController::new(network, config);
@@ -237,6 +250,7 @@ fn test_resume_with_new_checkpoint_path() {
             checkpoint_path: Some(new_checkpoint.clone()),
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -260,6 +274,7 @@ fn test_resume_metadata_continuity() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = TrainingController::new(network, config);
@@ -278,6 +293,7 @@ fn test_resume_metadata_continuity() {
             checkpoint_path: None,
             verbose: false,
             example_name: None,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -287,3 +303,116 @@ fn test_resume_metadata_continuity() {
 
     // TempDir automatically cleans up when dropped
 }
+
+#[test]
+fn test_resume_preserves_adam_state() {
+    let temp_dir = create_temp_dir();
+    let checkpoint_path = temp_dir.path().join("adam.json");
+    // A curve too wiggly for the network to fit, so the loss settles above zero
+    let inputs: Vec<Vec<f64>> = (0..16).map(|i| vec![i as f64 / 15.0]).collect();
+    let targets: Vec<Vec<f64>> = inputs
+        .iter()
+        .map(|x| vec![0.5 + 0.4 * (3.0 * std::f64::consts::PI * x[0]).sin()])
+        .collect();
+    let config = |epochs, checkpoint_path: Option<std::path::PathBuf>| TrainingConfig {
+        epochs,
+        checkpoint_interval: checkpoint_path.as_ref().map(|_| epochs),
+        checkpoint_path,
+        optimizer: Optimizer::adam(),
+        ..Default::default()
+    };
+
+    let network = Network::new_seeded(vec![1, 2, 1], SIGMOID, 0.05, 3);
+    let mut controller = TrainingController::new(network, config(500, Some(checkpoint_path.clone())));
+    let before = controller.train(inputs.clone(), targets.clone()).unwrap().final_loss;
+
+    // Resuming with the saved moments continues exactly as uninterrupted training
+    let mut resumed = TrainingController::from_checkpoint(&checkpoint_path, config(20, None)).unwrap();
+    let preserved = resumed.train(inputs.clone(), targets.clone()).unwrap();
+    let uninterrupted = controller.train(inputs.clone(), targets.clone()).unwrap();
+    for (a, b) in preserved.history.iter().zip(&uninterrupted.history) {
+        assert!((a.loss - b.loss).abs() < 1e-12, "epoch {}: {} vs {}", a.epoch, a.loss, b.loss);
+    }
+
+    // Starting Adam over takes full-size steps on every parameter
+    let (network, _) = Network::load_checkpoint_auto(&checkpoint_path).unwrap();
+    let mut reset = TrainingController::new(network, config(20, None));
+    let reset = reset.train(inputs, targets).unwrap();
+
+    let peak = |report: &TrainingReport| {
+        report.history.iter().map(|p| p.loss).fold(f64::MIN, f64::max)
+    };
+    assert!(peak(&preserved) < before, "{} vs {}", peak(&preserved), before);
+    assert!(peak(&reset) > before * 1.05, "{} vs {}", peak(&reset), before);
+}
+
+#[test]
+fn test_each_resume_appends_a_session_record() {
+    use neural_network::checkpoint::Checkpoint;
+
+    let temp_dir = create_temp_dir();
+    let checkpoint_path = temp_dir.path().join("sessions.json");
+    let inputs = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+    let targets = vec![vec![0.0], vec![1.0]];
+    let config = |epochs: u32| TrainingConfig {
+        epochs,
+        checkpoint_interval: Some(epochs),
+        checkpoint_path: Some(checkpoint_path.clone()),
+        ..Default::default()
+    };
+
+    let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1);
+    let mut controller = TrainingController::new(network, config(30));
+    controller.train(inputs.clone(), targets.clone()).unwrap();
+    assert!(Checkpoint::load(&checkpoint_path).unwrap().metadata.history.is_empty());
+
+    for (epochs, learning_rate) in [(20, 0.5), (10, 0.25)] {
+        let mut controller = TrainingController::from_checkpoint(&checkpoint_path, config(epochs)).unwrap();
+        controller.add_pre_epoch_callback(Box::new(move |_, network| network.learning_rate = learning_rate));
+        controller.train(inputs.clone(), targets.clone()).unwrap();
+    }
+
+    let metadata = Checkpoint::load(&checkpoint_path).unwrap().metadata;
+    assert_eq!(metadata.history.len(), 2);
+    assert_eq!(metadata.history[0].epochs, 20);
+    assert_eq!(metadata.history[0].learning_rate, 0.5);
+    assert_eq!(metadata.history[1].epochs, 10);
+    assert_eq!(metadata.history[1].learning_rate, 0.25);
+    assert_eq!(metadata.history[1].timestamp, metadata.timestamp);
+    assert!(metadata.history[0].timestamp <= metadata.history[1].timestamp);
+}
+
+#[test]
+fn test_resume_with_frozen_layers_ignores_stored_momentum() {
+    let temp_dir = create_temp_dir();
+    let checkpoint_path = temp_dir.path().join("momentum.json");
+    let inputs = vec![vec![0.0, 1.0], vec![1.0, 1.0]];
+    let targets = vec![vec![1.0], vec![0.0]];
+    let momentum = Optimizer::Momentum { beta: 0.9, nesterov: false };
+
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 11);
+    let config = TrainingConfig {
+        epochs: 50,
+        checkpoint_interval: Some(50),
+        checkpoint_path: Some(checkpoint_path.clone()),
+        optimizer: momentum,
+        ..Default::default()
+    };
+    TrainingController::new(network, config).train(inputs.clone(), targets.clone()).unwrap();
+
+    // The checkpoint's velocity covers layer 0, which must still stay put
+    let config = TrainingConfig {
+        epochs: 5,
+        frozen_layers: vec![0],
+        optimizer: momentum,
+        ..Default::default()
+    };
+    let mut resumed = TrainingController::from_checkpoint(&checkpoint_path, config).unwrap();
+    let initial = resumed.network().clone();
+    resumed.train(inputs, targets).unwrap();
+
+    let trained = resumed.network();
+    assert_eq!(trained.weights[0], initial.weights[0], "Frozen weights should not change");
+    assert_eq!(trained.biases[0], initial.biases[0], "Frozen biases should not change");
+    assert_ne!(trained.weights[1], initial.weights[1], "Unfrozen weights should change");
+}
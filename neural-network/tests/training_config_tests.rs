@@ -0,0 +1,68 @@
+// Integration tests for saving and loading TrainingConfig with serde
+use neural_network::checkpoint::CheckpointFormat;
+use neural_network::loss::{Loss, LossReduction};
+use neural_network::optimizer::Optimizer;
+use neural_network::training::TrainingConfig;
+use std::path::PathBuf;
+
+fn full_config() -> TrainingConfig {
+    TrainingConfig {
+        epochs: 5000,
+        checkpoint_interval: Some(250),
+        checkpoint_path: Some(PathBuf::from("checkpoints/xor.bin")),
+        checkpoint_on_best: false,
+        verbose: true,
+        example_name: Some("xor".to_string()),
+        loss: Loss::Huber { delta: 0.25 },
+        loss_reduction: LossReduction::PerElement,
+        checkpoint_format: CheckpointFormat::Bin,
+        frozen_layers: vec![0],
+        reservoir: true,
+        reservoir_lr_scale: 3.0,
+        validation_split: Some(0.2),
+        validation_seed: 17,
+        loss_ema: Some(0.1),
+        optimizer: Optimizer::adam(),
+        center_targets: true,
+        target_loss: Some(0.01),
+        weight_ema_decay: Some(0.99),
+        metrics_log_path: Some(PathBuf::from("logs/xor.jsonl")),
+    }
+}
+
+#[test]
+fn test_full_config_round_trips_through_json() {
+    let config = full_config();
+
+    let json = serde_json::to_string_pretty(&config).unwrap();
+    let restored: TrainingConfig = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, config);
+}
+
+#[test]
+fn test_missing_fields_take_defaults() {
+    let config: TrainingConfig =
+        serde_json::from_str(r#"{"epochs": 50, "optimizer": {"momentum": {"beta": 0.8}}}"#).unwrap();
+
+    let momentum = Optimizer::Momentum {
+        beta: 0.8,
+        nesterov: false,
+    };
+    assert_eq!(config.epochs, 50);
+    assert_eq!(config.optimizer, momentum);
+    assert_eq!(
+        config,
+        TrainingConfig {
+            epochs: 50,
+            optimizer: momentum,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_unknown_values_are_rejected() {
+    assert!(serde_json::from_str::<TrainingConfig>(r#"{"optimizer": "rmsprop"}"#).is_err());
+    assert!(serde_json::from_str::<TrainingConfig>(r#"{"epochs": -1}"#).is_err());
+}
@@ -1,5 +1,6 @@
 // Integration tests for TrainingController
 use neural_network::activations::SIGMOID;
+use neural_network::data_source::{Batch, DataSource};
 use neural_network::network::Network;
 use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
@@ -13,6 +14,7 @@ fn test_training_controller_basic() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -33,6 +35,7 @@ fn test_training_controller_with_callbacks() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -61,6 +64,7 @@ fn test_training_controller_with_multiple_callbacks() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -84,6 +88,7 @@ fn test_training_controller_with_multiple_callbacks() {
 }
 
 #[test]
+#[cfg(feature = "fs")]
 fn test_training_controller_auto_checkpoint() {
     let temp_dir = TempDir::new().unwrap();
     let checkpoint_path = temp_dir.path().join("auto_checkpoint.json");
@@ -95,6 +100,7 @@ fn test_training_controller_auto_checkpoint() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -116,6 +122,7 @@ fn test_training_controller_verbose_mode() {
         checkpoint_path: None,
         verbose: true,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -134,6 +141,7 @@ fn test_training_controller_returns_trained_network() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -161,6 +169,7 @@ fn test_training_config_defaults() {
         checkpoint_path: None,
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     assert_eq!(config.epochs, 1000);
@@ -170,6 +179,7 @@ fn test_training_config_defaults() {
 }
 
 #[test]
+#[cfg(feature = "fs")]
 fn test_training_controller_checkpoint_at_final_epoch() {
     let temp_dir = TempDir::new().unwrap();
     let checkpoint_path = temp_dir.path().join("final_checkpoint.json");
@@ -181,6 +191,7 @@ fn test_training_controller_checkpoint_at_final_epoch() {
         checkpoint_path: Some(checkpoint_path.clone()),
         verbose: false,
         example_name: None,
+        ..Default::default()
     };
 
     let mut controller = neural_network::training::TrainingController::new(network, config);
@@ -190,3 +201,963 @@ fn test_training_controller_checkpoint_at_final_epoch() {
     let (_, metadata) = Network::load_checkpoint(&checkpoint_path).unwrap();
     assert_eq!(metadata.epoch, 50);
 }
+
+#[test]
+fn test_training_controller_returns_report() {
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 20,
+        checkpoint_interval: None,
+        checkpoint_path: None,
+        verbose: false,
+        example_name: None,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(vec![vec![0.0, 0.0]], vec![vec![0.0]]).unwrap();
+
+    assert_eq!(report.epochs_run, 20);
+    assert!(report.final_loss.is_finite());
+}
+
+#[test]
+fn test_training_controller_with_huber_loss() {
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 50,
+        loss: neural_network::loss::Loss::Huber { delta: 0.1 },
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller
+        .train(vec![vec![0.0, 0.0], vec![1.0, 1.0]], vec![vec![0.0], vec![1.0]])
+        .unwrap();
+
+    assert!(report.final_loss.is_finite());
+}
+
+#[test]
+fn test_invalid_huber_delta_is_rejected() {
+    for delta in [-0.1, 0.0, f64::NAN, f64::INFINITY] {
+        let config = neural_network::training::TrainingConfig {
+            epochs: 5,
+            loss: neural_network::loss::Loss::Huber { delta },
+            ..Default::default()
+        };
+        let mut controller =
+            neural_network::training::TrainingController::new(Network::new(vec![2, 2, 1], SIGMOID, 0.5), config);
+        let error = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap_err();
+        assert!(error.to_string().contains("Huber delta"), "{}", error);
+    }
+}
+
+#[test]
+fn test_per_element_loss_divides_by_output_count() {
+    use neural_network::loss::LossReduction;
+
+    let quadrant = neural_network::examples::get_example("quadrant").unwrap();
+    let outputs = quadrant.targets[0].len();
+    let train = |loss_reduction| {
+        let network = Network::new_seeded(quadrant.recommended_arch.clone(), SIGMOID, 0.5, 3);
+        let config = neural_network::training::TrainingConfig {
+            epochs: 20,
+            loss_reduction,
+            ..Default::default()
+        };
+        let mut controller = neural_network::training::TrainingController::new(network, config);
+        controller
+            .train(quadrant.inputs.clone(), quadrant.targets.clone())
+            .unwrap()
+    };
+
+    let per_sample = train(LossReduction::PerSample);
+    let per_element = train(LossReduction::PerElement);
+
+    // Training is identical; only the reported loss is scaled
+    assert_eq!(outputs, 4);
+    assert!(per_element.final_loss < per_sample.final_loss);
+    for (sample, element) in per_sample.history.iter().zip(&per_element.history) {
+        assert!((element.loss * outputs as f64 - sample.loss).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_training_controller_stops_when_flag_set() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 1000,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let stop = Arc::new(AtomicBool::new(false));
+    controller.set_stop_flag(stop.clone());
+
+    let stop_clone = stop.clone();
+    controller.add_callback(Box::new(move |epoch, _loss, _network| {
+        if epoch == 10 {
+            stop_clone.store(true, Ordering::Relaxed);
+        }
+    }));
+
+    let report = controller.train(vec![vec![0.0, 0.0]], vec![vec![0.0]]).unwrap();
+    assert_eq!(report.epochs_run, 10, "Training should stop after the flagged epoch");
+}
+
+#[test]
+fn test_gradient_norm_shrinks_as_loss_plateaus() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 7);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 3000,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let reported_clone = reported.clone();
+    controller.add_progress_callback(Box::new(move |progress, _network| {
+        reported_clone.lock().unwrap().push(progress.gradient_norm);
+    }));
+
+    let inputs = vec![
+        vec![0.0, 0.0],
+        vec![0.0, 1.0],
+        vec![1.0, 0.0],
+        vec![1.0, 1.0],
+    ];
+    let targets = vec![vec![0.0], vec![0.0], vec![0.0], vec![1.0]];
+    let report = controller.train(inputs, targets).unwrap();
+
+    assert_eq!(report.history.len(), 3000);
+    assert_eq!(
+        *reported.lock().unwrap(),
+        report.history.iter().map(|p| p.gradient_norm).collect::<Vec<_>>(),
+        "Callbacks and history should agree"
+    );
+
+    let early = report.history[0].gradient_norm;
+    let late = report.history.last().unwrap().gradient_norm;
+    assert!(early > 0.0, "Gradient norm should be positive early in training");
+    assert!(late < early, "Gradient norm should shrink: {} -> {}", early, late);
+}
+
+#[test]
+fn test_frozen_layer_is_not_updated() {
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 11);
+    let initial = network.clone();
+    let config = neural_network::training::TrainingConfig {
+        epochs: 100,
+        frozen_layers: vec![0],
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    controller
+        .train(vec![vec![0.0, 1.0], vec![1.0, 1.0]], vec![vec![1.0], vec![0.0]])
+        .unwrap();
+
+    let trained = controller.network();
+    assert_eq!(trained.weights[0], initial.weights[0], "Frozen weights should not change");
+    assert_eq!(trained.biases[0], initial.biases[0], "Frozen biases should not change");
+    assert_ne!(trained.weights[1], initial.weights[1], "Unfrozen weights should change");
+}
+
+#[test]
+fn test_reservoir_readout_uses_scaled_learning_rate() {
+    let xor = neural_network::examples::get_example("xor").unwrap();
+    let train = |learning_rate: f64, config: neural_network::training::TrainingConfig| {
+        let network = Network::new_seeded(vec![2, 4, 1], SIGMOID, learning_rate, 8);
+        let mut controller = neural_network::training::TrainingController::new(network, config);
+        let report = controller.train(xor.inputs.clone(), xor.targets.clone()).unwrap();
+        (controller.into_network(), report)
+    };
+
+    // Reservoir at 0.1 with a scale of 4 matches freezing the hidden layer at 0.4
+    let (reservoir, report) = train(
+        0.1,
+        neural_network::training::TrainingConfig {
+            epochs: 20,
+            reservoir: true,
+            reservoir_lr_scale: 4.0,
+            ..Default::default()
+        },
+    );
+    let (frozen, _) = train(
+        0.4,
+        neural_network::training::TrainingConfig {
+            epochs: 20,
+            frozen_layers: vec![0],
+            ..Default::default()
+        },
+    );
+    for (a, b) in reservoir.weights[1].data.iter().zip(&frozen.weights[1].data) {
+        assert!((a - b).abs() < 1e-12, "{} vs {}", a, b);
+    }
+    assert_eq!(reservoir.learning_rate, 0.1);
+    assert_eq!(report.history[0].learning_rate, 0.1);
+
+    let invalid = neural_network::training::TrainingConfig {
+        epochs: 1,
+        reservoir: true,
+        reservoir_lr_scale: 0.0,
+        ..Default::default()
+    };
+    let mut controller =
+        neural_network::training::TrainingController::new(Network::new(vec![2, 4, 1], SIGMOID, 0.1), invalid);
+    assert!(controller.train(xor.inputs.clone(), xor.targets.clone()).is_err());
+}
+
+#[test]
+fn test_reservoir_trains_only_the_output_layer() {
+    let xor = neural_network::examples::get_example("xor").unwrap();
+    // The readout trains at 0.4 * 5 = 2.0, over random features spread
+    // evenly around zero
+    let mut network = Network::new_seeded(vec![2, 10, 1], SIGMOID, 0.4, 5);
+    for value in network.weights[0].data.iter_mut().chain(network.biases[0].data.iter_mut()) {
+        *value = 8.0 * *value - 4.0;
+    }
+    let initial = network.clone();
+    let config = neural_network::training::TrainingConfig {
+        epochs: 3000,
+        reservoir: true,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(xor.inputs.clone(), xor.targets.clone()).unwrap();
+
+    let trained = controller.network();
+    assert_eq!(trained.weights[0], initial.weights[0], "Hidden weights should not change");
+    assert_eq!(trained.biases[0], initial.biases[0], "Hidden biases should not change");
+    assert_ne!(trained.weights[1], initial.weights[1], "The readout should train");
+    assert!(report.final_loss < report.history[0].loss);
+    let accuracy = trained.accuracy(
+        &xor.inputs,
+        &xor.targets,
+        neural_network::metrics::AccuracyMode::Classification,
+    );
+    assert_eq!(accuracy, 1.0, "Random features should make xor separable");
+}
+
+#[test]
+fn test_reservoir_needs_a_hidden_layer() {
+    let network = Network::new(vec![2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        reservoir: true,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let error = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap_err();
+    assert!(error.to_string().contains("hidden layer"), "{}", error);
+}
+
+#[test]
+fn test_frozen_layer_index_is_validated() {
+    let network = Network::new(vec![2, 3, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        frozen_layers: vec![2],
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let result = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]);
+
+    assert!(result.is_err(), "Freezing a non-existent layer should fail");
+}
+
+#[test]
+fn test_checkpoint_interval_requires_path() {
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        checkpoint_interval: Some(5),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let error = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap_err();
+
+    assert!(error.to_string().contains("without a checkpoint_path"), "{}", error);
+}
+
+#[test]
+fn test_checkpoint_path_requires_interval() {
+    let temp_dir = TempDir::new().unwrap();
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        checkpoint_path: Some(temp_dir.path().join("never.json")),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    assert!(controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).is_err());
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn test_uneven_interval_still_checkpoints_last_epoch() {
+    let temp_dir = TempDir::new().unwrap();
+    let checkpoint_path = temp_dir.path().join("uneven.json");
+
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        checkpoint_interval: Some(3),
+        checkpoint_path: Some(checkpoint_path.clone()),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+
+    let (_, metadata) = Network::load_checkpoint(&checkpoint_path).unwrap();
+    assert_eq!(metadata.epoch, 10);
+}
+
+#[test]
+fn test_center_targets_trains_on_centered_outputs() {
+    use neural_network::activations::TANH;
+    use neural_network::matrix::Matrix;
+
+    // Targets far outside tanh's range until their mean is taken out
+    let inputs: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64 / 7.0]).collect();
+    let targets: Vec<Vec<f64>> = inputs.iter().map(|x| vec![3.0 + 0.5 * x[0]]).collect();
+
+    let network = Network::new_seeded(vec![1, 4, 1], TANH, 0.1, 2);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 2000,
+        center_targets: true,
+        ..Default::default()
+    };
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    controller.train(inputs.clone(), targets.clone()).unwrap();
+    let mut network = controller.into_network();
+
+    let offset = network.output_offset.clone().expect("Centering stores the target means");
+    assert!((offset[0] - 3.25).abs() < 1e-12);
+
+    let mut raw_sum = 0.0;
+    for (input, target) in inputs.iter().zip(&targets) {
+        let raw = network.feed_forward(Matrix::from(input.clone())).data[0];
+        assert!(raw.abs() < 0.3, "Raw output {} should be near zero", raw);
+        raw_sum += raw;
+
+        let prediction = network.predict(input).unwrap()[0];
+        assert!((prediction - target[0]).abs() < 0.05, "{} vs {}", prediction, target[0]);
+    }
+    assert!((raw_sum / inputs.len() as f64).abs() < 0.05);
+
+    // The offset is saved with the network
+    let restored: Network = serde_json::from_str(&serde_json::to_string(&network).unwrap()).unwrap();
+    assert_eq!(restored.predict(&inputs[3]).unwrap(), network.predict(&inputs[3]).unwrap());
+}
+
+/// In-memory source that serves fixed batches and counts what it hands out
+struct CountingSource {
+    batches: Vec<Batch>,
+    position: usize,
+    served: usize,
+    resets: usize,
+}
+
+impl DataSource for CountingSource {
+    fn next_batch(&mut self) -> Option<Batch> {
+        let batch = self.batches.get(self.position).cloned();
+        if batch.is_some() {
+            self.position += 1;
+            self.served += 1;
+        }
+        batch
+    }
+
+    fn reset(&mut self) -> anyhow::Result<()> {
+        self.position = 0;
+        self.resets += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_train_from_source_consumes_every_batch_each_epoch() {
+    let mut source = CountingSource {
+        batches: vec![
+            (vec![vec![0.0, 0.0], vec![0.0, 1.0]], vec![vec![0.0], vec![1.0]]),
+            (vec![vec![1.0, 0.0], vec![1.0, 1.0]], vec![vec![1.0], vec![0.0]]),
+        ],
+        position: 0,
+        served: 0,
+        resets: 0,
+    };
+
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 7);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 5,
+        ..Default::default()
+    };
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+
+    let report = controller.train_from_source(&mut source).expect("Training should succeed");
+
+    assert_eq!(report.epochs_run, 5);
+    assert_eq!(report.history.len(), 5);
+    assert_eq!(source.resets, 5, "Each epoch should rewind the source");
+    assert_eq!(source.served, 10, "Both batches should be read every epoch");
+    assert!(report.final_loss.is_finite());
+}
+
+#[test]
+fn test_validation_split_reports_validation_loss() {
+    let iris = neural_network::examples::get_example("iris").unwrap();
+    let network = Network::new_seeded(iris.recommended_arch.clone(), SIGMOID, 0.3, 1);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 20,
+        validation_split: Some(0.2),
+        validation_seed: 3,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(iris.inputs, iris.targets).unwrap();
+
+    assert_eq!(report.history.len(), 20);
+    let first = report.history[0].val_loss.expect("Every epoch should report validation loss");
+    let last = report.history[19].val_loss.unwrap();
+    assert!(last < first, "Validation loss should fall: {} -> {}", first, last);
+
+    // Without a split there is nothing to report
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let mut controller = neural_network::training::TrainingController::new(
+        network,
+        neural_network::training::TrainingConfig {
+            epochs: 1,
+            ..Default::default()
+        },
+    );
+    let report = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+    assert!(report.history[0].val_loss.is_none());
+}
+
+#[test]
+fn test_validation_split_must_be_a_fraction() {
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 1,
+        validation_split: Some(1.0),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    assert!(controller.train(vec![vec![0.0, 1.0]; 4], vec![vec![1.0]; 4]).is_err());
+}
+
+#[test]
+fn test_loss_ema_lags_and_smooths_the_loss() {
+    // A large learning rate makes the per-epoch loss jump around
+    let xor = neural_network::examples::get_example("xor").unwrap();
+    let network = Network::new_seeded(xor.recommended_arch.clone(), SIGMOID, 8.0, 5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 200,
+        loss_ema: Some(0.1),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(xor.inputs, xor.targets).unwrap();
+
+    let raw: Vec<f64> = report.history.iter().map(|p| p.loss).collect();
+    let ema: Vec<f64> = report.history.iter().map(|p| p.loss_ema.unwrap()).collect();
+    assert_eq!(ema[0], raw[0]);
+
+    // Each step moves the average a tenth of the way to the raw loss
+    for i in 1..raw.len() {
+        assert!((ema[i] - (0.1 * raw[i] + 0.9 * ema[i - 1])).abs() < 1e-12);
+    }
+
+    // Epoch-to-epoch changes are smaller and steadier than the raw loss's
+    let step_variance = |values: &[f64]| {
+        let steps: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+        let mean = steps.iter().sum::<f64>() / steps.len() as f64;
+        steps.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / steps.len() as f64
+    };
+    assert!(
+        step_variance(&ema) < step_variance(&raw),
+        "EMA should be smoother: {} vs {}",
+        step_variance(&ema),
+        step_variance(&raw)
+    );
+
+    // Without a smoothing factor there is no average, and it must be a fraction
+    let mut controller = neural_network::training::TrainingController::new(
+        Network::new(vec![2, 2, 1], SIGMOID, 0.5),
+        neural_network::training::TrainingConfig { epochs: 1, ..Default::default() },
+    );
+    let report = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+    assert!(report.history[0].loss_ema.is_none());
+
+    let mut controller = neural_network::training::TrainingController::new(
+        Network::new(vec![2, 2, 1], SIGMOID, 0.5),
+        neural_network::training::TrainingConfig { epochs: 1, loss_ema: Some(0.0), ..Default::default() },
+    );
+    assert!(controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).is_err());
+}
+
+#[test]
+fn test_zero_sample_weight_has_no_effect() {
+    use neural_network::optimizer::Optimizer;
+
+    let optimizers = [
+        Optimizer::Sgd,
+        Optimizer::Momentum { beta: 0.9, nesterov: false },
+        Optimizer::adam(),
+    ];
+    for optimizer in optimizers {
+        let train = |inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>, weights: Option<Vec<f64>>| {
+            let config = neural_network::training::TrainingConfig {
+                epochs: 50,
+                optimizer,
+                ..Default::default()
+            };
+            let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 4);
+            let mut controller = neural_network::training::TrainingController::new(network, config);
+            controller.train_weighted(inputs, targets, weights).unwrap();
+            controller.into_network()
+        };
+        let inputs = vec![vec![0.0, 1.0], vec![1.0, 1.0], vec![1.0, 0.0]];
+        let targets = vec![vec![1.0], vec![0.0], vec![1.0]];
+
+        // Without the last sample at all
+        let baseline = train(inputs[..2].to_vec(), targets[..2].to_vec(), None);
+
+        let ignored = train(inputs.clone(), targets.clone(), Some(vec![1.0, 1.0, 0.0]));
+        assert_eq!(ignored.weights, baseline.weights, "{:?}", optimizer);
+        assert_eq!(ignored.biases, baseline.biases, "{:?}", optimizer);
+
+        let included = train(inputs, targets, Some(vec![1.0, 1.0, 1.0]));
+        assert_ne!(included.weights, baseline.weights, "{:?}", optimizer);
+    }
+}
+
+#[test]
+fn test_sample_weights_are_validated() {
+    let mut controller = neural_network::training::TrainingController::new(
+        Network::new(vec![2, 2, 1], SIGMOID, 0.5),
+        neural_network::training::TrainingConfig { epochs: 1, ..Default::default() },
+    );
+    let inputs = vec![vec![0.0, 1.0], vec![1.0, 1.0]];
+    let targets = vec![vec![1.0], vec![0.0]];
+
+    let error = controller
+        .train_weighted(inputs.clone(), targets.clone(), Some(vec![1.0]))
+        .unwrap_err();
+    assert!(error.to_string().contains("1 sample weights for 2 samples"), "{}", error);
+    assert!(controller.train_weighted(inputs, targets, Some(vec![1.0, -1.0])).is_err());
+}
+
+#[test]
+fn test_target_loss_stops_training_early() {
+    let example = neural_network::examples::get_example("or").unwrap();
+    let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 3);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 50000,
+        target_loss: Some(0.01),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(example.inputs, example.targets).unwrap();
+
+    assert!(report.target_reached);
+    assert!(report.epochs_run < 50000, "ran {} epochs", report.epochs_run);
+    assert!(report.final_loss < 0.01);
+    // Training stops at the first epoch below the target
+    assert_eq!(report.history.len(), report.epochs_run as usize);
+    let previous = &report.history[report.history.len() - 2];
+    assert!(previous.loss >= 0.01, "{:?}", previous);
+}
+
+#[test]
+fn test_epochs_cap_training_when_target_loss_is_not_reached() {
+    let example = neural_network::examples::get_example("or").unwrap();
+    let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 3);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 200,
+        target_loss: Some(1e-9),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(example.inputs, example.targets).unwrap();
+
+    assert!(!report.target_reached);
+    assert_eq!(report.epochs_run, 200);
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn test_target_loss_checkpoints_the_last_epoch() {
+    let temp_dir = TempDir::new().unwrap();
+    let checkpoint_path = temp_dir.path().join("or.json");
+    let example = neural_network::examples::get_example("or").unwrap();
+    let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 3);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 50000,
+        target_loss: Some(0.05),
+        checkpoint_interval: Some(100000),
+        checkpoint_path: Some(checkpoint_path.clone()),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let report = controller.train(example.inputs, example.targets).unwrap();
+
+    let (_, metadata) = Network::load_checkpoint(&checkpoint_path).unwrap();
+    assert_eq!(metadata.epoch, report.epochs_run);
+}
+
+#[test]
+fn test_target_loss_must_be_positive() {
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        target_loss: Some(0.0),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let error = controller.train(vec![vec![0.0, 0.0]], vec![vec![0.0]]).unwrap_err();
+
+    assert!(error.to_string().contains("target_loss must be positive"), "{}", error);
+}
+
+#[test]
+fn test_pre_epoch_callback_sets_the_learning_rate_for_that_epoch() {
+    let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 6,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+
+    // Freeze the network on even epochs by zeroing the learning rate
+    controller.add_pre_epoch_callback(Box::new(|epoch, network| {
+        network.learning_rate = if epoch.is_multiple_of(2) { 0.0 } else { 0.5 };
+    }));
+    let snapshots = Arc::new(Mutex::new(Vec::new()));
+    let snapshots_clone = snapshots.clone();
+    controller.add_callback(Box::new(move |_epoch, _loss, network| {
+        let weights: Vec<f64> = network.weights.iter().flat_map(|w| w.data.clone()).collect();
+        snapshots_clone.lock().unwrap().push((network.learning_rate, weights));
+    }));
+
+    let inputs = vec![vec![0.0, 1.0], vec![1.0, 1.0]];
+    let targets = vec![vec![1.0], vec![0.0]];
+    controller.train(inputs, targets).unwrap();
+
+    let snapshots = snapshots.lock().unwrap();
+    assert_eq!(snapshots.len(), 6);
+    for (i, pair) in snapshots.windows(2).enumerate() {
+        let epoch = i + 2;
+        let (rate, weights) = &pair[1];
+        if epoch.is_multiple_of(2) {
+            assert_eq!(*rate, 0.0);
+            assert_eq!(weights, &pair[0].1, "Epoch {} should not update the weights", epoch);
+        } else {
+            assert_eq!(*rate, 0.5);
+            assert_ne!(weights, &pair[0].1, "Epoch {} should update the weights", epoch);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn test_checkpoint_on_best_keeps_the_lowest_loss_epoch() {
+    let temp_dir = TempDir::new().unwrap();
+    let checkpoint_path = temp_dir.path().join("best.json");
+    let example = neural_network::examples::get_example("and").unwrap();
+    let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 3);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 200,
+        checkpoint_path: Some(checkpoint_path.clone()),
+        checkpoint_on_best: true,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+
+    // Wreck the network partway through, and stop it recovering, so the
+    // loss gets worse
+    controller.add_pre_epoch_callback(Box::new(|epoch, network| {
+        if epoch == 150 {
+            for weights in &mut network.weights {
+                weights.data.iter_mut().for_each(|w| *w = -*w * 3.0);
+            }
+            network.learning_rate = 0.0;
+        }
+    }));
+    let snapshots = Arc::new(Mutex::new(Vec::new()));
+    let snapshots_clone = snapshots.clone();
+    controller.add_callback(Box::new(move |_epoch, _loss, network| {
+        snapshots_clone.lock().unwrap().push(network.clone());
+    }));
+
+    let report = controller.train(example.inputs.clone(), example.targets).unwrap();
+
+    let (best_index, best) = report
+        .history
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.loss.total_cmp(&b.1.loss))
+        .unwrap();
+    assert!(report.final_loss > best.loss, "The loss should have worsened: {:?}", report.history);
+    assert_eq!(controller.best_loss(), Some(best.loss));
+
+    let (saved, metadata) = Network::load_checkpoint(&checkpoint_path).unwrap();
+    assert_eq!(metadata.epoch, best.epoch);
+    let best_network = &snapshots.lock().unwrap()[best_index];
+    for input in &example.inputs {
+        assert_eq!(saved.predict(input).unwrap(), best_network.predict(input).unwrap());
+    }
+}
+
+#[test]
+fn test_checkpoint_on_best_conflicts_with_interval() {
+    let temp_dir = TempDir::new().unwrap();
+    let network = Network::new(vec![2, 2, 1], SIGMOID, 0.5);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 10,
+        checkpoint_interval: Some(5),
+        checkpoint_path: Some(temp_dir.path().join("best.json")),
+        checkpoint_on_best: true,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let error = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap_err();
+
+    assert!(error.to_string().contains("can't be combined"), "{}", error);
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn test_save_history_covers_every_training_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let history_path = temp_dir.path().join("history.json");
+    let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 20,
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let first = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+    let second = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+    assert_eq!(second.history.len(), 20, "Reports only cover their own run");
+    controller.save_history(&history_path).unwrap();
+
+    let json = std::fs::read_to_string(&history_path).unwrap();
+    let saved: Vec<neural_network::training::TrainingProgress> = serde_json::from_str(&json).unwrap();
+    assert_eq!(saved.len(), 40);
+    assert_eq!(saved[..20], first.history[..]);
+    assert_eq!(saved[20..], second.history[..]);
+    assert_eq!(saved[0].learning_rate, 0.5);
+}
+
+#[test]
+#[cfg(feature = "fs")]
+fn test_metrics_log_has_one_json_line_per_epoch() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("metrics.jsonl");
+    let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 25,
+        metrics_log_path: Some(log_path.clone()),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let lines_seen = Arc::new(Mutex::new(Vec::new()));
+    let lines_clone = lines_seen.clone();
+    let path_clone = log_path.clone();
+    controller.add_callback(Box::new(move |_epoch, _loss, _network| {
+        // Earlier epochs are already on disk while training runs
+        let contents = std::fs::read_to_string(&path_clone).unwrap_or_default();
+        lines_clone.lock().unwrap().push(contents.lines().count());
+    }));
+    let report = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap();
+
+    let contents = std::fs::read_to_string(&log_path).unwrap();
+    let logged: Vec<neural_network::training::TrainingProgress> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(logged.len(), 25);
+    assert_eq!(logged, report.history);
+    assert_eq!(*lines_seen.lock().unwrap(), (0..25).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_nesterov_reaches_target_loss_faster_than_classic_momentum() {
+    let example = neural_network::examples::get_example("parity3").unwrap();
+    let epochs_to_target = |nesterov: bool, seed: u64| {
+        let network = Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.2, seed);
+        let config = neural_network::training::TrainingConfig {
+            epochs: 4000,
+            target_loss: Some(0.01),
+            optimizer: neural_network::optimizer::Optimizer::Momentum { beta: 0.9, nesterov },
+            ..Default::default()
+        };
+        let mut controller = neural_network::training::TrainingController::new(network, config);
+        let report = controller.train(example.inputs.clone(), example.targets.clone()).unwrap();
+        assert!(report.target_reached, "seed {} nesterov {} ended at loss {}", seed, nesterov, report.final_loss);
+        report.epochs_run
+    };
+
+    // Individual seeds vary, so compare the total over several
+    let classic: u32 = (1..=5).map(|seed| epochs_to_target(false, seed)).sum();
+    let nesterov: u32 = (1..=5).map(|seed| epochs_to_target(true, seed)).sum();
+
+    assert!(nesterov < classic, "Nesterov took {} epochs, classic momentum {}", nesterov, classic);
+}
+
+#[test]
+fn test_cross_validate_returns_one_loss_per_fold() {
+    let example = neural_network::examples::get_example("and").unwrap();
+    let config = neural_network::training::TrainingConfig {
+        epochs: 200,
+        ..Default::default()
+    };
+    let factory = || Network::new_seeded(example.recommended_arch.clone(), SIGMOID, 0.5, 1);
+
+    let losses =
+        neural_network::training::cross_validate(factory, &example.inputs, &example.targets, 2, &config)
+            .unwrap();
+
+    assert_eq!(losses.len(), 2);
+    assert!(losses.iter().all(|loss| loss.is_finite() && *loss >= 0.0), "{:?}", losses);
+
+    for k in [1, 5] {
+        let error =
+            neural_network::training::cross_validate(factory, &example.inputs, &example.targets, k, &config)
+                .unwrap_err();
+        assert!(error.to_string().contains("between 2 and 4 folds"), "{}", error);
+    }
+
+    // Every fold would write to the same file
+    let configs = [
+        neural_network::training::TrainingConfig {
+            checkpoint_interval: Some(10),
+            checkpoint_path: Some("fold.json".into()),
+            ..config.clone()
+        },
+        neural_network::training::TrainingConfig {
+            metrics_log_path: Some("folds.jsonl".into()),
+            ..config.clone()
+        },
+    ];
+    for config in configs {
+        let error =
+            neural_network::training::cross_validate(factory, &example.inputs, &example.targets, 2, &config)
+                .unwrap_err();
+        assert!(error.to_string().contains("every fold would write to"), "{}", error);
+    }
+}
+
+#[test]
+fn test_ema_weights_are_close_to_final_weights() {
+    let example = neural_network::examples::get_example("xor").unwrap();
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 3);
+    let config = neural_network::training::TrainingConfig {
+        epochs: 2000,
+        weight_ema_decay: Some(0.99),
+        ..Default::default()
+    };
+
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    controller.train(example.inputs.clone(), example.targets.clone()).unwrap();
+    let raw = controller.network().clone();
+    controller.apply_ema_weights().unwrap();
+    let averaged = controller.network().clone();
+
+    assert_ne!(averaged.weights, raw.weights);
+    for input in &example.inputs {
+        let (a, b) = (raw.predict(input).unwrap()[0], averaged.predict(input).unwrap()[0]);
+        assert!(a != b && (a - b).abs() < 0.05, "raw {} vs averaged {}", a, b);
+    }
+
+    // Applying again swaps the raw weights back
+    controller.apply_ema_weights().unwrap();
+    assert_eq!(controller.network().weights, raw.weights);
+}
+
+#[test]
+fn test_apply_ema_weights_needs_an_average() {
+    let network = Network::new_seeded(vec![2, 2, 1], SIGMOID, 0.5, 1);
+    let mut controller = neural_network::training::TrainingController::new(network.clone(), Default::default());
+    assert!(controller.apply_ema_weights().is_err());
+
+    let config = neural_network::training::TrainingConfig {
+        epochs: 1,
+        weight_ema_decay: Some(1.0),
+        ..Default::default()
+    };
+    let mut controller = neural_network::training::TrainingController::new(network, config);
+    let error = controller.train(vec![vec![0.0, 1.0]], vec![vec![1.0]]).unwrap_err();
+    assert!(error.to_string().contains("weight_ema_decay"), "{}", error);
+}
+
+#[test]
+fn test_best_model_tracker_keeps_the_lowest_loss_network() {
+    use neural_network::callbacks::best_model_tracker;
+    use neural_network::examples;
+    use neural_network::training::{TrainingConfig, TrainingController};
+
+    let xor = examples::get_example("xor").unwrap();
+    let mse = |network: &Network| -> f64 {
+        xor.inputs
+            .iter()
+            .zip(&xor.targets)
+            .map(|(input, target)| {
+                let output = network.predict(input).unwrap();
+                output.iter().zip(target).map(|(o, t)| (o - t) * (o - t)).sum::<f64>()
+            })
+            .sum::<f64>()
+            / xor.inputs.len() as f64
+    };
+
+    let network = Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 7);
+    let config = TrainingConfig {
+        epochs: 1010,
+        ..Default::default()
+    };
+    let mut controller = TrainingController::new(network, config);
+    // Flipping the output weights late in the run makes the loss jump
+    controller.add_pre_epoch_callback(Box::new(|epoch, network| {
+        if epoch == 1001 {
+            let output = network.weights.len() - 1;
+            network.weights[output] = network.weights[output].map(|w| -w);
+        }
+    }));
+    let (callback, best) = best_model_tracker();
+    controller.add_callback(callback);
+    let report = controller.train(xor.inputs.clone(), xor.targets.clone()).unwrap();
+
+    let snapshot = best.snapshot().expect("Should track a best model");
+    assert!(snapshot.epoch <= 1000, "Best epoch {} should precede the jump", snapshot.epoch);
+    assert_eq!(best.loss(), controller.best_loss());
+    assert!(snapshot.loss < report.final_loss);
+
+    let final_network = controller.into_network();
+    let best_network = best.network().unwrap();
+    assert!((mse(&best_network) - snapshot.loss).abs() < 1e-12);
+    assert!(mse(&best_network) < mse(&final_network));
+}
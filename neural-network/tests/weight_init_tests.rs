@@ -0,0 +1,64 @@
+use neural_network::activations::{RELU, SIGMOID};
+use neural_network::init::WeightInit;
+use neural_network::network::Network;
+
+/// Mean and population standard deviation of every weight
+fn weight_stats(network: &Network) -> (f64, f64) {
+    let weights: Vec<f64> = network.weights.iter().flat_map(|w| w.data.iter().copied()).collect();
+    let mean = weights.iter().sum::<f64>() / weights.len() as f64;
+    let variance = weights.iter().map(|w| (w - mean) * (w - mean)).sum::<f64>() / weights.len() as f64;
+    (mean, variance.sqrt())
+}
+
+#[test]
+fn test_normal_init_matches_requested_std() {
+    let init = WeightInit::Normal { std: 0.2 };
+    let network = Network::new_with_init(vec![100, 200, 50], SIGMOID, 0.5, init, 1).unwrap();
+
+    // 30,000 samples: the standard error of the mean is about 0.001
+    let (mean, std) = weight_stats(&network);
+    assert!(mean.abs() < 0.01, "mean {}", mean);
+    assert!((std - 0.2).abs() < 0.01, "std {}", std);
+}
+
+#[test]
+fn test_uniform_range_init_matches_requested_bounds() {
+    let init = WeightInit::UniformRange { low: -0.5, high: 1.5 };
+    let network = Network::new_with_init(vec![100, 200, 50], SIGMOID, 0.5, init, 2).unwrap();
+
+    let all = network.weights.iter().chain(&network.biases).flat_map(|m| &m.data);
+    assert!(all.into_iter().all(|&w| (-0.5..1.5).contains(&w)));
+    // Uniform on [a, b): mean (a + b) / 2 and std (b - a) / sqrt(12)
+    let (mean, std) = weight_stats(&network);
+    assert!((mean - 0.5).abs() < 0.02, "mean {}", mean);
+    assert!((std - 2.0 / 12f64.sqrt()).abs() < 0.01, "std {}", std);
+}
+
+#[test]
+fn test_heuristics_scale_with_layer_size() {
+    let network = Network::new_with_init(vec![400, 300], SIGMOID, 0.5, WeightInit::Xavier, 3).unwrap();
+    let limit = (6.0f64 / 700.0).sqrt();
+    assert!(network.weights[0].data.iter().all(|w| w.abs() <= limit));
+    assert!(network.biases[0].data.iter().all(|&b| b == 0.0));
+
+    let network = Network::new_with_init(vec![200, 300], RELU, 0.5, WeightInit::He, 4).unwrap();
+    let (mean, std) = weight_stats(&network);
+    assert!(mean.abs() < 0.01, "mean {}", mean);
+    assert!((std - 0.1).abs() < 0.005, "std {}", std);
+}
+
+#[test]
+fn test_init_is_seeded_and_validated() {
+    let init = WeightInit::Normal { std: 1.0 };
+    let a = Network::new_with_init(vec![2, 3, 1], SIGMOID, 0.5, init, 9).unwrap();
+    let b = Network::new_with_init(vec![2, 3, 1], SIGMOID, 0.5, init, 9).unwrap();
+    assert_eq!(a.weights, b.weights);
+
+    // The default matches `new_seeded`
+    let uniform = Network::new_with_init(vec![2, 3, 1], SIGMOID, 0.5, WeightInit::default(), 9).unwrap();
+    assert_eq!(uniform.weights, Network::new_seeded(vec![2, 3, 1], SIGMOID, 0.5, 9).weights);
+
+    let bad = WeightInit::UniformRange { low: 1.0, high: 1.0 };
+    assert!(Network::new_with_init(vec![2, 1], SIGMOID, 0.5, bad, 0).is_err());
+    assert!(Network::new_with_init(vec![2, 1], SIGMOID, 0.5, WeightInit::Normal { std: -1.0 }, 0).is_err());
+}